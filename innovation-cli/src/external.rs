@@ -0,0 +1,214 @@
+//! `referee` subcommand: games between bot processes speaking the engine protocol
+//! (`innovation_core::protocol`) on their stdin/stdout, possibly against built-in agents.
+
+use innovation_core::actions::Action;
+use innovation_core::agent::{self, Agent, Observation};
+use innovation_core::game_state::{EndCondition, GamePhase, GameState};
+use innovation_core::protocol::{self, BotMessage, EngineMessage};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+/// Prefix of `--engine` values naming a built-in agent instead of a command.
+pub const BUILTIN_PREFIX: &str = "builtin:";
+
+pub struct RefereeOptions {
+    pub handshake_timeout: Duration,
+    pub move_timeout: Duration,
+    /// Illegal or malformed answers tolerated per seat and game; one more forfeits.
+    pub max_illegal: usize,
+    pub max_steps: usize,
+}
+
+/// A bot process, its stdout read line by line on a background thread.
+pub struct ExternalBot {
+    /// Name given at the handshake; the command line until then.
+    pub name: String,
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl ExternalBot {
+    pub fn launch(command: &str) -> Result<Self, String> {
+        #[cfg(windows)]
+        let mut cmd = {
+            let mut cmd = Command::new("cmd");
+            cmd.arg("/C").arg(command);
+            cmd
+        };
+        #[cfg(not(windows))]
+        let mut cmd = {
+            let mut cmd = Command::new("sh");
+            cmd.arg("-c").arg(command);
+            cmd
+        };
+        let mut child = cmd.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| format!("cannot launch '{}': {}", command, e))?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { name: command.to_string(), child, stdin, lines })
+    }
+
+    fn send(&mut self, message: &EngineMessage) -> Result<(), String> {
+        writeln!(self.stdin, "{}", message.to_line())
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("cannot write to bot: {}", e))
+    }
+
+    /// Next non-empty line, as a message; the outer error means the bot is gone or too slow.
+    fn receive(&self, deadline: Instant) -> Result<Result<BotMessage, String>, String> {
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            match self.lines.recv_timeout(left) {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Ok(BotMessage::parse(&line)),
+                Err(RecvTimeoutError::Timeout) => return Err("timed out".to_string()),
+                Err(RecvTimeoutError::Disconnected) => return Err("exited".to_string()),
+            }
+        }
+    }
+
+    fn handshake(&mut self, game: &GameState, seat: usize, timeout: Duration) -> Result<(), String> {
+        self.send(&EngineMessage::hello(game, seat))?;
+        match self.receive(Instant::now() + timeout)? {
+            Ok(BotMessage::Ready { name }) => {
+                self.name = name;
+                Ok(())
+            }
+            Ok(other) => Err(format!("expected ready, got {:?}", other)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Drop for ExternalBot {
+    fn drop(&mut self) {
+        let _ = self.send(&EngineMessage::Quit);
+        let deadline = Instant::now() + Duration::from_millis(500);
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+pub enum Seat {
+    External(ExternalBot),
+    Builtin(Box<dyn Agent>),
+}
+
+impl Seat {
+    /// Launches `spec`: a command line, or `builtin:<agent spec>`.
+    pub fn launch(spec: &str, seed: u64) -> Result<Self, String> {
+        match spec.strip_prefix(BUILTIN_PREFIX) {
+            Some(agent) => agent::from_spec(agent, seed).map(Seat::Builtin).map_err(|e| e.to_string()),
+            None => ExternalBot::launch(spec).map(Seat::External),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Seat::External(bot) => bot.name.clone(),
+            Seat::Builtin(agent) => format!("{}{}", BUILTIN_PREFIX, agent.name()),
+        }
+    }
+}
+
+pub struct Outcome {
+    pub winners: Vec<usize>,
+    pub end: Option<EndCondition>,
+    /// Seat that forfeited and why.
+    pub forfeit: Option<(usize, String)>,
+    pub actions: usize,
+}
+
+/// Plays `game` to the end between `seats`, refereeing the external ones.
+pub fn referee(game: &mut GameState, seats: &mut [Seat], options: &RefereeOptions) -> Outcome {
+    let mut outcome = Outcome { winners: Vec::new(), end: None, forfeit: None, actions: 0 };
+    for (pid, seat) in seats.iter_mut().enumerate() {
+        if let Seat::External(bot) = seat {
+            if let Err(e) = bot.handshake(game, pid, options.handshake_timeout) {
+                outcome.forfeit = Some((pid, format!("handshake failed: {}", e)));
+            }
+        }
+        if outcome.forfeit.is_some() {
+            break;
+        }
+    }
+
+    let mut strikes = vec![0usize; seats.len()];
+    let mut id = 0u64;
+    while outcome.forfeit.is_none() && game.phase != GamePhase::GameOver && outcome.actions < options.max_steps {
+        let actor = game.acting_player();
+        id += 1;
+        let action = match &mut seats[actor] {
+            Seat::Builtin(agent) => Ok(agent.choose(&Observation::new(game, actor))),
+            Seat::External(bot) => ask(bot, game, actor, id, &mut strikes[actor], options),
+        };
+        match action.and_then(|action| game.apply_action(action).map_err(|e| e.to_string())) {
+            Ok(()) => outcome.actions += 1,
+            Err(e) => outcome.forfeit = Some((actor, e)),
+        }
+    }
+
+    if let Some((loser, _)) = &outcome.forfeit {
+        outcome.winners = (0..seats.len()).filter(|&pid| !game.config.teammates(pid, *loser)).collect();
+    } else {
+        outcome.winners = game.winners.clone();
+        outcome.end = game.end_condition;
+    }
+    let over = EngineMessage::GameOver {
+        winners: outcome.winners.clone(),
+        end: outcome.end,
+        forfeit: outcome.forfeit.as_ref().map(|(pid, _)| *pid),
+    };
+    for seat in seats.iter_mut() {
+        if let Seat::External(bot) = seat {
+            let _ = bot.send(&over);
+        }
+    }
+    outcome
+}
+
+/// Sends request `id` until the bot answers with a legal action or runs out of strikes.
+fn ask(bot: &mut ExternalBot, game: &GameState, seat: usize, id: u64, strikes: &mut usize, options: &RefereeOptions) -> Result<Action, String> {
+    let request = EngineMessage::request(id, game, seat);
+    loop {
+        bot.send(&request)?;
+        let deadline = Instant::now() + options.move_timeout;
+        let refusal = loop {
+            match bot.receive(deadline)? {
+                Ok(BotMessage::Action { id: answered, action }) if answered == id => {
+                    match protocol::validate(game, &action) {
+                        Ok(()) => return Ok(action),
+                        Err(e) => break format!("illegal action {:?}: {}", action, e),
+                    }
+                }
+                // Late answers to earlier requests and repeated greetings are ignored
+                Ok(_) => continue,
+                Err(e) => break e,
+            }
+        };
+        *strikes += 1;
+        if *strikes > options.max_illegal {
+            return Err(refusal);
+        }
+        bot.send(&EngineMessage::Error { id, message: refusal })?;
+    }
+}
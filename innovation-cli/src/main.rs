@@ -1,6 +1,20 @@
 use innovation_core::{load_all_cards, Color, GameState, actions::Action};
+use innovation_core::agent::{self, Agent, Observation};
+use innovation_core::analysis::{self, AnalysisConfig, Evaluator, MomentKind, Replay};
+use innovation_core::config::{GameConfig, HouseRule};
+use innovation_core::dogma::coverage::{self, DogmaSource};
+use innovation_core::dogma::explain::{self, Lang};
+use innovation_core::dogma::registry;
+use innovation_core::rules::Ruleset;
+use innovation_core::model::Expansion;
+use innovation_core::solver::{self, SolverConfig};
+use innovation_core::ScenarioBuilder;
 use std::collections::HashMap;
 use clap::{Parser, Subcommand};
+
+mod external;
+mod tournament;
+
 use rustyline::completion::{Completer, Pair};
 use rustyline::error::ReadlineError;
 use rustyline::highlight::Highlighter;
@@ -18,8 +32,12 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Verify database integrity and stats
-    Verify,
+    /// Verify database integrity and stats; exits with 1 if the card data has problems
+    Verify {
+        /// Language of the engine interpretation of the dogmas: en or fr
+        #[arg(long, default_value = "fr")]
+        lang: String,
+    },
     /// Run a game simulation
     Simulate {
         #[arg(short, long, default_value_t = 12345)]
@@ -28,6 +46,140 @@ enum Commands {
         players: usize,
         #[arg(short = 'a', long, default_value_t = 10)]
         max_age: u8,
+        /// Achievements needed to win (default depends on the player count)
+        #[arg(long)]
+        achievements: Option<usize>,
+        /// Skip the initial melds and start directly in Main phase
+        #[arg(long)]
+        skip_setup: bool,
+        /// House rule: every player gets two actions on the first turn
+        #[arg(long)]
+        no_first_turn_limit: bool,
+        /// House rule: no free draw when an opponent shares a dogma
+        #[arg(long)]
+        no_sharing_bonus: bool,
+        /// Team game (4 players): seats 0 and 2 against seats 1 and 3
+        #[arg(long)]
+        teams: bool,
+        /// Rules edition: standard or third-edition
+        #[arg(long, default_value = "standard")]
+        rules: String,
+        /// Expansion to play with: echoes, figures, cities or artifacts (repeatable; some ship only part of their cards, see docs/extensions.md)
+        #[arg(long = "expansion", value_name = "NAME")]
+        expansions: Vec<String>,
+        /// Seat played by the computer, e.g. P1=random or P1=ismcts:iterations=200 (repeatable)
+        #[arg(long = "bot", value_name = "SEAT=AGENT")]
+        bots: Vec<String>,
+        /// Write the actions played to this file, for the analyze command
+        #[arg(long, value_name = "FILE")]
+        replay: Option<String>,
+        /// Print the post-game analysis once the game is over
+        #[arg(long)]
+        analyze: bool,
+    },
+    /// Play bot games between agent configurations and report statistics
+    Tournament {
+        /// Agent configuration, e.g. random, heuristic:difficulty=easy, ismcts:iterations=200 (repeatable)
+        #[arg(long = "agent", value_name = "AGENT", required = true)]
+        agents: Vec<String>,
+        /// Games per player count
+        #[arg(short = 'n', long, default_value_t = 100)]
+        games: usize,
+        /// Seed of the first game; each game uses the next one
+        #[arg(short, long, default_value_t = 1)]
+        seed: u64,
+        /// Player counts to play, e.g. 2,3,4
+        #[arg(short, long, value_delimiter = ',', default_value = "2")]
+        players: Vec<usize>,
+        #[arg(short = 'a', long, default_value_t = 10)]
+        max_age: u8,
+        /// Actions after which a game counts as unfinished
+        #[arg(long, default_value_t = 5000)]
+        max_steps: usize,
+        /// Games played in parallel (0: one per core)
+        #[arg(short, long, default_value_t = 0)]
+        threads: usize,
+        /// Also write the report as JSON to this file ("-" for stdout, instead of the table)
+        #[arg(long, value_name = "FILE")]
+        json: Option<String>,
+    },
+    /// Referee games between bot processes speaking the JSON engine protocol
+    Referee {
+        /// Bot command line, one per seat in seat order; builtin:<agent> plays a built-in agent
+        #[arg(long = "engine", value_name = "COMMAND", required = true)]
+        engines: Vec<String>,
+        /// Games to play; seats rotate from one game to the next
+        #[arg(short = 'n', long, default_value_t = 1)]
+        games: usize,
+        #[arg(short, long, default_value_t = 1)]
+        seed: u64,
+        #[arg(short = 'a', long, default_value_t = 10)]
+        max_age: u8,
+        #[arg(long, default_value_t = 10000)]
+        handshake_timeout_ms: u64,
+        #[arg(long, default_value_t = 5000)]
+        move_timeout_ms: u64,
+        /// Illegal answers tolerated per seat and game before it forfeits
+        #[arg(long, default_value_t = 3)]
+        max_illegal: usize,
+        #[arg(long, default_value_t = 5000)]
+        max_steps: usize,
+    },
+    /// Learn weights for the 'learned' agent by self-play (TD learning)
+    Train {
+        /// Self-play games
+        #[arg(short = 'n', long, default_value_t = 1000)]
+        games: usize,
+        #[arg(short, long, default_value_t = 1)]
+        seed: u64,
+        #[arg(short, long, default_value_t = 2)]
+        players: usize,
+        #[arg(short = 'a', long, default_value_t = 10)]
+        max_age: u8,
+        #[arg(long, default_value_t = 2000)]
+        max_steps: usize,
+        #[arg(long, default_value_t = 0.05)]
+        learning_rate: f64,
+        /// Trace decay of TD(lambda)
+        #[arg(long, default_value_t = 0.7)]
+        lambda: f64,
+        /// Share of random moves
+        #[arg(long, default_value_t = 0.1)]
+        exploration: f64,
+        /// Weights to start from (default: untrained)
+        #[arg(long, value_name = "FILE")]
+        init: Option<String>,
+        /// Where to write the weights, for learned:weights=FILE
+        #[arg(short, long, value_name = "FILE", default_value = "weights.json")]
+        output: String,
+    },
+    /// Search a scenario (JSON file) exhaustively for a forced win of the seat to act
+    Solve {
+        /// Scenario file, in the format of ScenarioBuilder::from_json
+        scenario: String,
+        /// Most actions searched ahead, input answers included
+        #[arg(short, long, default_value_t = 6)]
+        depth: usize,
+        /// Positions searched before giving up on deeper searches
+        #[arg(long, default_value_t = 2_000_000)]
+        max_nodes: u64,
+    },
+    /// Rate every position of a finished game and point out its key moves
+    Analyze {
+        /// Replay file, as written by simulate --replay
+        replay: String,
+        /// ISMCTS iterations per position (0: rate positions with the heuristic)
+        #[arg(short, long, default_value_t = 0)]
+        iterations: usize,
+        /// Most moves pointed out
+        #[arg(short, long, default_value_t = 5)]
+        moments: usize,
+        /// Smallest change of the mover's win chance worth pointing out, from 0 to 1
+        #[arg(short, long, default_value_t = 0.1)]
+        threshold: f64,
+        /// Write the analysis as JSON to this file ("-" for stdout, instead of the report)
+        #[arg(long, value_name = "FILE")]
+        json: Option<String>,
     },
 }
 
@@ -35,12 +187,325 @@ fn main() {
     let cli = Cli::parse();
 
     match &cli.command {
-        Commands::Verify => verify_db(),
-        Commands::Simulate { seed, players, max_age } => simulate_game(*seed, *players, *max_age),
+        Commands::Verify { lang } => {
+            let Some(lang) = Lang::by_name(lang) else {
+                eprintln!("Unknown language '{}' (expected one of: {})", lang, Lang::NAMES.join(", "));
+                std::process::exit(2);
+            };
+            if verify_db(lang) > 0 {
+                std::process::exit(1);
+            }
+        }
+        Commands::Train { games, seed, players, max_age, max_steps, learning_rate, lambda, exploration, init, output } => {
+            let config = agent::TrainingConfig {
+                games: *games,
+                seed: *seed,
+                player_count: *players,
+                max_age: *max_age,
+                max_steps: *max_steps,
+                learning_rate: *learning_rate,
+                lambda: *lambda,
+                exploration: *exploration,
+            };
+            train_weights(&config, init.as_deref(), output);
+        }
+        Commands::Solve { scenario, depth, max_nodes } => {
+            let config = SolverConfig { depth: *depth, max_nodes: *max_nodes, ..SolverConfig::default() };
+            solve_scenario(scenario, &config);
+        }
+        Commands::Analyze { replay, iterations, moments, threshold, json } => {
+            let evaluator = match iterations {
+                0 => Evaluator::Heuristic(agent::HeuristicWeights::hard()),
+                &n => Evaluator::Ismcts(agent::IsmctsConfig { budget: agent::Budget::Iterations(n), ..agent::IsmctsConfig::default() }),
+            };
+            let config = AnalysisConfig { evaluator, moments: *moments, threshold: *threshold, ..AnalysisConfig::default() };
+            analyze_replay(replay, &config, json.as_deref());
+        }
+        Commands::Referee { engines, games, seed, max_age, handshake_timeout_ms, move_timeout_ms, max_illegal, max_steps } => {
+            let options = external::RefereeOptions {
+                handshake_timeout: std::time::Duration::from_millis(*handshake_timeout_ms),
+                move_timeout: std::time::Duration::from_millis(*move_timeout_ms),
+                max_illegal: *max_illegal,
+                max_steps: *max_steps,
+            };
+            referee_games(engines, *games, *seed, *max_age, &options);
+        }
+        Commands::Tournament { agents, games, seed, players, max_age, max_steps, threads, json } => {
+            for spec in agents {
+                if let Err(e) = agent::from_spec(spec, 0) {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }
+            }
+            let options = tournament::TournamentOptions {
+                agents: agents.clone(),
+                games: *games,
+                seed: *seed,
+                player_counts: players.clone(),
+                max_age: *max_age,
+                max_steps: *max_steps,
+                threads: *threads,
+            };
+            let report = tournament::run(&options);
+            let encoded = serde_json::to_string_pretty(&report).expect("report serializes");
+            match json.as_deref() {
+                Some("-") => println!("{}", encoded),
+                Some(path) => {
+                    tournament::print_table(&report);
+                    if let Err(e) = std::fs::write(path, encoded) {
+                        eprintln!("Cannot write {}: {}", path, e);
+                        std::process::exit(1);
+                    }
+                }
+                None => tournament::print_table(&report),
+            }
+        }
+        Commands::Simulate { seed, players, max_age, achievements, skip_setup, no_first_turn_limit, no_sharing_bonus, teams, rules, expansions, bots, replay, analyze } => {
+            let Some(ruleset) = Ruleset::by_name(rules) else {
+                eprintln!("Unknown rules '{}' (expected one of: {})", rules, Ruleset::NAMES.join(", "));
+                std::process::exit(2);
+            };
+            let mut expansion_list = Vec::new();
+            for name in expansions {
+                let Some(expansion) = Expansion::by_name(name) else {
+                    eprintln!("Unknown expansion '{}' (expected one of: {})", name, Expansion::NAMES.join(", "));
+                    std::process::exit(2);
+                };
+                expansion_list.push(expansion);
+            }
+            let config = GameConfig::new(*players).map(|mut config| {
+                config = config.ruleset(ruleset);
+                config = config.max_age(*max_age).skip_setup(*skip_setup).team_play(*teams);
+                for &expansion in &expansion_list {
+                    config = config.expansion(expansion);
+                }
+                if let Some(count) = achievements {
+                    config = config.achievements_needed(*count);
+                }
+                if *no_first_turn_limit {
+                    config = config.house_rule(HouseRule::NoFirstTurnLimit);
+                }
+                if *no_sharing_bonus {
+                    config = config.house_rule(HouseRule::NoSharingBonus);
+                }
+                config
+            });
+            let seats = match parse_bots(bots, *players, *seed) {
+                Ok(seats) => seats,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }
+            };
+            if let Ok(config) = &config {
+                for coverage in config.partial_expansions() {
+                    eprintln!("Warning: expansion {:?} is partial ({} of {} published cards)", coverage.expansion, coverage.cards, coverage.published);
+                }
+            }
+            match config.and_then(|config| GameState::from_config(*seed, config)) {
+                Ok(game) => simulate_game(*seed, game, seats, replay.as_deref(), *analyze),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }
+            }
+        }
+    }
+}
+
+fn referee_games(engines: &[String], games: usize, seed: u64, max_age: u8, options: &external::RefereeOptions) {
+    let mut wins = vec![0.0f64; engines.len()];
+    for game_idx in 0..games {
+        let game_seed = seed.wrapping_add(game_idx as u64);
+        let lineup: Vec<usize> = (0..engines.len()).map(|pid| (game_idx + pid) % engines.len()).collect();
+        let mut game = match GameConfig::new(engines.len()).and_then(|c| GameState::from_config(game_seed, c.max_age(max_age))) {
+            Ok(game) => game,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(2);
+            }
+        };
+        let mut seats = Vec::new();
+        for (pid, &idx) in lineup.iter().enumerate() {
+            match external::Seat::launch(&engines[idx], game_seed.wrapping_add(pid as u64 + 1)) {
+                Ok(seat) => seats.push(seat),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(2);
+                }
+            }
+        }
+        let outcome = external::referee(&mut game, &mut seats, options);
+        let names: Vec<String> = seats.iter().enumerate().map(|(pid, seat)| format!("P{}={}", pid, seat.name())).collect();
+        print!("Game {} (seed {}), {}: ", game_idx + 1, game_seed, names.join(" "));
+        match (&outcome.forfeit, outcome.end) {
+            (Some((pid, reason)), _) => println!("P{} forfeits ({}), winners {:?}", pid, reason, outcome.winners),
+            (None, Some(end)) => println!("winners {:?} by {:?} after {} actions", outcome.winners, end, outcome.actions),
+            (None, None) => println!("unfinished after {} actions", outcome.actions),
+        }
+        for &pid in &outcome.winners {
+            wins[lineup[pid]] += 1.0 / outcome.winners.len() as f64;
+        }
+    }
+    println!("\nWins:");
+    for (engine, won) in engines.iter().zip(&wins) {
+        println!("  {:.1}/{}  {}", won, games, engine);
+    }
+}
+
+/// Parses `--bot` specs (`P1=random`) into one optional agent per seat.
+/// Each bot is seeded from the game seed and its seat.
+fn parse_bots(specs: &[String], players: usize, seed: u64) -> Result<Vec<Option<Box<dyn Agent>>>, String> {
+    let mut seats: Vec<Option<Box<dyn Agent>>> = (0..players).map(|_| None).collect();
+    for spec in specs {
+        let (seat, name) = spec.split_once('=').ok_or_else(|| format!("Invalid bot '{}' (expected P1=random)", spec))?;
+        let seat: usize = seat.trim_start_matches(['P', 'p']).parse()
+            .map_err(|_| format!("Invalid seat in '{}'", spec))?;
+        if seat >= players {
+            return Err(format!("Seat P{} does not exist with {} players", seat, players));
+        }
+        let bot_seed = seed.wrapping_add(seat as u64 + 1);
+        let bot = agent::from_spec(name, bot_seed).map_err(|e| e.to_string())?;
+        seats[seat] = Some(bot);
+    }
+    Ok(seats)
+}
+
+fn train_weights(config: &agent::TrainingConfig, init: Option<&str>, output: &str) {
+    let weights = match init.map(agent::LearnedWeights::load).transpose() {
+        Ok(weights) => weights.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    let start = std::time::Instant::now();
+    let report_every = (config.games / 10).max(1);
+    let trained = agent::train(config, weights, |done, _| {
+        if done % report_every == 0 || done == config.games {
+            println!("{}/{} games ({:.1}s)", done, config.games, start.elapsed().as_secs_f64());
+        }
+    });
+    let trained = match trained {
+        Ok(trained) => trained,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = std::fs::write(output, trained.to_json()) {
+        eprintln!("Cannot write {}: {}", output, e);
+        std::process::exit(1);
+    }
+    println!("Weights written to {}", output);
+    for (name, weight) in trained.features.iter().zip(&trained.weights) {
+        println!("  {:<20} {:>8.3}", name, weight);
+    }
+}
+
+fn solve_scenario(path: &str, config: &SolverConfig) {
+    let game = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {}", path, e))
+        .and_then(|json| ScenarioBuilder::from_json(&json).and_then(|s| s.build()).map_err(|e| e.to_string()));
+    let game = match game {
+        Ok(game) => game,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    let solution = solver::solve(&game, config);
+    println!("P{} to act, searched {} positions", solution.seat, solution.nodes);
+    if solution.forced_win() {
+        println!("Forced win in {} action{}", solution.depth, if solution.depth == 1 { "" } else { "s" });
+    } else if solution.lost() {
+        println!("Lost: no line wins (searched {} actions deep)", solution.depth);
+    } else if solution.proven {
+        println!("Win chance {:.1}% with best play (searched {} actions deep)", solution.value * 100.0, solution.depth);
+    } else {
+        println!("No forced win found within {} actions (win chance at least {:.1}%)", solution.depth, solution.value * 100.0);
+    }
+    if solution.truncated {
+        println!("Search stopped at depth {}: node limit of {} reached", solution.depth, config.max_nodes);
+    }
+    println!("\nBest line:");
+    for (idx, action) in solution.best_line.iter().enumerate() {
+        println!("  {}. {:?}", idx + 1, action);
+    }
+    println!("\nMoves (> marks lower bounds):");
+    for m in &solution.moves {
+        println!("  {}{:>5.1}%  {:?}", if m.proven { " " } else { ">" }, m.value * 100.0, m.action);
+    }
+}
+
+fn analyze_replay(path: &str, config: &AnalysisConfig, json: Option<&str>) {
+    let report = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {}", path, e))
+        .and_then(|json| Replay::from_json(&json).and_then(|r| analysis::analyze(&r, config)).map_err(|e| e.to_string()));
+    let report = match report {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    match json {
+        Some("-") => println!("{}", report.to_json()),
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, report.to_json()) {
+                eprintln!("Cannot write {}: {}", path, e);
+                std::process::exit(1);
+            }
+            print_analysis(&report);
+        }
+        None => print_analysis(&report),
+    }
+}
+
+fn print_analysis(report: &analysis::Analysis) {
+    let seats = |pids: &[usize]| pids.iter().map(|p| format!("P{}", p)).collect::<Vec<_>>().join(", ");
+    println!("\n=== Game analysis ===");
+    if report.winners.is_empty() {
+        println!("The game did not end.");
+    } else {
+        println!("Winners: {}", seats(&report.winners));
+    }
+    println!("\nKey moves:");
+    if report.moments.is_empty() {
+        println!("  None: no move changed its player's chances much.");
+    }
+    for m in &report.moments {
+        let kind = match m.kind {
+            MomentKind::Blunder => "blunder",
+            MomentKind::TurningPoint => "turning point",
+        };
+        println!("  Turn {:>3}, P{} {:?}: {}, win {:.0}% -> {:.0}%", m.turn, m.player, m.action, kind, m.before * 100.0, m.after * 100.0);
+    }
+    let symbols: Vec<String> = report.symbols.iter().map(|s| format!("{:?}", s)).collect();
+    for pid in 0..report.turns.first().map_or(0, |t| t.players.len()) {
+        println!("\nP{}:", pid);
+        println!("  {:>4} {:>5} {:>4} {} {:>5}", "Turn", "Score", "Ach", symbols.iter().map(|s| format!("{:>10}", s)).collect::<String>(), "Win");
+        for turn in &report.turns {
+            let p = &turn.players[pid];
+            let icons: String = p.icons.iter().map(|n| format!("{:>10}", n)).collect();
+            println!("  {:>4} {:>5} {:>4} {} {:>4.0}%", turn.turn, p.score, p.achievements, icons, p.value * 100.0);
+        }
     }
 }
 
-fn verify_db() {
+/// Card data and JSON schema the engine is built with.
+const CARDS_JSON: &str = include_str!("../../data/cards.json");
+const DATA_FILES: [(&str, &str); 5] = [
+    ("cards.json", CARDS_JSON),
+    ("echoes.json", include_str!("../../data/echoes.json")),
+    ("cities.json", include_str!("../../data/cities.json")),
+    ("figures.json", include_str!("../../data/figures.json")),
+    ("artifacts.json", include_str!("../../data/artifacts.json")),
+];
+const SCHEMA_JSON: &str = include_str!("../../data/schema.json");
+
+/// Prints the database report and returns the number of problems found.
+fn verify_db(lang: Lang) -> usize {
     let cards = load_all_cards();
     println!("Total cards loaded: {}", cards.len());
 
@@ -71,6 +536,104 @@ fn verify_db() {
         let total_age: usize = colors.values().sum();
         println!("  Total: {}", total_age);
     }
+
+    // Registry effects next to the printed text, to spot where they drift apart
+    let mut registered: Vec<_> = cards.values()
+        .filter_map(|card| registry::get_effects(&card.name).map(|effects| (card, effects)))
+        .collect();
+    registered.sort_by(|(a, _), (b, _)| (a.age, &a.name).cmp(&(b.age, &b.name)));
+    println!("\nEngine interpretation of {} registered cards:", registered.len());
+    for (card, effects) in registered {
+        println!("\n{} (age {}):", card.name, card.age);
+        for (idx, dogma) in card.dogmas.iter().enumerate() {
+            println!("  Card:   {}", dogma.text.replace('\n', "\n          "));
+            match effects.get(idx) {
+                Some(effect) => println!("  Engine: {}", explain::explain_dogma(effect, dogma.is_supremacy, lang)),
+                None => println!("  Engine: -"),
+            }
+        }
+    }
+
+    verify_expansions();
+    let mut problems = verify_coverage(cards.values().collect());
+    problems += verify_schema();
+    if problems > 0 {
+        println!("\n{} problem(s) found.", problems);
+    }
+    problems
+}
+
+/// Prints how much of each expansion's published set the data files hold.
+fn verify_expansions() {
+    println!("\nExpansion card data:");
+    for expansion in Expansion::NAMES.iter().filter_map(|name| Expansion::by_name(name)) {
+        match innovation_core::db::expansion_coverage(expansion) {
+            Some(coverage) if coverage.is_partial() => {
+                println!("  {:?}: {} of {} published cards (partial)", expansion, coverage.cards, coverage.published);
+            }
+            Some(coverage) => println!("  {:?}: {} cards (complete)", expansion, coverage.cards),
+            None => {
+                let cards = load_all_cards().values().filter(|card| card.expansion == expansion).count();
+                println!("  {:?}: {} cards", expansion, cards);
+            }
+        }
+    }
+}
+
+/// Prints what runs each card's dogmas, and returns the number of problems.
+fn verify_coverage(mut cards: Vec<&innovation_core::model::Card>) -> usize {
+    cards.sort_by(|a, b| (a.expansion as u8, a.age, &a.name).cmp(&(b.expansion as u8, b.age, &b.name)));
+    println!("\nDogma coverage:");
+    let mut counts: HashMap<DogmaSource, usize> = HashMap::new();
+    let mut problems = 0;
+    for card in cards.into_iter().filter(|card| !card.dogmas.is_empty() || !card.karma.is_empty()) {
+        let report = coverage::card_coverage(card);
+        let mut sources: Vec<_> = report.dogmas.iter().map(|source| match source {
+            DogmaSource::Registry => "registry",
+            DogmaSource::Legacy => "legacy",
+            DogmaSource::Missing => "-",
+        }).collect();
+        sources.extend(card.karma.iter().map(|_| "karma"));
+        println!("  {:<32} {:?} {:>2}: {}", card.name, card.expansion, card.age, sources.join(", "));
+        for problem in &report.problems {
+            println!("    ! {}", problem);
+        }
+        for &source in &report.dogmas {
+            *counts.entry(source).or_default() += 1;
+        }
+        problems += report.problems.len();
+    }
+    let count = |source| counts.get(&source).copied().unwrap_or(0);
+    println!("Dogmas: {} registry, {} legacy, {} missing", count(DogmaSource::Registry), count(DogmaSource::Legacy), count(DogmaSource::Missing));
+    problems
+}
+
+/// Validates every card data file against data/schema.json, and returns the number of errors.
+fn verify_schema() -> usize {
+    let parse = |name, json| serde_json::from_str::<serde_json::Value>(json).map_err(|e| format!("{} is not valid JSON: {}", name, e));
+    let validator = parse("schema.json", SCHEMA_JSON).and_then(|schema| {
+        jsonschema::validator_for(&schema).map_err(|e| format!("schema.json is not a valid schema: {}", e))
+    });
+    let mut total = 0;
+    for (name, json) in DATA_FILES {
+        println!("\nSchema validation of data/{}:", name);
+        let errors: Vec<String> = match (&validator, parse(name, json)) {
+            (Ok(validator), Ok(cards)) => validator.iter_errors(&cards).map(|e| {
+                let path = e.instance_path.to_string();
+                format!("{}: {}", if path.is_empty() { "/" } else { &path }, e)
+            }).collect(),
+            (Err(e), _) => vec![e.clone()],
+            (_, Err(e)) => vec![e],
+        };
+        if errors.is_empty() {
+            println!("  OK");
+        }
+        for error in &errors {
+            println!("  ! {}", error);
+        }
+        total += errors.len();
+    }
+    total
 }
 
 // Custom helper for rustyline with dynamic completions
@@ -87,6 +650,7 @@ impl GameHelper {
                 "draw".to_string(), 
                 "dogma".to_string(),
                 "achieve".to_string(),
+                "hint".to_string(),
                 "quit".to_string(),
             ],
             cards: Vec::new(),
@@ -147,12 +711,77 @@ impl Highlighter for GameHelper {}
 impl Validator for GameHelper {}
 impl Helper for GameHelper {}
 
-fn simulate_game(seed: u64, player_count: usize, max_age: u8) {
-    println!("Starting simulation with seed {}, {} players, max age {}...", seed, player_count, max_age);
-    println!("Tip: Use TAB for auto-completion!");
-    let mut game = GameState::new_with_max_age(seed, player_count, max_age);
+/// One line per team, partners side by side.
+fn print_teams(game: &GameState) {
+    let mut shown = Vec::new();
+    for p in &game.players {
+        let team = game.team_members(p.id);
+        if shown.contains(&team) {
+            continue;
+        }
+        let members: Vec<String> = team.iter().map(|&pid| {
+            let member = &game.players[pid];
+            format!("P{}: {} pts, {} ach", pid, member.calculate_score(), member.achievements.len())
+        }).collect();
+        println!("Team {}: {}  (achievements {}/{})", game.config.team_of(p.id), members.join(" | "),
+            game.team_achievements(p.id), game.config.achievements_needed);
+        shown.push(team);
+    }
+}
+
+/// Prints the three best moves of the acting seat, as searched from what it can see.
+fn print_hints(game: &GameState, seed: u64) {
+    let hints = agent::hints(game, agent::IsmctsConfig::default(), seed, 3);
+    if hints.is_empty() {
+        println!("No move to suggest.");
+        return;
+    }
+    let seats = |pids: &[usize]| pids.iter().map(|p| format!("P{}", p)).collect::<Vec<_>>().join(", ");
+    for (i, hint) in hints.iter().enumerate() {
+        let mut line = format!("{}. {:?}  win {:.0}% ({} visits)", i + 1, hint.action, hint.value * 100.0, hint.visits);
+        if let Action::Dogma(_) = hint.action {
+            line += &match hint.sharers.as_slice() {
+                [] => " - not shared".to_string(),
+                pids => format!(" - shared by {}", seats(pids)),
+            };
+            if !hint.demanded.is_empty() {
+                line += &format!("; demand hits {}", seats(&hint.demanded));
+            }
+        }
+        println!("{}", line);
+    }
+}
+
+fn simulate_game(seed: u64, mut game: GameState, bots: Vec<Option<Box<dyn Agent>>>, replay_path: Option<&str>, analyze: bool) {
+    let mut replay = Replay::new(seed, game.config.clone());
+    play_session(seed, &mut game, bots, &mut replay);
+    if let Some(path) = replay_path {
+        match std::fs::write(path, replay.to_json()) {
+            Ok(()) => println!("Replay written to {}", path),
+            Err(e) => eprintln!("Cannot write {}: {}", path, e),
+        }
+    }
+    if analyze && !game.winners.is_empty() {
+        match analysis::analyze(&replay, &AnalysisConfig::default()) {
+            Ok(report) => print_analysis(&report),
+            Err(e) => eprintln!("Cannot analyze the game: {}", e),
+        }
+    }
+}
+
+/// Plays until the game ends or the user quits, recording every action played.
+fn play_session(seed: u64, game: &mut GameState, mut bots: Vec<Option<Box<dyn Agent>>>, replay: &mut Replay) {
+    println!("Starting simulation with seed {}, {} players, max age {}...", seed, game.config.player_count, game.config.max_age);
+    for (pid, bot) in bots.iter().enumerate() {
+        if let Some(bot) = bot {
+            println!("P{} is played by the '{}' bot", pid, bot.name());
+        }
+    }
+    if bots.iter().any(|bot| bot.is_none()) {
+        println!("Tip: Use TAB for auto-completion!");
+    }
     
-    let mut helper = GameHelper::new();
+    let helper = GameHelper::new();
     let mut rl: Editor<GameHelper, rustyline::history::DefaultHistory> = Editor::new().unwrap();
     rl.set_helper(Some(helper));
 
@@ -162,13 +791,30 @@ fn simulate_game(seed: u64, player_count: usize, max_age: u8) {
             return;
         }
 
+        // Bot seats play without prompting
+        let actor = game.acting_player();
+        if let Some(bot) = bots[actor].as_mut() {
+            let action = bot.choose(&Observation::new(game, actor));
+            let logged = game.action_log.len();
+            if let Err(e) = game.apply_action(action.clone()) {
+                println!("Bot P{} played an illegal action {:?}: {:?}", actor, action, e);
+                return;
+            }
+            replay.actions.push(action.clone());
+            println!("P{} ({}): {:?}", actor, bot.name(), action);
+            for line in game.action_log.iter().skip(logged) {
+                println!("  {}", line);
+            }
+            continue;
+        }
+
         // Update completer with current cards
         if let Some(h) = rl.helper_mut() {
             let p = &game.players[game.current_player];
             let board_cards: Vec<String> = p.board.values()
                 .filter_map(|pile| pile.top().cloned())
                 .collect();
-            h.update_cards(&p.hand, board_cards);
+            h.update_cards(&p.hand.to_vec(), board_cards);
         }
 
         // Check for Pending Input
@@ -190,6 +836,10 @@ fn simulate_game(seed: u64, player_count: usize, max_age: u8) {
             match rl.readline("Select> ") {
                 Ok(line) => {
                     let trimmed = line.trim().to_string();
+                    if trimmed == "hint" {
+                        print_hints(game, seed);
+                        continue;
+                    }
                     let input = if trimmed == "auto" {
                         match req {
                             innovation_core::dogma::flow::InputRequest::SelectCard { source, .. } => {
@@ -204,8 +854,10 @@ fn simulate_game(seed: u64, player_count: usize, max_age: u8) {
                         trimmed
                     };
                     
-                    if let Err(e) = game.apply_action(Action::ResolveInput(input)) {
-                        println!("Error applying input: {:?}", e);
+                    let action = Action::ResolveInput(input);
+                    match game.apply_action(action.clone()) {
+                        Ok(()) => replay.actions.push(action),
+                        Err(e) => println!("Error applying input: {:?}", e),
                     }
                 },
                 Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => return,
@@ -224,44 +876,48 @@ fn simulate_game(seed: u64, player_count: usize, max_age: u8) {
             println!("\n--- Player {} Turn (Action {}/{}) ---", 
                 game.current_player, 
                 game.actions_taken + 1,
-                if game.turn_number == 1 { 1 } else { 2 }
+                if game.turn_number == 1 { 1 } else { game.config.ruleset.actions_per_turn }
             );
+            if game.config.team_play {
+                print_teams(game);
+            }
             let p = &game.players[game.current_player];
             println!("Hand: {:?}", p.hand);
-            println!("Board: {:?}", p.board.keys());
-            let db = load_all_cards();
-            println!("Score: {} points ({:?})", p.calculate_score(&db), p.score_pile);
-            println!("Commands: meld <card>, draw, dogma <card>, achieve <age>, quit");
+            println!("Board: {:?}", p.board.keys().collect::<Vec<_>>());
+            println!("Score: {} points ({:?})", p.calculate_score(), p.score_pile);
+            println!("Commands: meld <card>, draw, dogma <card>, achieve <age>, hint, quit");
         }
 
         match rl.readline("> ") {
             Ok(line) => {
                 let _ = rl.add_history_entry(&line);
-                let cmd: Vec<&str> = line.trim().split_whitespace().collect();
+                let cmd: Vec<&str> = line.split_whitespace().collect();
                 
                 if cmd.is_empty() { continue; }
                 
-                let res = match cmd[0] {
+                let action = match cmd[0] {
                     "meld" => {
-                        if cmd.len() > 1 { game.apply_action(Action::Meld(cmd[1].to_string())) }
+                        if cmd.len() > 1 { Action::Meld(cmd[1].to_string()) }
                         else { println!("Usage: meld <card_id>"); continue; }
                     },
-                    "draw" => game.apply_action(Action::Draw),
+                    "draw" => Action::Draw,
                     "dogma" => {
-                        if cmd.len() > 1 { game.apply_action(Action::Dogma(cmd[1].to_string())) }
+                        if cmd.len() > 1 { Action::Dogma(cmd[1].to_string()) }
                         else { println!("Usage: dogma <card_id>"); continue; }
                     },
                     "achieve" => {
                         println!("Achieve not implemented yet");
-                        Ok(())
+                        continue;
                     },
+                    "hint" => { print_hints(game, seed); continue; }
                     "exit" | "quit" => return,
-                    _ => { println!("Unknown command. Try: meld, draw, dogma, achieve, quit"); continue; }
+                    _ => { println!("Unknown command. Try: meld, draw, dogma, achieve, hint, quit"); continue; }
                 };
                 
-                if let Err(e) = res {
+                if let Err(e) = game.apply_action(action.clone()) {
                     println!("Action Error: {:?}", e);
                 } else {
+                    replay.actions.push(action);
                     println!("OK.");
                 }
             },
@@ -0,0 +1,258 @@
+//! `tournament` subcommand: bot-only games between agent configurations, played in
+//! parallel, summarized as win rates, confidence intervals, end conditions and Elo.
+
+use innovation_core::agent;
+use innovation_core::config::GameConfig;
+use innovation_core::game_state::{EndCondition, GameState};
+use serde::Serialize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const ELO_START: f64 = 1500.0;
+const ELO_K: f64 = 16.0;
+/// 95% two-sided normal quantile.
+const Z_95: f64 = 1.96;
+
+pub struct TournamentOptions {
+    /// Agent specs (see `agent::from_spec`), one per configuration.
+    pub agents: Vec<String>,
+    /// Games per player count.
+    pub games: usize,
+    pub seed: u64,
+    pub player_counts: Vec<usize>,
+    pub max_age: u8,
+    /// Games still running after this many actions count as unfinished.
+    pub max_steps: usize,
+    /// Games played at once; 0 uses every available core.
+    pub threads: usize,
+}
+
+/// One game of the schedule: which configuration sits in each seat.
+#[derive(Debug, Clone)]
+struct Pairing {
+    seed: u64,
+    seats: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameResult {
+    pub seed: u64,
+    pub players: usize,
+    /// Configuration index of each seat.
+    pub seats: Vec<usize>,
+    pub winners: Vec<usize>,
+    /// None when the game hit the step limit or failed.
+    pub end: Option<EndCondition>,
+    pub turns: u32,
+    pub actions: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentStats {
+    pub agent: String,
+    /// Seats played.
+    pub games: usize,
+    /// Wins, shared wins counting as a fraction.
+    pub wins: f64,
+    pub win_rate: f64,
+    /// 95% Wilson score interval of the win rate.
+    pub ci_low: f64,
+    pub ci_high: f64,
+    pub elo: f64,
+    pub avg_turns: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EndBreakdown {
+    pub achievements: usize,
+    pub score: usize,
+    pub special: usize,
+    pub unfinished: usize,
+    pub errors: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub games: usize,
+    pub avg_turns: f64,
+    pub avg_actions: f64,
+    pub end_conditions: EndBreakdown,
+    pub agents: Vec<AgentStats>,
+    pub results: Vec<GameResult>,
+}
+
+/// Every player count gets `games` games; seats rotate through the configurations
+/// from one game to the next.
+fn schedule(options: &TournamentOptions) -> Vec<Pairing> {
+    let count = options.agents.len();
+    let mut pairings = Vec::new();
+    for &players in &options.player_counts {
+        for game in 0..options.games {
+            pairings.push(Pairing {
+                seed: options.seed.wrapping_add(game as u64),
+                seats: (0..players).map(|seat| (game + seat) % count).collect(),
+            });
+        }
+    }
+    pairings
+}
+
+fn play(options: &TournamentOptions, pairing: &Pairing) -> GameResult {
+    let mut result = GameResult {
+        seed: pairing.seed,
+        players: pairing.seats.len(),
+        seats: pairing.seats.clone(),
+        winners: Vec::new(),
+        end: None,
+        turns: 0,
+        actions: 0,
+        error: None,
+    };
+    let game = GameConfig::new(pairing.seats.len())
+        .map(|config| config.max_age(options.max_age))
+        .and_then(|config| GameState::from_config(pairing.seed, config));
+    let agents = pairing.seats.iter().enumerate()
+        .map(|(seat, &idx)| agent::from_spec(&options.agents[idx], pairing.seed.wrapping_mul(31).wrapping_add(seat as u64)))
+        .collect::<Result<Vec<_>, _>>();
+    let (mut game, mut agents) = match (game, agents) {
+        (Ok(game), Ok(agents)) => (game, agents),
+        (Err(e), _) | (_, Err(e)) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+    match agent::play_game(&mut game, &mut agents, options.max_steps) {
+        Ok(actions) => result.actions = actions,
+        Err(e) => result.error = Some(e.to_string()),
+    }
+    result.winners = game.winners.clone();
+    result.end = game.end_condition;
+    result.turns = game.turn_number;
+    result
+}
+
+pub fn run(options: &TournamentOptions) -> Report {
+    let pairings = schedule(options);
+    let threads = match options.threads {
+        0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<(usize, GameResult)>> = Mutex::new(Vec::with_capacity(pairings.len()));
+    std::thread::scope(|scope| {
+        for _ in 0..threads.min(pairings.len()) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(pairing) = pairings.get(idx) else { break };
+                let result = play(options, pairing);
+                results.lock().expect("results lock").push((idx, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().expect("results lock");
+    results.sort_by_key(|(idx, _)| *idx);
+    summarize(options, results.into_iter().map(|(_, r)| r).collect())
+}
+
+fn summarize(options: &TournamentOptions, results: Vec<GameResult>) -> Report {
+    let count = options.agents.len();
+    let mut ends = EndBreakdown::default();
+    let mut games = vec![0usize; count];
+    let mut wins = vec![0.0f64; count];
+    let mut turns = vec![0u64; count];
+    let mut elo = vec![ELO_START; count];
+
+    for result in &results {
+        match (&result.error, result.end) {
+            (Some(_), _) => ends.errors += 1,
+            (None, Some(EndCondition::Achievements)) => ends.achievements += 1,
+            (None, Some(EndCondition::Score)) => ends.score += 1,
+            (None, Some(EndCondition::Special)) => ends.special += 1,
+            (None, None) => ends.unfinished += 1,
+        }
+        if result.error.is_some() {
+            continue;
+        }
+        let won = |seat: usize| result.winners.contains(&seat);
+        for (seat, &idx) in result.seats.iter().enumerate() {
+            games[idx] += 1;
+            turns[idx] += result.turns as u64;
+            if won(seat) {
+                wins[idx] += 1.0 / result.winners.len() as f64;
+            }
+        }
+
+        // Every pair of seats held by different configurations is one Elo match
+        let before = elo.clone();
+        for a in 0..result.seats.len() {
+            for b in a + 1..result.seats.len() {
+                let (ia, ib) = (result.seats[a], result.seats[b]);
+                if ia == ib {
+                    continue;
+                }
+                let score = match (won(a), won(b)) {
+                    (true, false) => 1.0,
+                    (false, true) => 0.0,
+                    _ => 0.5,
+                };
+                let expected = 1.0 / (1.0 + 10f64.powf((before[ib] - before[ia]) / 400.0));
+                elo[ia] += ELO_K * (score - expected);
+                elo[ib] -= ELO_K * (score - expected);
+            }
+        }
+    }
+
+    let agents = (0..count).map(|idx| {
+        let n = games[idx];
+        let rate = if n == 0 { 0.0 } else { wins[idx] / n as f64 };
+        let (ci_low, ci_high) = wilson(rate, n);
+        AgentStats {
+            agent: options.agents[idx].clone(),
+            games: n,
+            wins: wins[idx],
+            win_rate: rate,
+            ci_low,
+            ci_high,
+            elo: elo[idx],
+            avg_turns: if n == 0 { 0.0 } else { turns[idx] as f64 / n as f64 },
+        }
+    }).collect();
+
+    let finished = || results.iter().filter(|r| r.error.is_none());
+    let played = finished().count().max(1) as f64;
+    Report {
+        games: results.len(),
+        avg_turns: finished().map(|r| r.turns as f64).sum::<f64>() / played,
+        avg_actions: finished().map(|r| r.actions as f64).sum::<f64>() / played,
+        end_conditions: ends,
+        agents,
+        results,
+    }
+}
+
+/// 95% Wilson score interval of a proportion `p` observed over `n` trials.
+fn wilson(p: f64, n: usize) -> (f64, f64) {
+    if n == 0 {
+        return (0.0, 1.0);
+    }
+    let n = n as f64;
+    let z2 = Z_95 * Z_95;
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+pub fn print_table(report: &Report) {
+    let width = report.agents.iter().map(|a| a.agent.len()).max().unwrap_or(0).max(5);
+    println!("{:<width$}  {:>6}  {:>7}  {:>6}  {:>15}  {:>6}  {:>6}", "Agent", "Games", "Wins", "Win%", "95% CI", "Elo", "Turns");
+    for a in &report.agents {
+        println!("{:<width$}  {:>6}  {:>7.1}  {:>5.1}%  [{:>5.1}%, {:>5.1}%]  {:>6.0}  {:>6.1}",
+            a.agent, a.games, a.wins, a.win_rate * 100.0, a.ci_low * 100.0, a.ci_high * 100.0, a.elo, a.avg_turns);
+    }
+    let ends = &report.end_conditions;
+    println!();
+    println!("Games: {}, average length {:.1} turns ({:.1} actions)", report.games, report.avg_turns, report.avg_actions);
+    println!("End conditions: achievements {}, score {}, special {}, unfinished {}, errors {}",
+        ends.achievements, ends.score, ends.special, ends.unfinished, ends.errors);
+}
@@ -1,3 +1,6 @@
+use crate::db::{self, CardIdx};
+use crate::dogma::figures;
+use crate::model::Color;
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -8,3 +11,46 @@ pub enum Action {
     Dogma(String), // Card ID
     ResolveInput(String),
 }
+
+/// An action other than an input answer, naming its card by interned index: what
+/// `GameState::legal_moves` lists and `GameState::apply_move` plays. Moves are `Copy`,
+/// so listing and playing them allocates nothing; they convert to and from `Action`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    Draw,
+    Meld(CardIdx),
+    /// The standard achievement of an age.
+    Achieve(u8),
+    /// Figures: the decree of a color.
+    Decree(Color),
+    Dogma(CardIdx),
+}
+
+impl Move {
+    /// The move `action` stands for; `None` for input answers and for cards or
+    /// achievements the database does not know.
+    pub fn from_action(action: &Action) -> Option<Self> {
+        match action {
+            Action::Draw => Some(Move::Draw),
+            Action::Meld(card_id) => db::card_index(card_id).map(Move::Meld),
+            Action::Dogma(card_id) => db::card_index(card_id).map(Move::Dogma),
+            Action::Achieve(target) => match target.strip_prefix("Age ").and_then(|age| age.parse().ok()) {
+                Some(age) => Some(Move::Achieve(age)),
+                None => figures::decree_color(target).map(Move::Decree),
+            },
+            Action::ResolveInput(_) => None,
+        }
+    }
+}
+
+impl From<Move> for Action {
+    fn from(mv: Move) -> Self {
+        match mv {
+            Move::Draw => Action::Draw,
+            Move::Meld(idx) => Action::Meld(db::card_at(idx).name.clone()),
+            Move::Achieve(age) => Action::Achieve(format!("Age {}", age)),
+            Move::Decree(color) => Action::Achieve(figures::decree_name(color).to_string()),
+            Move::Dogma(idx) => Action::Dogma(db::card_at(idx).name.clone()),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Hand-tuned position evaluation and the greedy bot built on it.
+//!
+//! A position is scored from one seat's side as a weighted sum of its leads over the
+//! best opponent (score, achievements, icons, top card ages, splays, hand size).
+//! `HeuristicAgent` plays each candidate move on one random deal of the hidden cards
+//! and keeps the best scored result. The difficulty levels differ only in the noise
+//! added to each score.
+
+use super::{Agent, Observation};
+use crate::actions::Action;
+use crate::db::load_all_cards;
+use crate::game_state::{GamePhase, GameState};
+use crate::model::{SplayDirection, Symbol};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Most answers weighed for one input request.
+const MAX_RESPONSES: usize = 64;
+
+/// Position features, each one the seat's value minus the best opponent's.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Features {
+    pub score: f64,
+    pub achievements: f64,
+    /// Sum over symbols of the icon count lead.
+    pub icons: f64,
+    /// Sum of the top card ages.
+    pub top_ages: f64,
+    /// Splayed piles, left counting 1, right 2 and up 3.
+    pub splays: f64,
+    pub hand: f64,
+}
+
+impl Features {
+    pub fn of(state: &GameState, seat: usize) -> Self {
+        let own = Self::absolute(state, seat);
+        let best = state.players.iter()
+            .filter(|p| !state.config.teammates(p.id, seat))
+            .map(|p| Self::absolute(state, p.id))
+            .fold(None, |best: Option<Self>, f| Some(best.map_or(f, |b| b.max(&f))))
+            .unwrap_or_default();
+
+        let own_icons = state.players[seat].icon_totals();
+        let icons = Symbol::ALL.iter().map(|&symbol| {
+            let mine = own_icons.get(symbol) as f64;
+            let theirs = state.players.iter()
+                .filter(|p| !state.config.teammates(p.id, seat))
+                .map(|p| p.icon_totals().get(symbol) as f64)
+                .fold(0.0, f64::max);
+            mine - theirs
+        }).sum();
+
+        Self {
+            score: own.score - best.score,
+            achievements: own.achievements - best.achievements,
+            icons,
+            top_ages: own.top_ages - best.top_ages,
+            splays: own.splays - best.splays,
+            hand: own.hand - best.hand,
+        }
+    }
+
+    /// Raw values of one seat (icons left at zero).
+    fn absolute(state: &GameState, pid: usize) -> Self {
+        let db = load_all_cards();
+        let p = &state.players[pid];
+        Self {
+            score: p.calculate_score() as f64,
+            achievements: state.team_achievements(pid) as f64,
+            icons: 0.0,
+            top_ages: p.board.values()
+                .filter_map(|pile| pile.top().and_then(|id| db.get(id)))
+                .map(|c| c.age as f64)
+                .sum(),
+            splays: p.board.values().map(|pile| match pile.splay() {
+                SplayDirection::None => 0.0,
+                SplayDirection::Left => 1.0,
+                SplayDirection::Right => 2.0,
+                SplayDirection::Up => 3.0,
+            }).sum(),
+            hand: p.hand.len() as f64,
+        }
+    }
+
+    fn max(&self, other: &Self) -> Self {
+        Self {
+            score: self.score.max(other.score),
+            achievements: self.achievements.max(other.achievements),
+            icons: self.icons.max(other.icons),
+            top_ages: self.top_ages.max(other.top_ages),
+            splays: self.splays.max(other.splays),
+            hand: self.hand.max(other.hand),
+        }
+    }
+}
+
+/// Weights of [`Features`]; `noise` blurs every evaluation to weaken the bot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeuristicWeights {
+    pub score: f64,
+    pub achievements: f64,
+    pub icons: f64,
+    pub top_ages: f64,
+    pub splays: f64,
+    pub hand: f64,
+    /// Value of a won game (negated for a lost one).
+    pub win: f64,
+    /// Uniform noise amplitude added to each candidate's evaluation.
+    pub noise: f64,
+}
+
+impl Default for HeuristicWeights {
+    fn default() -> Self {
+        Self::hard()
+    }
+}
+
+impl HeuristicWeights {
+    /// Difficulties accepted by [`HeuristicWeights::by_difficulty`].
+    pub const DIFFICULTIES: [&'static str; 3] = ["easy", "normal", "hard"];
+
+    pub fn hard() -> Self {
+        Self {
+            score: 0.5,
+            achievements: 10.0,
+            icons: 0.3,
+            top_ages: 0.6,
+            splays: 1.0,
+            hand: 0.3,
+            win: 1000.0,
+            noise: 0.0,
+        }
+    }
+
+    pub fn normal() -> Self {
+        Self { noise: 2.0, ..Self::hard() }
+    }
+
+    pub fn easy() -> Self {
+        Self { noise: 8.0, ..Self::hard() }
+    }
+
+    pub fn by_difficulty(name: &str) -> Option<Self> {
+        match name {
+            "easy" => Some(Self::easy()),
+            "normal" => Some(Self::normal()),
+            "hard" => Some(Self::hard()),
+            _ => None,
+        }
+    }
+}
+
+/// Evaluation of `state` from `seat`'s side: high is good.
+pub fn evaluate(state: &GameState, seat: usize, weights: &HeuristicWeights) -> f64 {
+    if state.phase == GamePhase::GameOver {
+        return if state.winners.contains(&seat) { weights.win } else { -weights.win };
+    }
+    let f = Features::of(state, seat);
+    weights.score * f.score
+        + weights.achievements * f.achievements
+        + weights.icons * f.icons
+        + weights.top_ages * f.top_ages
+        + weights.splays * f.splays
+        + weights.hand * f.hand
+}
+
+/// Greedy bot: plays the action or answer with the best evaluation one action ahead,
+/// on a random deal of the cards it cannot see.
+pub struct HeuristicAgent {
+    weights: HeuristicWeights,
+    rng: StdRng,
+}
+
+impl HeuristicAgent {
+    pub fn new(seed: u64) -> Self {
+        Self::with_weights(seed, HeuristicWeights::default())
+    }
+
+    pub fn with_weights(seed: u64, weights: HeuristicWeights) -> Self {
+        Self { weights, rng: StdRng::seed_from_u64(seed) }
+    }
+
+    pub fn weights(&self) -> &HeuristicWeights {
+        &self.weights
+    }
+}
+
+/// Moves open to the acting seat: its legal actions, or its answers to the pending input.
+pub fn candidate_moves(state: &GameState, limit: usize) -> Vec<Action> {
+    match &state.pending_input {
+        Some(req) => req.responses(limit).into_iter().map(Action::ResolveInput).collect(),
+        None => state.legal_actions(),
+    }
+}
+
+impl Agent for HeuristicAgent {
+    fn name(&self) -> &str {
+        "heuristic"
+    }
+
+    fn choose(&mut self, obs: &Observation) -> Action {
+        let candidates = candidate_moves(&obs.view, MAX_RESPONSES);
+        if candidates.len() <= 1 {
+            return candidates.into_iter().next().unwrap_or(Action::Draw);
+        }
+        let deal = obs.determinize(&mut self.rng);
+        let mut best: Option<(f64, Action)> = None;
+        for action in candidates {
+            let mut next = deal.clone();
+            if next.apply_action(action.clone()).is_err() {
+                continue;
+            }
+            let mut value = evaluate(&next, obs.seat, &self.weights);
+            if self.weights.noise > 0.0 {
+                value += self.rng.gen_range(-self.weights.noise..=self.weights.noise);
+            }
+            if best.as_ref().is_none_or(|(v, _)| value > *v) {
+                best = Some((value, action));
+            }
+        }
+        best.map(|(_, action)| action).unwrap_or(Action::Draw)
+    }
+}
@@ -0,0 +1,41 @@
+//! Move suggestions for human players: an ISMCTS search from the acting seat's
+//! observation, so hints never use cards that seat cannot see.
+
+use super::ismcts::{IsmctsAgent, IsmctsConfig};
+use super::Observation;
+use crate::actions::Action;
+use crate::dogma::preview::{dogma_lines, reached};
+use crate::game_state::GameState;
+
+/// One suggested move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    pub action: Action,
+    /// Estimated chance to win after this move, from 0 to 1.
+    pub value: f64,
+    /// Search iterations that went through this move.
+    pub visits: u32,
+    /// For a dogma: the other players who would share its effects.
+    pub sharers: Vec<usize>,
+    /// For a dogma: the players its demands would hit.
+    pub demanded: Vec<usize>,
+}
+
+/// Who a dogma of `card_id` activated by `player_id` would reach, as
+/// (sharers, demanded), from the current boards.
+pub fn dogma_reach(state: &GameState, player_id: usize, card_id: &str) -> (Vec<usize>, Vec<usize>) {
+    dogma_lines(state, player_id, card_id).map_or_else(|_| (Vec::new(), Vec::new()), |lines| reached(&lines))
+}
+
+/// The `count` best moves of the seat expected to act in `game`, best first.
+pub fn hints(game: &GameState, config: IsmctsConfig, seed: u64, count: usize) -> Vec<Hint> {
+    let obs = Observation::for_acting_player(game);
+    let stats = IsmctsAgent::with_config(seed, config).search(&obs);
+    stats.into_iter().take(count).map(|s| {
+        let (sharers, demanded) = match &s.action {
+            Action::Dogma(card_id) => dogma_reach(&obs.view, obs.seat, card_id),
+            _ => (Vec::new(), Vec::new()),
+        };
+        Hint { action: s.action, value: s.value, visits: s.visits, sharers, demanded }
+    }).collect()
+}
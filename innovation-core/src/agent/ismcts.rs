@@ -0,0 +1,271 @@
+//! Information-set Monte Carlo Tree Search (single observer).
+//!
+//! Every iteration deals the cards the seat cannot see at random (a determinization
+//! consistent with its `Observation`), then walks one shared tree of actions and input
+//! answers, only following moves that are legal in that deal. Leaves are scored by a
+//! short random playout cut off by the heuristic evaluation. Each thread grows its own
+//! tree from its own seed; root statistics are summed at the end.
+
+use super::heuristic::{candidate_moves, evaluate, HeuristicWeights};
+use super::random::random_response;
+use super::{Agent, Observation};
+use crate::actions::Action;
+use crate::game_state::{GamePhase, GameState};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+/// When a search stops.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    /// Total iterations, split across threads (reproducible for a given seed).
+    Iterations(usize),
+    /// Wall-clock time per decision.
+    Time(Duration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IsmctsConfig {
+    pub budget: Budget,
+    /// Search threads; 0 uses every available core.
+    pub threads: usize,
+    /// UCB exploration constant.
+    pub exploration: f64,
+    /// Random moves played after a leaf before evaluating.
+    pub rollout_depth: usize,
+    /// Most answers considered for one input request.
+    pub max_responses: usize,
+    /// Evaluation of positions where the playout stops.
+    pub weights: HeuristicWeights,
+}
+
+impl Default for IsmctsConfig {
+    fn default() -> Self {
+        Self {
+            budget: Budget::Iterations(1000),
+            threads: 0,
+            exploration: 0.7,
+            rollout_depth: 20,
+            max_responses: 32,
+            weights: HeuristicWeights::hard(),
+        }
+    }
+}
+
+/// Root statistics of one candidate move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveStats {
+    pub action: Action,
+    pub visits: u32,
+    /// Mean reward, from 0 (loss) to 1 (win).
+    pub value: f64,
+}
+
+pub struct IsmctsAgent {
+    config: IsmctsConfig,
+    rng: StdRng,
+}
+
+impl IsmctsAgent {
+    pub fn new(seed: u64) -> Self {
+        Self::with_config(seed, IsmctsConfig::default())
+    }
+
+    pub fn with_config(seed: u64, config: IsmctsConfig) -> Self {
+        Self { config, rng: StdRng::seed_from_u64(seed) }
+    }
+
+    /// Searches from `obs`, returning the root moves sorted by decreasing visits.
+    pub fn search(&mut self, obs: &Observation) -> Vec<MoveStats> {
+        let search_seed: u64 = self.rng.gen();
+        let threads = match self.config.threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        let config = &self.config;
+        if threads == 1 {
+            // Inline, for targets without threads (wasm)
+            let tree = grow_tree(obs, config, config.budget, search_seed);
+            return root_stats(&[tree]);
+        }
+        let trees: Vec<Tree> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads).map(|idx| {
+                let budget = match config.budget {
+                    Budget::Iterations(total) => Budget::Iterations(total / threads + usize::from(idx < total % threads)),
+                    time => time,
+                };
+                let seed = search_seed ^ (idx as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+                scope.spawn(move || grow_tree(obs, config, budget, seed))
+            }).collect();
+            handles.into_iter().map(|h| h.join().expect("search thread panicked")).collect()
+        });
+        root_stats(&trees)
+    }
+}
+
+/// Root moves summed over `trees`, by decreasing visits.
+fn root_stats(trees: &[Tree]) -> Vec<MoveStats> {
+    let mut stats: Vec<MoveStats> = Vec::new();
+    for tree in trees {
+        for &child in &tree.nodes[0].children {
+            let node = &tree.nodes[child];
+            let action = node.action.clone().expect("child nodes hold an action");
+            match stats.iter_mut().find(|s| s.action == action) {
+                Some(s) => {
+                    let visits = s.visits + node.visits;
+                    s.value = (s.value * s.visits as f64 + node.total) / visits.max(1) as f64;
+                    s.visits = visits;
+                }
+                None => stats.push(MoveStats {
+                    action,
+                    visits: node.visits,
+                    value: node.total / node.visits.max(1) as f64,
+                }),
+            }
+        }
+    }
+    stats.sort_by(|a, b| b.visits.cmp(&a.visits).then(b.value.total_cmp(&a.value)));
+    stats
+}
+
+impl Agent for IsmctsAgent {
+    fn name(&self) -> &str {
+        "ismcts"
+    }
+
+    fn choose(&mut self, obs: &Observation) -> Action {
+        let candidates = candidate_moves(&obs.view, self.config.max_responses);
+        if candidates.len() <= 1 {
+            return candidates.into_iter().next().unwrap_or(Action::Draw);
+        }
+        self.search(obs).into_iter().next().map(|s| s.action).unwrap_or_else(|| candidates[0].clone())
+    }
+}
+
+struct Node {
+    /// Move leading here from the parent (None at the root).
+    action: Option<Action>,
+    /// Seat that played `action`, whose reward this node accumulates.
+    actor: usize,
+    children: Vec<usize>,
+    visits: u32,
+    /// Times this node's move was legal when its parent was visited.
+    available: u32,
+    total: f64,
+}
+
+struct Tree {
+    nodes: Vec<Node>,
+}
+
+fn grow_tree(obs: &Observation, config: &IsmctsConfig, budget: Budget, seed: u64) -> Tree {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut tree = Tree {
+        nodes: vec![Node { action: None, actor: obs.seat, children: Vec::new(), visits: 0, available: 0, total: 0.0 }],
+    };
+    // Only read the clock for time budgets: wasm has none
+    let start = matches!(budget, Budget::Time(_)).then(Instant::now);
+    let mut done = 0;
+    loop {
+        match budget {
+            Budget::Iterations(n) if done >= n => break,
+            Budget::Time(limit) if done > 0 && start.is_some_and(|s| s.elapsed() >= limit) => break,
+            _ => {}
+        }
+        iterate(&mut tree, obs, config, &mut rng);
+        done += 1;
+    }
+    tree
+}
+
+fn iterate(tree: &mut Tree, obs: &Observation, config: &IsmctsConfig, rng: &mut StdRng) {
+    let mut state = obs.determinize(rng);
+    // Playouts never read the log
+    state.config.log_actions = false;
+    let mut node = 0;
+    let mut path = vec![0];
+
+    // Selection and expansion
+    while state.phase != GamePhase::GameOver {
+        let moves = candidate_moves(&state, config.max_responses);
+        if moves.is_empty() {
+            break;
+        }
+        let actor = state.acting_player();
+        let children = tree.nodes[node].children.clone();
+        let mut legal_children = Vec::new();
+        for &child in &children {
+            if tree.nodes[child].action.as_ref().is_some_and(|a| moves.contains(a)) {
+                tree.nodes[child].available += 1;
+                legal_children.push(child);
+            }
+        }
+        let untried: Vec<&Action> = moves.iter()
+            .filter(|m| !children.iter().any(|&c| tree.nodes[c].action.as_ref() == Some(*m)))
+            .collect();
+
+        if let Some(&action) = untried.choose(rng) {
+            if state.apply_action(action.clone()).is_err() {
+                break;
+            }
+            tree.nodes.push(Node { action: Some(action.clone()), actor, children: Vec::new(), visits: 0, available: 1, total: 0.0 });
+            let child = tree.nodes.len() - 1;
+            tree.nodes[node].children.push(child);
+            path.push(child);
+            break;
+        }
+
+        let Some(child) = legal_children.into_iter().max_by(|&a, &b| {
+            ucb(&tree.nodes[a], config.exploration).total_cmp(&ucb(&tree.nodes[b], config.exploration))
+        }) else { break };
+        let action = tree.nodes[child].action.clone().expect("child nodes hold an action");
+        if state.apply_action(action).is_err() {
+            break;
+        }
+        node = child;
+        path.push(child);
+    }
+
+    // Playout
+    for _ in 0..config.rollout_depth {
+        if state.phase == GamePhase::GameOver {
+            break;
+        }
+        let played = match &state.pending_input {
+            Some(req) => state.apply_action(Action::ResolveInput(random_response(req, rng))),
+            None => match state.legal_moves().choose(rng) {
+                Some(&mv) => state.apply_move(mv),
+                None => break,
+            },
+        };
+        if played.is_err() {
+            break;
+        }
+    }
+
+    // Backpropagation
+    let rewards = rewards(&state, &config.weights);
+    for &idx in &path {
+        let node = &mut tree.nodes[idx];
+        node.visits += 1;
+        node.total += rewards[node.actor];
+    }
+}
+
+fn ucb(node: &Node, exploration: f64) -> f64 {
+    let visits = node.visits.max(1) as f64;
+    node.total / visits + exploration * ((node.available.max(1) as f64).ln() / visits).sqrt()
+}
+
+/// Reward of every seat, from 0 to 1: the result if the game is over, else the
+/// squashed heuristic evaluation.
+fn rewards(state: &GameState, weights: &HeuristicWeights) -> Vec<f64> {
+    (0..state.players.len()).map(|seat| {
+        if state.phase == GamePhase::GameOver {
+            if state.winners.contains(&seat) { 1.0 } else { 0.0 }
+        } else {
+            1.0 / (1.0 + (-evaluate(state, seat, weights) / 10.0).exp())
+        }
+    }).collect()
+}
@@ -0,0 +1,271 @@
+//! Linear evaluation learned by temporal-difference self-play.
+//!
+//! A position is described from one seat's side by a fixed vector of features, each
+//! the seat's value minus the best opponent's, and its value is the seat's chance to
+//! win, `sigmoid(weights · features)`. Training plays games in which every seat picks
+//! the move leading to its best value (or, now and then, a random one) and moves each
+//! seat's value towards the next position's value, and towards the result at the end
+//! (TD(λ)). Training runs on one thread from one seed, so it always gives the same
+//! weights.
+
+use super::heuristic::candidate_moves;
+use super::{Agent, Observation};
+use crate::actions::Action;
+use crate::config::GameConfig;
+use crate::db::{self, load_all_cards};
+use crate::errors::GameError;
+use crate::game_state::{GamePhase, GameState};
+use crate::model::{Color, Symbol};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Most answers weighed for one input request.
+const MAX_RESPONSES: usize = 64;
+
+/// Names of the features, in vector order. Weights files list them too, so a file
+/// written for another feature set is refused.
+pub const FEATURE_NAMES: [&str; 24] = [
+    "bias",
+    "score",
+    "achievements",
+    "icons_crown", "icons_leaf", "icons_factory", "icons_lightbulb", "icons_castle", "icons_clock",
+    "top_age_red", "top_age_blue", "top_age_green", "top_age_yellow", "top_age_purple",
+    "splay_red", "splay_blue", "splay_green", "splay_yellow", "splay_purple",
+    "hand_size",
+    "hand_ages",
+    "hand_meld_upgrades",
+    "max_top_age",
+    "pile_count",
+];
+
+/// Raw values of one seat, before taking the difference with opponents.
+fn seat_values(state: &GameState, pid: usize) -> [f64; FEATURE_NAMES.len()] {
+    let cards = load_all_cards();
+    let p = &state.players[pid];
+    let mut v = [0.0; FEATURE_NAMES.len()];
+    v[1] = p.calculate_score() as f64 / 20.0;
+    v[2] = state.team_achievements(pid) as f64 / state.config.achievements_needed.max(1) as f64;
+    let icons = p.icon_totals();
+    for (i, &symbol) in Symbol::ALL.iter().enumerate() {
+        v[3 + i] = icons.get(symbol) as f64 / 10.0;
+    }
+    for (i, color) in Color::ALL.iter().enumerate() {
+        if let Some(pile) = p.board.get(color) {
+            v[9 + i] = pile.top_index().map_or(0.0, |idx| db::card_stats(idx).age as f64 / 10.0);
+            v[14 + i] = pile.splay().index() as f64 / 3.0;
+        }
+    }
+    let hand: Vec<_> = p.hand.iter().filter_map(|id| cards.get(id)).collect();
+    v[19] = hand.len() as f64 / 5.0;
+    v[20] = hand.iter().map(|c| c.age as f64).sum::<f64>() / 20.0;
+    v[21] = hand.iter().filter(|c| {
+        let top = p.board.get(&c.color).and_then(|pile| pile.top_index());
+        top.is_none_or(|idx| db::card_stats(idx).age < c.age)
+    }).count() as f64 / 5.0;
+    v[22] = p.max_top_age() as f64 / 10.0;
+    v[23] = p.board.len() as f64 / 5.0;
+    v
+}
+
+/// Feature vector of `state` from `seat`'s side.
+pub fn features(state: &GameState, seat: usize) -> Vec<f64> {
+    let own = seat_values(state, seat);
+    let mut best = [f64::NEG_INFINITY; FEATURE_NAMES.len()];
+    for p in state.players.iter().filter(|p| !state.config.teammates(p.id, seat)) {
+        for (b, v) in best.iter_mut().zip(seat_values(state, p.id)) {
+            *b = b.max(v);
+        }
+    }
+    let mut f: Vec<f64> = own.iter().zip(best).map(|(o, b)| if b.is_finite() { o - b } else { *o }).collect();
+    f[0] = 1.0;
+    f
+}
+
+/// Weights of [`FEATURE_NAMES`], as saved by training.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedWeights {
+    pub features: Vec<String>,
+    pub weights: Vec<f64>,
+    /// Self-play games these weights were trained on.
+    #[serde(default)]
+    pub games: usize,
+}
+
+impl Default for LearnedWeights {
+    /// Untrained: every position is a coin flip.
+    fn default() -> Self {
+        Self {
+            features: FEATURE_NAMES.iter().map(|n| n.to_string()).collect(),
+            weights: vec![0.0; FEATURE_NAMES.len()],
+            games: 0,
+        }
+    }
+}
+
+impl LearnedWeights {
+    pub fn from_json(json: &str) -> Result<Self, GameError> {
+        let weights: Self = serde_json::from_str(json)
+            .map_err(|e| GameError::InvalidConfig(format!("invalid weights file: {}", e)))?;
+        if weights.features != FEATURE_NAMES || weights.weights.len() != FEATURE_NAMES.len() {
+            return Err(GameError::InvalidConfig("weights file was trained on other features".into()));
+        }
+        Ok(weights)
+    }
+
+    pub fn load(path: &str) -> Result<Self, GameError> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| GameError::InvalidConfig(format!("cannot read {}: {}", path, e)))?;
+        Self::from_json(&json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("weights serialize")
+    }
+
+    /// Estimated chance that `seat`'s side wins from `state`.
+    pub fn value(&self, state: &GameState, seat: usize) -> f64 {
+        if state.phase == GamePhase::GameOver {
+            return result(state, seat);
+        }
+        sigmoid(dot(&self.weights, &features(state, seat)))
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn result(state: &GameState, seat: usize) -> f64 {
+    if state.winners.iter().any(|&w| state.config.teammates(w, seat)) { 1.0 } else { 0.0 }
+}
+
+/// Move of the acting seat with the best value one action ahead, if any is legal.
+fn best_move(state: &GameState, weights: &LearnedWeights) -> Option<Action> {
+    let seat = state.acting_player();
+    let mut best: Option<(f64, Action)> = None;
+    for action in candidate_moves(state, MAX_RESPONSES) {
+        let mut next = state.clone();
+        if next.apply_action(action.clone()).is_err() {
+            continue;
+        }
+        let value = weights.value(&next, seat);
+        if best.as_ref().is_none_or(|(v, _)| value > *v) {
+            best = Some((value, action));
+        }
+    }
+    best.map(|(_, action)| action)
+}
+
+/// Greedy bot on learned weights: plays the move with the best value one action
+/// ahead, on a random deal of the cards it cannot see.
+pub struct LearnedAgent {
+    weights: LearnedWeights,
+    rng: StdRng,
+}
+
+impl LearnedAgent {
+    pub fn new(seed: u64, weights: LearnedWeights) -> Self {
+        Self { weights, rng: StdRng::seed_from_u64(seed) }
+    }
+
+    pub fn weights(&self) -> &LearnedWeights {
+        &self.weights
+    }
+}
+
+impl Agent for LearnedAgent {
+    fn name(&self) -> &str {
+        "learned"
+    }
+
+    fn choose(&mut self, obs: &Observation) -> Action {
+        let deal = obs.determinize(&mut self.rng);
+        best_move(&deal, &self.weights).unwrap_or(Action::Draw)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingConfig {
+    pub games: usize,
+    /// Seeds the games and the exploration.
+    pub seed: u64,
+    pub player_count: usize,
+    pub max_age: u8,
+    /// Games still running after this many actions stop without a final update.
+    pub max_steps: usize,
+    /// Step size of the weight updates.
+    pub learning_rate: f64,
+    /// Trace decay: 0 learns from the next position only, 1 from the result only.
+    pub lambda: f64,
+    /// Share of moves played at random, so that training sees more positions.
+    pub exploration: f64,
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self {
+            games: 1000,
+            seed: 1,
+            player_count: 2,
+            max_age: 10,
+            max_steps: 2000,
+            learning_rate: 0.05,
+            lambda: 0.7,
+            exploration: 0.1,
+        }
+    }
+}
+
+/// Trains `weights` by self-play; `progress` is called after every game with the
+/// number of games played so far.
+pub fn train(config: &TrainingConfig, mut weights: LearnedWeights, mut progress: impl FnMut(usize, &LearnedWeights)) -> Result<LearnedWeights, GameError> {
+    if !(0.0..=1.0).contains(&config.exploration) || !(0.0..=1.0).contains(&config.lambda) {
+        return Err(GameError::InvalidConfig("exploration and lambda must lie between 0 and 1".into()));
+    }
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let game_config = GameConfig::new(config.player_count)?.max_age(config.max_age);
+    for game_idx in 0..config.games {
+        let mut state = GameState::from_config(rng.gen(), game_config.clone())?;
+        // One eligibility trace per seat
+        let mut traces = vec![vec![0.0; FEATURE_NAMES.len()]; config.player_count];
+        for _ in 0..config.max_steps {
+            if state.phase == GamePhase::GameOver {
+                break;
+            }
+            let action = if rng.gen_bool(config.exploration) {
+                candidate_moves(&state, MAX_RESPONSES).choose(&mut rng).cloned()
+            } else {
+                best_move(&state, &weights)
+            };
+            let Some(action) = action else { break };
+            let mut next = state.clone();
+            if next.apply_action(action).is_err() {
+                // Only exploration can pick a refused answer: skip it
+                continue;
+            }
+            for (seat, trace) in traces.iter_mut().enumerate() {
+                let x = features(&state, seat);
+                let value = sigmoid(dot(&weights.weights, &x));
+                let target = weights.value(&next, seat);
+                let slope = value * (1.0 - value);
+                for (e, xi) in trace.iter_mut().zip(&x) {
+                    *e = config.lambda * *e + slope * xi;
+                }
+                let step = config.learning_rate * (target - value);
+                for (w, e) in weights.weights.iter_mut().zip(trace.iter()) {
+                    *w += step * e;
+                }
+            }
+            state = next;
+        }
+        weights.games += 1;
+        progress(game_idx + 1, &weights);
+    }
+    Ok(weights)
+}
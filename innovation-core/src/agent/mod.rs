@@ -0,0 +1,244 @@
+//! Computer players.
+//!
+//! An `Agent` sees the game through an `Observation` scoped to its seat and answers
+//! with the next `Action`. When the engine waits for one of the seat's inputs, the
+//! answer is an `Action::ResolveInput`, exactly as a human player would send it.
+
+mod heuristic;
+mod hint;
+mod ismcts;
+mod learned;
+mod random;
+
+pub use heuristic::{candidate_moves, evaluate, Features, HeuristicAgent, HeuristicWeights};
+pub use hint::{dogma_reach, hints, Hint};
+pub use ismcts::{Budget, IsmctsAgent, IsmctsConfig, MoveStats};
+pub use learned::{train, LearnedAgent, LearnedWeights, TrainingConfig, FEATURE_NAMES};
+pub use random::{random_response, RandomAgent};
+
+use crate::actions::Action;
+use crate::card_list::CardList;
+use crate::db::{self, CardIdx};
+use crate::dogma::flow::InputRequest;
+use crate::errors::GameError;
+use crate::game_state::{GamePhase, GameState};
+use crate::model::Expansion;
+use crate::shared::ChunkedVec;
+use rand::Rng;
+use std::collections::HashMap;
+
+pub trait Agent {
+    /// Short name used in logs and reports.
+    fn name(&self) -> &str;
+
+    /// Next action of `obs.seat`, which is the seat expected to act
+    /// (see `GameState::acting_player`).
+    fn choose(&mut self, obs: &Observation) -> Action;
+}
+
+/// Agent kinds accepted by [`from_spec`].
+pub const NAMES: [&str; 4] = ["random", "heuristic", "ismcts", "learned"];
+
+/// Built-in agent from a spec such as `random`, `heuristic:difficulty=easy` or
+/// `ismcts:iterations=200,threads=1`, seeded with `seed`.
+///
+/// Options: `difficulty` (heuristic); `iterations`, `time_ms`, `threads` and
+/// `depth` (ismcts); `weights`, the weights file to load (learned, required).
+pub fn from_spec(spec: &str, seed: u64) -> Result<Box<dyn Agent>, GameError> {
+    let (kind, options) = spec.split_once(':').unwrap_or((spec, ""));
+    let mut options: Vec<(&str, &str)> = options.split(',')
+        .filter(|o| !o.is_empty())
+        .map(|o| o.split_once('=').ok_or_else(|| GameError::InvalidConfig(format!("agent option '{}' has no value", o))))
+        .collect::<Result<_, _>>()?;
+    let number = |value: &str| value.parse::<u64>()
+        .map_err(|_| GameError::InvalidConfig(format!("agent option value '{}' is not a number", value)));
+
+    let agent: Box<dyn Agent> = match kind {
+        "random" => Box::new(RandomAgent::new(seed)),
+        "heuristic" => {
+            let mut weights = HeuristicWeights::default();
+            let mut unknown = Vec::new();
+            for (key, value) in options.drain(..) {
+                match key {
+                    "difficulty" => weights = HeuristicWeights::by_difficulty(value).ok_or_else(|| {
+                        GameError::InvalidConfig(format!("unknown difficulty '{}' (expected one of: {})", value, HeuristicWeights::DIFFICULTIES.join(", ")))
+                    })?,
+                    _ => unknown.push((key, value)),
+                }
+            }
+            options = unknown;
+            Box::new(HeuristicAgent::with_weights(seed, weights))
+        }
+        "ismcts" => {
+            let mut config = IsmctsConfig::default();
+            let mut unknown = Vec::new();
+            for (key, value) in options.drain(..) {
+                match key {
+                    "iterations" => config.budget = Budget::Iterations(number(value)? as usize),
+                    "time_ms" => config.budget = Budget::Time(std::time::Duration::from_millis(number(value)?)),
+                    "threads" => config.threads = number(value)? as usize,
+                    "depth" => config.rollout_depth = number(value)? as usize,
+                    _ => unknown.push((key, value)),
+                }
+            }
+            options = unknown;
+            Box::new(IsmctsAgent::with_config(seed, config))
+        }
+        "learned" => {
+            let idx = options.iter().position(|(key, _)| *key == "weights")
+                .ok_or_else(|| GameError::InvalidConfig("agent 'learned' needs a weights=FILE option".into()))?;
+            let (_, path) = options.remove(idx);
+            Box::new(LearnedAgent::new(seed, LearnedWeights::load(path)?))
+        }
+        _ => {
+            return Err(GameError::InvalidConfig(format!("unknown agent '{}' (expected one of: {})", kind, NAMES.join(", "))));
+        }
+    };
+    if let Some((key, value)) = options.first() {
+        return Err(GameError::InvalidConfig(format!("invalid option {}={} for agent '{}'", key, value, kind)));
+    }
+    Ok(agent)
+}
+
+/// Plays `game` with one agent per seat until it ends or `max_steps` actions were
+/// played, and returns the number of actions played.
+pub fn play_game(game: &mut GameState, agents: &mut [Box<dyn Agent>], max_steps: usize) -> Result<usize, GameError> {
+    let mut steps = 0;
+    while game.phase != GamePhase::GameOver && steps < max_steps {
+        let actor = game.acting_player();
+        let action = agents[actor].choose(&Observation::new(game, actor));
+        game.apply_action(action)?;
+        steps += 1;
+    }
+    Ok(steps)
+}
+
+/// The game as one seat sees it.
+///
+/// Cards hidden from the seat (opponents' hands, score piles and foreshadowed cards,
+/// and every draw pile) are swapped for unseen cards of the same expansion and age,
+/// laid out in a fixed order, so the view tells nothing about which cards they are.
+/// The same swap applies wherever else the state names a hidden card: the pending
+/// input, the setup melds and the dogma in progress. The action log, which names
+/// every drawn card, is left out.
+#[derive(Clone)]
+pub struct Observation {
+    pub seat: usize,
+    pub view: GameState,
+}
+
+impl Observation {
+    pub fn new(game: &GameState, seat: usize) -> Self {
+        let mut view = game.clone();
+        view.action_log = ChunkedVec::new();
+        refill_hidden(&mut view, seat, |pool| pool.sort());
+        Self { seat, view }
+    }
+
+    /// Observation of the seat expected to act.
+    pub fn for_acting_player(game: &GameState) -> Self {
+        Self::new(game, game.acting_player())
+    }
+
+    pub fn pending_input(&self) -> Option<&InputRequest> {
+        self.view.pending_input.as_ref()
+    }
+
+    /// Legal actions outside of inputs (empty while an input is pending).
+    pub fn legal_actions(&self) -> Vec<Action> {
+        self.view.legal_actions()
+    }
+
+    /// The view with every hidden card replaced by a `?<age>` placeholder
+    /// (`?<age> <expansion>` for expansion cards), for clients outside the engine.
+    pub fn redacted(&self) -> GameState {
+        let mut state = self.view.clone();
+        let mut placeholders = HashMap::new();
+        for pile in hidden_piles(&mut state, self.seat) {
+            *pile = pile.indices().iter().map(|&idx| {
+                let card = db::card_at(idx);
+                let placeholder = db::placeholder(card.age, card.expansion);
+                placeholders.insert(card.name.clone(), db::card_at(placeholder).name.clone());
+                placeholder
+            }).collect();
+        }
+        rename_hidden(&mut state, &placeholders);
+        state
+    }
+
+    /// A full game consistent with this view: the hidden cards are dealt again at random.
+    pub fn determinize<R: Rng>(&self, rng: &mut R) -> GameState {
+        let mut state = self.view.clone();
+        refill_hidden(&mut state, self.seat, |pool| {
+            pool.sort();
+            pool.shuffle(rng);
+        });
+        state
+    }
+}
+
+/// Card lists whose content `seat` cannot see, in a stable order.
+fn hidden_piles(state: &mut GameState, seat: usize) -> Vec<&mut CardList> {
+    let mut piles = Vec::new();
+    for p in state.players.iter_mut().filter(|p| p.id != seat).map(|p| &mut **p) {
+        piles.push(&mut p.hand);
+        piles.push(&mut p.score_pile);
+        piles.push(&mut p.foreshadow);
+    }
+    piles.extend(state.deck.values_mut().map(|pile| &mut **pile));
+    piles.extend(state.expansion_decks.values_mut().flat_map(|decks| decks.values_mut()).map(|pile| &mut **pile));
+    piles
+}
+
+/// Pools the hidden cards by expansion and age, lets `arrange` order each pool,
+/// then deals them back into the same slots.
+fn refill_hidden(state: &mut GameState, seat: usize, mut arrange: impl FnMut(&mut CardList)) {
+    let key = |idx: CardIdx| {
+        let card = db::card_at(idx);
+        (card.expansion, card.age)
+    };
+
+    let mut pools: HashMap<(Expansion, u8), CardList> = HashMap::new();
+    for pile in hidden_piles(state, seat) {
+        for &idx in pile.indices() {
+            pools.entry(key(idx)).or_default().push_index(idx);
+        }
+    }
+    let mut keys: Vec<_> = pools.keys().copied().collect();
+    keys.sort_by_key(|(expansion, age)| (format!("{:?}", expansion), *age));
+    for k in keys {
+        arrange(pools.get_mut(&k).expect("pool exists"));
+    }
+
+    let mut swaps = HashMap::new();
+    for pile in hidden_piles(state, seat) {
+        *pile = pile.indices().iter().map(|&slot| {
+            match pools.get_mut(&key(slot)).and_then(|pool| pool.pop_index()) {
+                Some(card) => {
+                    swaps.insert(db::card_at(slot).name.clone(), db::card_at(card).name.clone());
+                    card
+                }
+                None => slot,
+            }
+        }).collect();
+    }
+    rename_hidden(state, &swaps);
+    state.rehash();
+}
+
+/// Renames the hidden cards named outside the card lists, as their slots were renamed.
+fn rename_hidden(state: &mut GameState, names: &HashMap<String, String>) {
+    let rename = |id: &mut String| {
+        if let Some(name) = names.get(id.as_str()) {
+            id.clone_from(name);
+        }
+    };
+    if let Some(InputRequest::SelectCard { source, .. }) = state.pending_input.as_mut() {
+        source.iter_mut().for_each(rename);
+    }
+    state.initial_melds.values_mut().for_each(rename);
+    if let Some(ds) = state.dogma_state.as_mut() {
+        rename(&mut ds.card_id);
+        ds.echoes.iter_mut().for_each(rename);
+    }
+}
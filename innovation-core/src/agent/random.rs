@@ -0,0 +1,57 @@
+//! Uniformly random play, the baseline the other agents are measured against.
+//!
+//! `random_response` also answers inputs during ISMCTS playouts.
+
+use super::{Agent, Observation};
+use crate::actions::Action;
+use crate::dogma::flow::InputRequest;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Plays a uniformly random legal action, and a random valid answer to every input.
+pub struct RandomAgent {
+    rng: StdRng,
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> Self {
+        Self { rng: StdRng::seed_from_u64(seed) }
+    }
+
+    fn respond(&mut self, req: &InputRequest) -> String {
+        random_response(req, &mut self.rng)
+    }
+}
+
+impl Agent for RandomAgent {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn choose(&mut self, obs: &Observation) -> Action {
+        if let Some(req) = obs.pending_input() {
+            return Action::ResolveInput(self.respond(req));
+        }
+        obs.legal_actions().choose(&mut self.rng).cloned().unwrap_or(Action::Draw)
+    }
+}
+
+/// A random answer to `req` that the engine accepts.
+pub fn random_response<R: Rng>(req: &InputRequest, rng: &mut R) -> String {
+    match req {
+        InputRequest::SelectCard { source, min, max, .. } => {
+            let lo = (*min).min(source.len());
+            let hi = (*max).min(source.len());
+            let count = rng.gen_range(lo..=hi);
+            let picked: Vec<&String> = source.choose_multiple(rng, count).collect();
+            picked.iter().map(|c| c.as_str()).collect::<Vec<_>>().join(",")
+        }
+        InputRequest::SelectColor { colors, .. } => {
+            colors.choose(rng).map(|c| format!("{:?}", c)).unwrap_or_default()
+        }
+        InputRequest::Confirm { .. } => {
+            if rng.gen_bool(0.5) { "yes".to_string() } else { "no".to_string() }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Post-game analysis.
+//!
+//! A finished game is kept as a [`Replay`]: the seed and rules it was created with
+//! and every action played, which is enough to rebuild each position since the
+//! engine draws all its randomness from the seed. The analysis rates every position
+//! with a bot, flags the moves after which the mover's chances moved the most, and
+//! sums up each player's score, achievements and icons at the end of every turn.
+
+use crate::actions::Action;
+use crate::agent::{evaluate, HeuristicWeights, IsmctsAgent, IsmctsConfig, Observation};
+use crate::config::GameConfig;
+use crate::errors::GameError;
+use crate::game_state::{GamePhase, GameState};
+use crate::model::Symbol;
+use serde::{Deserialize, Serialize};
+
+/// A game as the list of actions played from its start.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub config: GameConfig,
+    pub actions: Vec<Action>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: GameConfig) -> Self {
+        Self { seed, config, actions: Vec::new() }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, GameError> {
+        serde_json::from_str(json).map_err(|e| GameError::InvalidConfig(format!("invalid replay: {}", e)))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("replay serializes")
+    }
+
+    /// Every position of the game, from the start to after the last action.
+    pub fn positions(&self) -> Result<Vec<GameState>, GameError> {
+        let mut state = GameState::from_config(self.seed, self.config.clone())?;
+        let mut positions = Vec::with_capacity(self.actions.len() + 1);
+        for (idx, action) in self.actions.iter().enumerate() {
+            positions.push(state.clone());
+            state.apply_action(action.clone()).map_err(|e| {
+                GameError::InvalidAction(format!("replay action {} ({:?}) is illegal: {}", idx + 1, action, e))
+            })?;
+        }
+        positions.push(state);
+        Ok(positions)
+    }
+}
+
+/// Bot rating the positions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Evaluator {
+    /// The heuristic evaluation, squashed to a chance to win as ISMCTS playouts do.
+    Heuristic(HeuristicWeights),
+    /// An ISMCTS search from the acting player's view; its best move's value is the
+    /// acting side's chance, and opponents share the rest.
+    Ismcts(IsmctsConfig),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisConfig {
+    pub evaluator: Evaluator,
+    /// Smallest change of the mover's chance to win worth flagging.
+    pub threshold: f64,
+    /// Most moves flagged.
+    pub moments: usize,
+    /// Seeds the ISMCTS searches.
+    pub seed: u64,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            evaluator: Evaluator::Heuristic(HeuristicWeights::hard()),
+            threshold: 0.1,
+            moments: 5,
+            seed: 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MomentKind {
+    /// The move lowered its player's chances.
+    Blunder,
+    /// The move raised its player's chances.
+    TurningPoint,
+}
+
+/// A move that changed its player's chances a lot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Moment {
+    pub kind: MomentKind,
+    /// Index of the action in the replay.
+    pub index: usize,
+    pub turn: u32,
+    pub player: usize,
+    pub action: Action,
+    /// The player's chance to win before and after the move.
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Moment {
+    pub fn swing(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+/// One player at the end of a turn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerTurn {
+    pub score: u32,
+    pub achievements: usize,
+    /// Visible icons, in the order of [`Analysis::symbols`].
+    pub icons: Vec<u32>,
+    /// Chance to win according to the evaluator.
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnSummary {
+    pub turn: u32,
+    /// By seat.
+    pub players: Vec<PlayerTurn>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Analysis {
+    pub winners: Vec<usize>,
+    pub symbols: Vec<Symbol>,
+    /// Every position's chance to win of each seat, from the start to the end.
+    pub values: Vec<Vec<f64>>,
+    /// Flagged moves, in game order.
+    pub moments: Vec<Moment>,
+    pub turns: Vec<TurnSummary>,
+}
+
+impl Analysis {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("analysis serializes")
+    }
+}
+
+/// Rates every position of `replay` and picks its key moves.
+pub fn analyze(replay: &Replay, config: &AnalysisConfig) -> Result<Analysis, GameError> {
+    let positions = replay.positions()?;
+    let values: Vec<Vec<f64>> = positions.iter().enumerate()
+        .map(|(idx, state)| chances(state, config, config.seed.wrapping_add(idx as u64)))
+        .collect();
+
+    let mut moments: Vec<Moment> = replay.actions.iter().enumerate()
+        .map(|(idx, action)| {
+            let player = positions[idx].acting_player();
+            let (before, after) = (values[idx][player], values[idx + 1][player]);
+            let kind = if after < before { MomentKind::Blunder } else { MomentKind::TurningPoint };
+            Moment { kind, index: idx, turn: positions[idx].turn_number, player, action: action.clone(), before, after }
+        })
+        .filter(|m| m.swing().abs() >= config.threshold)
+        .collect();
+    // Stable, so equal swings keep the earliest moves
+    moments.sort_by(|a, b| b.swing().abs().total_cmp(&a.swing().abs()));
+    moments.truncate(config.moments);
+    moments.sort_by_key(|m| m.index);
+
+    // A turn ends where the next position starts another one, or where the game does
+    let turns = positions.iter().enumerate()
+        .filter(|(idx, state)| positions.get(idx + 1).is_none_or(|next| next.turn_number != state.turn_number))
+        .map(|(idx, state)| summarize(state, &values[idx]))
+        .collect();
+
+    let last = positions.last().expect("a replay has a start");
+    Ok(Analysis { winners: last.winners.clone(), symbols: Symbol::ALL.to_vec(), values, moments, turns })
+}
+
+fn summarize(state: &GameState, values: &[f64]) -> TurnSummary {
+    let players = state.players.iter().enumerate().map(|(pid, p)| {
+        let icons = p.icon_totals();
+        PlayerTurn {
+            score: p.calculate_score(),
+            achievements: state.achievement_count(pid),
+            icons: Symbol::ALL.iter().map(|&s| icons.get(s)).collect(),
+            value: values[pid],
+        }
+    }).collect();
+    TurnSummary { turn: state.turn_number, players }
+}
+
+/// Chance to win of every seat in `state`.
+fn chances(state: &GameState, config: &AnalysisConfig, seed: u64) -> Vec<f64> {
+    let seats = 0..state.players.len();
+    if state.phase == GamePhase::GameOver {
+        return seats.map(|pid| if state.winners.iter().any(|&w| state.config.teammates(w, pid)) { 1.0 } else { 0.0 }).collect();
+    }
+    match &config.evaluator {
+        Evaluator::Heuristic(weights) => seats
+            .map(|pid| 1.0 / (1.0 + (-evaluate(state, pid, weights) / 10.0).exp()))
+            .collect(),
+        Evaluator::Ismcts(ismcts) => {
+            let obs = Observation::for_acting_player(state);
+            let value = IsmctsAgent::with_config(seed, ismcts.clone()).search(&obs)
+                .first().map_or(0.5, |best| best.value);
+            let opponents = seats.clone().filter(|&pid| !state.config.teammates(pid, obs.seat)).count().max(1);
+            seats.map(|pid| {
+                if state.config.teammates(pid, obs.seat) { value } else { (1.0 - value) / opponents as f64 }
+            }).collect()
+        }
+    }
+}
@@ -0,0 +1,382 @@
+//! Interned card lists: hands, score piles, foreshadowed cards and draw piles.
+
+use crate::db::{self, CardIdx};
+use crate::errors::GameError;
+use crate::shared::Shared;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Index;
+
+/// Cards in a fixed order, held as interned indices. Reads like the `Vec<String>` of
+/// card IDs these zones used to be (`iter`, `len`, `contains`, indexing, comparison
+/// with a `Vec<String>`) and serializes as one, but moving a card copies two bytes.
+///
+/// Only cards the database knows can be added (see `db::register_card`): `push` and
+/// `insert` fail with `GameError::CardNotFound` for any other name.
+///
+/// The list also keeps the total age of its cards, which is what a score pile is worth.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct CardList {
+    cards: Vec<CardIdx>,
+    total_age: u32,
+}
+
+/// Iterator over the card IDs of a `CardList`.
+pub type Iter<'a> = std::iter::Map<std::slice::Iter<'a, CardIdx>, fn(&'a CardIdx) -> &'a String>;
+
+fn name_of(idx: &CardIdx) -> &'static String {
+    &db::card_at(*idx).name
+}
+
+fn intern(card_id: &str) -> Result<CardIdx, GameError> {
+    db::intern(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))
+}
+
+impl CardList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The list of `names`, failing on the first unknown one.
+    pub fn from_names<S: AsRef<str>>(names: impl IntoIterator<Item = S>) -> Result<Self, GameError> {
+        names.into_iter().map(|name| intern(name.as_ref())).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Card IDs, in order.
+    pub fn iter(&self) -> Iter<'_> {
+        self.cards.iter().map(name_of)
+    }
+
+    /// Like `iter`, but the card IDs stay usable once the list has changed.
+    pub fn names(&self) -> impl Iterator<Item = &'static String> + '_ {
+        self.cards.iter().map(name_of)
+    }
+
+    /// Interned cards, in order.
+    pub fn indices(&self) -> &[CardIdx] {
+        &self.cards
+    }
+
+    /// Sum of the cards' ages.
+    pub fn total_age(&self) -> u32 {
+        self.total_age
+    }
+
+    pub fn get(&self, at: usize) -> Option<&'static String> {
+        self.cards.get(at).map(name_of)
+    }
+
+    pub fn first(&self) -> Option<&'static String> {
+        self.cards.first().map(name_of)
+    }
+
+    pub fn last(&self) -> Option<&'static String> {
+        self.cards.last().map(name_of)
+    }
+
+    pub fn contains(&self, card_id: &str) -> bool {
+        self.position(card_id).is_some()
+    }
+
+    /// Position of the first copy of `card_id`.
+    pub fn position(&self, card_id: &str) -> Option<usize> {
+        let idx = db::intern(card_id)?;
+        self.cards.iter().position(|&i| i == idx)
+    }
+
+    pub fn push(&mut self, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        self.push_index(intern(card_id.as_ref())?);
+        Ok(())
+    }
+
+    pub fn push_index(&mut self, idx: CardIdx) {
+        self.cards.push(idx);
+        self.total_age += db::card_age(idx) as u32;
+    }
+
+    pub fn insert(&mut self, at: usize, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        self.insert_index(at, intern(card_id.as_ref())?);
+        Ok(())
+    }
+
+    pub fn insert_index(&mut self, at: usize, idx: CardIdx) {
+        self.cards.insert(at, idx);
+        self.total_age += db::card_age(idx) as u32;
+    }
+
+    /// Takes out the card at `at`, shifting the later ones. Panics if `at` is out of range.
+    pub fn remove(&mut self, at: usize) -> &'static String {
+        name_of(&self.remove_index(at))
+    }
+
+    pub fn remove_index(&mut self, at: usize) -> CardIdx {
+        let idx = self.cards.remove(at);
+        self.total_age -= db::card_age(idx) as u32;
+        idx
+    }
+
+    /// Takes out the first copy of `card_id`, if any.
+    pub fn remove_card(&mut self, card_id: &str) -> Option<&'static String> {
+        self.position(card_id).map(|at| self.remove(at))
+    }
+
+    pub fn pop(&mut self) -> Option<&'static String> {
+        self.pop_index().as_ref().map(name_of)
+    }
+
+    pub fn pop_index(&mut self) -> Option<CardIdx> {
+        let idx = self.cards.pop()?;
+        self.total_age -= db::card_age(idx) as u32;
+        Some(idx)
+    }
+
+    pub fn clear(&mut self) {
+        self.cards.clear();
+        self.total_age = 0;
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&'static String) -> bool) {
+        self.cards.retain(|idx| keep(name_of(idx)));
+        self.total_age = db::total_age(&self.cards);
+    }
+
+    /// Sorts by card ID.
+    pub fn sort(&mut self) {
+        self.cards.sort_by_key(name_of);
+    }
+
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+    }
+
+    /// Card IDs, copied out.
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl Index<usize> for CardList {
+    type Output = String;
+
+    fn index(&self, at: usize) -> &String {
+        name_of(&self.cards[at])
+    }
+}
+
+impl<'a> IntoIterator for &'a CardList {
+    type Item = &'a String;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Shared<CardList> {
+    type Item = &'a String;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl FromIterator<CardIdx> for CardList {
+    fn from_iter<I: IntoIterator<Item = CardIdx>>(indices: I) -> Self {
+        let cards: Vec<CardIdx> = indices.into_iter().collect();
+        let total_age = db::total_age(&cards);
+        CardList { cards, total_age }
+    }
+}
+
+impl Extend<CardIdx> for CardList {
+    fn extend<I: IntoIterator<Item = CardIdx>>(&mut self, indices: I) {
+        for idx in indices {
+            self.push_index(idx);
+        }
+    }
+}
+
+impl TryFrom<Vec<String>> for CardList {
+    type Error = GameError;
+
+    fn try_from(names: Vec<String>) -> Result<Self, GameError> {
+        CardList::from_names(names)
+    }
+}
+
+impl From<CardList> for Vec<String> {
+    fn from(cards: CardList) -> Self {
+        cards.to_vec()
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<Vec<S>> for CardList {
+    fn eq(&self, other: &Vec<S>) -> bool {
+        self.iter().eq(other.iter().map(AsRef::as_ref))
+    }
+}
+
+impl<S: AsRef<str>, const N: usize> PartialEq<[S; N]> for CardList {
+    fn eq(&self, other: &[S; N]) -> bool {
+        self.iter().eq(other.iter().map(AsRef::as_ref))
+    }
+}
+
+impl PartialEq<CardList> for Vec<String> {
+    fn eq(&self, other: &CardList) -> bool {
+        other == self
+    }
+}
+
+impl fmt::Debug for CardList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Serialize for CardList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for CardList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        CardList::from_names(&names).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Highest age a draw pile can have.
+const MAX_AGE: u8 = 10;
+
+/// Ages of the `Decks` slots, for `Decks::keys` to hand out references to.
+const AGES: [u8; MAX_AGE as usize] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+/// Draw piles of one set of cards, one slot per age from 1 to 10. Reads like the
+/// `HashMap<u8, Shared<Vec<String>>>` the piles used to be kept in, visits them by
+/// increasing age, and serializes as that map. Like the board piles, the piles are
+/// shared between clones of the game until one of them draws.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Decks {
+    piles: [Option<Shared<CardList>>; MAX_AGE as usize],
+}
+
+fn slot(age: u8) -> Option<usize> {
+    (1..=MAX_AGE).contains(&age).then(|| age as usize - 1)
+}
+
+impl Decks {
+    pub fn get(&self, age: &u8) -> Option<&Shared<CardList>> {
+        self.piles.get(slot(*age)?)?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, age: &u8) -> Option<&mut Shared<CardList>> {
+        self.piles.get_mut(slot(*age)?)?.as_mut()
+    }
+
+    pub fn contains_key(&self, age: &u8) -> bool {
+        self.get(age).is_some()
+    }
+
+    /// The pile of `age`, started empty if there is none.
+    ///
+    /// # Panics
+    /// If `age` is not between 1 and 10.
+    pub fn pile_mut(&mut self, age: u8) -> &mut Shared<CardList> {
+        let slot = slot(age).unwrap_or_else(|| panic!("no draw pile for age {}", age));
+        self.piles[slot].get_or_insert_with(Default::default)
+    }
+
+    /// Sets the pile of `age`, returning the previous one. Ages outside 1 to 10 have
+    /// no pile: the pile is handed back.
+    pub fn insert(&mut self, age: u8, pile: Shared<CardList>) -> Option<Shared<CardList>> {
+        match slot(age) {
+            Some(slot) => self.piles[slot].replace(pile),
+            None => Some(pile),
+        }
+    }
+
+    pub fn remove(&mut self, age: &u8) -> Option<Shared<CardList>> {
+        self.piles.get_mut(slot(*age)?)?.take()
+    }
+
+    /// Ages with a pile, increasing.
+    pub fn keys(&self) -> impl Iterator<Item = &u8> + '_ {
+        self.iter().map(|(age, _)| age)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Shared<CardList>> + '_ {
+        self.piles.iter().flatten()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Shared<CardList>> + '_ {
+        self.piles.iter_mut().flatten()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&u8, &Shared<CardList>)> + '_ {
+        AGES.iter().zip(&self.piles).filter_map(|(age, pile)| Some((age, pile.as_ref()?)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.piles.iter().all(Option::is_none)
+    }
+}
+
+impl Index<&u8> for Decks {
+    type Output = Shared<CardList>;
+
+    fn index(&self, age: &u8) -> &Shared<CardList> {
+        self.get(age).unwrap_or_else(|| panic!("no draw pile for age {}", age))
+    }
+}
+
+impl FromIterator<(u8, Shared<CardList>)> for Decks {
+    fn from_iter<I: IntoIterator<Item = (u8, Shared<CardList>)>>(piles: I) -> Self {
+        let mut decks = Decks::default();
+        for (age, pile) in piles {
+            decks.insert(age, pile);
+        }
+        decks
+    }
+}
+
+impl From<HashMap<u8, Shared<CardList>>> for Decks {
+    fn from(piles: HashMap<u8, Shared<CardList>>) -> Self {
+        piles.into_iter().collect()
+    }
+}
+
+impl From<Decks> for HashMap<u8, Shared<CardList>> {
+    fn from(decks: Decks) -> Self {
+        AGES.into_iter().zip(decks.piles).filter_map(|(age, pile)| Some((age, pile?))).collect()
+    }
+}
+
+impl Serialize for Decks {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Decks {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::<u8, Shared<CardList>>::deserialize(deserializer).map(Decks::from)
+    }
+}
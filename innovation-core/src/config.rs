@@ -0,0 +1,198 @@
+//! Game configuration: the rules a game is created with, validated up front and
+//! stored in the `GameState` so saves and replays know which rules were in force.
+
+use crate::errors::GameError;
+use crate::model::Expansion;
+use crate::rules::{Ruleset, SharingBonus};
+use serde::{Deserialize, Serialize};
+
+/// Optional deviations from the official rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HouseRule {
+    /// Every player gets two actions on the first turn.
+    NoFirstTurnLimit,
+    /// Sharing a cooperative dogma does not give the activator a free draw.
+    /// Shorthand for a ruleset whose `sharing_bonus` is `SharingBonus::None`.
+    NoSharingBonus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameConfig {
+    pub player_count: usize,
+    pub achievements_needed: usize,
+    pub max_age: u8,
+    /// Start directly in Main phase with the dealt hands, without initial melds.
+    pub skip_setup: bool,
+    /// Expansions played on top of the base game. Some ship only part of their
+    /// published cards; see [`GameConfig::partial_expansions`].
+    pub expansions: Vec<Expansion>,
+    pub house_rules: Vec<HouseRule>,
+    /// Four-player team game: seats 0 and 2 play against seats 1 and 3.
+    #[serde(default)]
+    pub team_play: bool,
+    /// Edition-dependent rule numbers.
+    #[serde(default)]
+    pub ruleset: Ruleset,
+    /// Write what happens to `GameState::action_log`. Simulations that never read
+    /// the log turn it off: formatting the entries is a large part of a random game.
+    #[serde(default = "log_actions_default")]
+    pub log_actions: bool,
+}
+
+fn log_actions_default() -> bool {
+    true
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            player_count: 2,
+            achievements_needed: Self::default_achievements_needed(2),
+            max_age: 10,
+            skip_setup: false,
+            expansions: Vec::new(),
+            house_rules: Vec::new(),
+            team_play: false,
+            ruleset: Ruleset::default(),
+            log_actions: true,
+        }
+    }
+}
+
+impl GameConfig {
+    /// Expansions the engine has rules for.
+    pub const SUPPORTED_EXPANSIONS: [Expansion; 5] = [
+        Expansion::Base, Expansion::Echoes, Expansion::Cities, Expansion::Figures, Expansion::Artifacts,
+    ];
+
+    /// Official rules for `player_count` players (2 to 4).
+    pub fn new(player_count: usize) -> Result<Self, GameError> {
+        let config = Self {
+            player_count,
+            achievements_needed: Self::default_achievements_needed(player_count),
+            ..Self::default()
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Achievements required to win: 6 with 2 players, 5 with 3, 4 with 4.
+    pub fn default_achievements_needed(player_count: usize) -> usize {
+        match player_count {
+            3 => 5,
+            4 => 4,
+            _ => 6,
+        }
+    }
+
+    pub fn achievements_needed(mut self, count: usize) -> Self {
+        self.achievements_needed = count;
+        self
+    }
+
+    pub fn max_age(mut self, max_age: u8) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn skip_setup(mut self, skip: bool) -> Self {
+        self.skip_setup = skip;
+        self
+    }
+
+    /// Switches to the team game. Teams need as many achievements as a
+    /// two-player game, so this also resets `achievements_needed`.
+    pub fn team_play(mut self, on: bool) -> Self {
+        self.team_play = on;
+        let sides = if on { 2 } else { self.player_count };
+        self.achievements_needed = Self::default_achievements_needed(sides);
+        self
+    }
+
+    /// Keeps the `NoSharingBonus` house rule in force over the new ruleset.
+    pub fn ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.ruleset = ruleset;
+        if self.has_house_rule(HouseRule::NoSharingBonus) {
+            self.ruleset.sharing_bonus = SharingBonus::None;
+        }
+        self
+    }
+
+    pub fn log_actions(mut self, on: bool) -> Self {
+        self.log_actions = on;
+        self
+    }
+
+    pub fn expansion(mut self, expansion: Expansion) -> Self {
+        self.expansions.push(expansion);
+        self
+    }
+
+    pub fn house_rule(mut self, rule: HouseRule) -> Self {
+        self.house_rules.push(rule);
+        if rule == HouseRule::NoSharingBonus {
+            self.ruleset.sharing_bonus = SharingBonus::None;
+        }
+        self
+    }
+
+    pub fn has_house_rule(&self, rule: HouseRule) -> bool {
+        self.house_rules.contains(&rule)
+    }
+
+    pub fn has_expansion(&self, expansion: Expansion) -> bool {
+        expansion == Expansion::Base || self.expansions.contains(&expansion)
+    }
+
+    /// Enabled expansions whose card data holds only part of the published set: their
+    /// decks are smaller than in the boxed game, which changes how games unfold.
+    pub fn partial_expansions(&self) -> Vec<crate::db::ExpansionCoverage> {
+        self.expansions.iter()
+            .filter_map(|&expansion| crate::db::expansion_coverage(expansion))
+            .filter(|coverage| coverage.is_partial())
+            .collect()
+    }
+
+    /// Team of a seat. Partners sit in alternating seats; without team play
+    /// every seat is its own team.
+    pub fn team_of(&self, player: usize) -> usize {
+        if self.team_play { player % 2 } else { player }
+    }
+
+    pub fn teammates(&self, a: usize, b: usize) -> bool {
+        self.team_of(a) == self.team_of(b)
+    }
+
+    /// Checks the whole configuration; also run by `GameState::from_config`,
+    /// since configs can be edited or deserialized after `new`.
+    pub fn validate(&self) -> Result<(), GameError> {
+        if !(2..=4).contains(&self.player_count) {
+            return Err(GameError::InvalidConfig(format!("{} players (2 to 4 supported)", self.player_count)));
+        }
+        if !(1..=10).contains(&self.max_age) {
+            return Err(GameError::InvalidConfig(format!("max age {} (1 to 10 supported)", self.max_age)));
+        }
+        if self.team_play && self.player_count != 4 {
+            return Err(GameError::InvalidConfig("team play needs exactly 4 players".into()));
+        }
+        if self.achievements_needed == 0 {
+            return Err(GameError::InvalidConfig("at least one achievement must be needed".into()));
+        }
+        self.ruleset.validate()?;
+        if self.has_house_rule(HouseRule::NoSharingBonus) && self.ruleset.sharing_bonus != SharingBonus::None {
+            return Err(GameError::InvalidConfig(format!("house rule NoSharingBonus conflicts with the sharing bonus of ruleset '{}'", self.ruleset.name)));
+        }
+        for (idx, expansion) in self.expansions.iter().enumerate() {
+            if self.expansions[..idx].contains(expansion) {
+                return Err(GameError::InvalidConfig(format!("expansion {:?} listed twice", expansion)));
+            }
+            if !Self::SUPPORTED_EXPANSIONS.contains(expansion) {
+                return Err(GameError::InvalidConfig(format!("expansion {:?} is not supported yet", expansion)));
+            }
+            if !crate::db::has_cards(*expansion) {
+                return Err(GameError::InvalidConfig(format!("no card data for expansion {:?}", expansion)));
+            }
+        }
+        Ok(())
+    }
+}
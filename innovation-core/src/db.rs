@@ -1,6 +1,8 @@
-use crate::model::Card;
+use crate::model::{Card, Color, Expansion, IconTotals, SplayDirection};
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+use std::sync::RwLock;
 
 // We assume/require the data directory is available or we embed.
 // For simplicity in this workspace, let's embed.
@@ -9,20 +11,252 @@ use once_cell::sync::Lazy;
 // innovation-core/src/db.rs -> ../../data/cards.json
 const CARDS_JSON: &str = include_str!("../../data/cards.json");
 
+/// One data file per expansion; every card in it belongs to that expansion.
+const EXPANSION_JSON: [(Expansion, &str); 4] = [
+    (Expansion::Echoes, include_str!("../../data/echoes.json")),
+    (Expansion::Cities, include_str!("../../data/cities.json")),
+    (Expansion::Figures, include_str!("../../data/figures.json")),
+    (Expansion::Artifacts, include_str!("../../data/artifacts.json")),
+];
+
 #[derive(serde::Deserialize)]
 struct CardsFile {
     cards: Vec<Card>,
 }
 
+#[derive(serde::Deserialize)]
+struct MetadataFile {
+    metadata: Metadata,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    /// Size of the printed set, when the file holds only part of it.
+    #[serde(default)]
+    published_card_count: Option<usize>,
+}
+
 pub static CARDS: Lazy<HashMap<String, Card>> = Lazy::new(|| {
     let data: CardsFile = serde_json::from_str(CARDS_JSON).expect("Failed to parse cards.json");
-    data.cards.into_iter().map(|c| (c.name.clone(), c)).collect()
+    let mut cards: HashMap<String, Card> = data.cards.into_iter().map(|c| (c.name.clone(), c)).collect();
+    for (expansion, json) in EXPANSION_JSON {
+        let data: CardsFile = serde_json::from_str(json)
+            .unwrap_or_else(|e| panic!("Failed to parse {:?} cards: {}", expansion, e));
+        for mut card in data.cards {
+            card.expansion = expansion;
+            cards.insert(card.name.clone(), card);
+        }
+    }
+    cards
 });
 
 pub fn load_all_cards() -> &'static HashMap<String, Card> {
     &CARDS
 }
 
+/// Interned card: its position in the dense card table, sorted by age then name.
+/// Base game and expansions together exceed 256 cards, hence 16 bits.
+pub type CardIdx = u16;
+
+/// Per-card data precomputed for the hot paths (icon counting, scores).
+#[derive(Debug, Clone)]
+pub struct CardStats {
+    pub age: u8,
+    pub color: Color,
+    /// Icons shown as the top card of a pile.
+    pub top: IconTotals,
+    /// Icons shown under another card, indexed by `SplayDirection::index`.
+    pub splayed: [IconTotals; 4],
+}
+
+struct Entry {
+    card: &'static Card,
+    stats: CardStats,
+}
+
+impl Entry {
+    fn new(card: &'static Card) -> Self {
+        let stats = CardStats {
+            age: card.age,
+            color: card.color,
+            top: IconTotals::of(&card.icons),
+            splayed: SplayDirection::ALL.map(|splay| {
+                let icons: Vec<_> = card.splay_positions(splay).iter()
+                    .filter_map(|&idx| card.icons.get(idx).copied())
+                    .collect();
+                IconTotals::of(&icons)
+            }),
+        };
+        Entry { card, stats }
+    }
+}
+
+struct CardTable {
+    entries: Vec<Entry>,
+    /// Ages of `entries`, packed: score piles are summed on every move.
+    ages: Vec<u8>,
+    index: FxHashMap<&'static str, CardIdx>,
+}
+
+static TABLE: Lazy<CardTable> = Lazy::new(|| {
+    let mut cards: Vec<&'static Card> = CARDS.values().collect();
+    cards.sort_by(|a, b| (a.age, &a.name).cmp(&(b.age, &b.name)));
+    let index = cards.iter().enumerate().map(|(idx, card)| (card.name.as_str(), idx as CardIdx)).collect();
+    let ages = cards.iter().map(|card| card.age).collect();
+    CardTable { entries: cards.into_iter().map(Entry::new).collect(), ages, index }
+});
+
+/// Cards registered at run time, interned after those of the data files.
+#[derive(Default)]
+struct Registered {
+    entries: Vec<&'static Entry>,
+    index: FxHashMap<&'static str, CardIdx>,
+}
+
+static REGISTERED: Lazy<RwLock<Registered>> = Lazy::new(Default::default);
+
+/// Interns a card missing from the data files (a custom or test card), so that it can
+/// be put on a board. Registering a name twice returns the first registration.
+/// Such cards are not listed by `load_all_cards`.
+pub fn register_card(card: Card) -> CardIdx {
+    if let Some(idx) = card_index(&card.name) {
+        return idx;
+    }
+    let mut registered = REGISTERED.write().expect("card registry lock");
+    if let Some(&idx) = registered.index.get(card.name.as_str()) {
+        return idx;
+    }
+    let idx = TABLE.entries.len() + registered.entries.len();
+    assert!(idx <= CardIdx::MAX as usize, "too many cards for CardIdx");
+    let entry: &'static Entry = Box::leak(Box::new(Entry::new(Box::leak(Box::new(card)))));
+    registered.entries.push(entry);
+    registered.index.insert(entry.card.name.as_str(), idx as CardIdx);
+    idx as CardIdx
+}
+
+fn entry(idx: CardIdx) -> &'static Entry {
+    match TABLE.entries.get(idx as usize) {
+        Some(entry) => entry,
+        None => REGISTERED.read().expect("card registry lock").entries[idx as usize - TABLE.entries.len()],
+    }
+}
+
+/// Interned index of the card named `name`.
+pub fn card_index(name: &str) -> Option<CardIdx> {
+    match TABLE.index.get(name) {
+        Some(&idx) => Some(idx),
+        None => REGISTERED.read().expect("card registry lock").index.get(name).copied(),
+    }
+}
+
+/// The card named `name`, registered cards included.
+pub fn card(name: &str) -> Option<&'static Card> {
+    card_index(name).map(card_at)
+}
+
+/// Like `card_index`, but also knows the `?<age>` and `?<age> <expansion>` placeholders
+/// that stand for hidden cards in redacted views, registering them on first use.
+pub fn intern(name: &str) -> Option<CardIdx> {
+    card_index(name).or_else(|| {
+        let hidden = name.strip_prefix('?')?;
+        let (age, expansion) = hidden.split_once(' ').unwrap_or((hidden, ""));
+        let expansion = match expansion {
+            "" => Expansion::Base,
+            name => [Expansion::Echoes, Expansion::Figures, Expansion::Cities, Expansion::Artifacts]
+                .into_iter()
+                .find(|e| format!("{:?}", e) == name)?,
+        };
+        Some(placeholder(age.parse().ok()?, expansion))
+    })
+}
+
+/// Interned stand-in for a hidden card of `age` from `expansion`, named `?<age>`
+/// (`?<age> <expansion>` outside the base game). It shows no icons.
+pub fn placeholder(age: u8, expansion: Expansion) -> CardIdx {
+    let name = match expansion {
+        Expansion::Base => format!("?{}", age),
+        _ => format!("?{} {:?}", age, expansion),
+    };
+    register_card(Card {
+        name,
+        age,
+        color: Color::Red,
+        icons: Vec::new(),
+        dogmas: Vec::new(),
+        expansion,
+        echo: None,
+        karma: Vec::new(),
+        inspire: None,
+    })
+}
+
+/// The card interned as `idx`. Panics on an index `card_index` never returned.
+pub fn card_at(idx: CardIdx) -> &'static Card {
+    entry(idx).card
+}
+
+pub fn card_stats(idx: CardIdx) -> &'static CardStats {
+    &entry(idx).stats
+}
+
+/// `card_stats(idx).age`, without loading the rest of the stats.
+pub fn card_age(idx: CardIdx) -> u8 {
+    match TABLE.ages.get(idx as usize) {
+        Some(&age) => age,
+        None => card_stats(idx).age,
+    }
+}
+
+/// Sum of the ages of `cards`.
+pub fn total_age(cards: &[CardIdx]) -> u32 {
+    let ages = &TABLE.ages;
+    cards.iter().map(|&idx| ages.get(idx as usize).copied().unwrap_or_else(|| card_stats(idx).age) as u32).sum()
+}
+
+/// Number of cards in the data files; their indices run from 0 to this, excluded.
+pub fn card_count() -> usize {
+    TABLE.entries.len()
+}
+
+/// How much of an expansion's published set its data file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpansionCoverage {
+    pub expansion: Expansion,
+    /// Cards in the data file.
+    pub cards: usize,
+    /// Cards in the published set.
+    pub published: usize,
+}
+
+impl ExpansionCoverage {
+    /// Whether games with this expansion draw from a reduced set of cards.
+    pub fn is_partial(&self) -> bool {
+        self.cards < self.published
+    }
+}
+
+static PUBLISHED: Lazy<HashMap<Expansion, usize>> = Lazy::new(|| {
+    EXPANSION_JSON.iter().filter_map(|&(expansion, json)| {
+        let data: MetadataFile = serde_json::from_str(json)
+            .unwrap_or_else(|e| panic!("Failed to parse {:?} metadata: {}", expansion, e));
+        data.metadata.published_card_count.map(|count| (expansion, count))
+    }).collect()
+});
+
+/// Cards of `expansion` in the data files against the published set, if its data
+/// file gives the size of the published set.
+pub fn expansion_coverage(expansion: Expansion) -> Option<ExpansionCoverage> {
+    let published = *PUBLISHED.get(&expansion)?;
+    let cards = CARDS.values().filter(|c| c.expansion == expansion).count();
+    Some(ExpansionCoverage { expansion, cards, published })
+}
+
+/// Whether the data files hold any card of `expansion`.
+pub fn has_cards(expansion: Expansion) -> bool {
+    CARDS.values().any(|c| c.expansion == expansion)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
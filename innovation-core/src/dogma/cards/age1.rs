@@ -1,32 +1,32 @@
 #![allow(dead_code)] // Legacy implementations kept for reference/fallback
 use crate::game_state::GameState;
 use crate::errors::GameError;
+use super::Handler;
 use crate::dogma::flow::InputRequest;
 use crate::model::Color;
 
-pub fn execute(state: &mut GameState, player_id: usize, card_name: &str, dogma_index: usize, input: Option<String>) -> Result<(), GameError> {
-    match card_name {
-        // "Agriculture" => agriculture(state, player_id, dogma_index, input),
-        // "Archerie" => archerie(state, player_id, dogma_index, input),
-        "Tissage" => tissage(state, player_id, dogma_index, input),
-        // "Code de lois" => code_de_lois(state, player_id, dogma_index, input),
-        // "Élevage" => elevage(state, player_id, dogma_index, input),
-        // "Maçonnerie" => maconnerie(state, player_id, dogma_index, input),
-        "Métallurgie" => metallurgie(state, player_id, dogma_index, input),
-        "Mysticisme" => mysticisme(state, player_id, dogma_index, input),
-        "Rames" => rames(state, player_id, dogma_index, input),
-        // "Poterie" => poterie(state, player_id, dogma_index, input),
-        // "Fabrication d'outils" => fabrication_d_outils(state, player_id, dogma_index, input),
-        // "Voiles" => voiles(state, player_id, dogma_index, input),
-        // "La Roue" => la_roue(state, player_id, dogma_index, input),
-        // "Outils" => outils(state, player_id, dogma_index, input),
-        // "Écriture" => ecriture(state, player_id, dogma_index, input),
-        "Cités-États" => cites_etats(state, player_id, dogma_index, input),
-        _ => {
-            println!("Warning: Unimplemented card {}", card_name);
-            Ok(())
-        }
-    }
+/// Hand-written dogma of the age 1 card `card_name`, if it has one.
+pub fn handler(card_name: &str) -> Option<Handler> {
+    let handler: Handler = match card_name {
+        // "Agriculture" => agriculture,
+        // "Archerie" => archerie,
+        "Tissage" => tissage,
+        // "Code de lois" => code_de_lois,
+        // "Élevage" => elevage,
+        // "Maçonnerie" => maconnerie,
+        "Métallurgie" => metallurgie,
+        "Mysticisme" => mysticisme,
+        "Rames" => rames,
+        // "Poterie" => poterie,
+        // "Fabrication d'outils" => fabrication_d_outils,
+        // "Voiles" => voiles,
+        // "La Roue" => la_roue,
+        // "Outils" => outils,
+        // "Écriture" => ecriture,
+        "Cités-États" => cites_etats,
+        _ => return None,
+    };
+    Some(handler)
 }
 
 // --- Card Implementations ---
@@ -39,7 +39,7 @@ fn agriculture(state: &mut GameState, player_id: usize, _dogma_index: usize, inp
         if state.players[player_id].hand.is_empty() { return Ok(()); }
         state.pending_input = Some(InputRequest::SelectCard {
             player_id,
-            source: state.players[player_id].hand.clone(),
+            source: state.players[player_id].hand.to_vec(),
             min: 0, max: 1
         });
         update_sub_step(state, 1);
@@ -47,12 +47,12 @@ fn agriculture(state: &mut GameState, player_id: usize, _dogma_index: usize, inp
     } else if sub_step == 1 {
         if let Some(cid) = input.filter(|s| !s.is_empty()) {
             state.remove_from_hand(player_id, &cid)?;
-            state.return_card(cid)?;
+            state.return_card(&cid)?;
             
             if state.players[player_id].hand.is_empty() { return Ok(()); }
             state.pending_input = Some(InputRequest::SelectCard {
                 player_id,
-                source: state.players[player_id].hand.clone(),
+                source: state.players[player_id].hand.to_vec(),
                 min: 1, max: 1
             });
             update_sub_step(state, 2);
@@ -61,7 +61,7 @@ fn agriculture(state: &mut GameState, player_id: usize, _dogma_index: usize, inp
     } else if sub_step == 2 {
         if let Some(cid) = input {
             state.remove_from_hand(player_id, &cid)?;
-            state.score_card(player_id, cid)?;
+            state.score_card(player_id, &cid)?;
         }
         Ok(())
     } else { Ok(()) }
@@ -94,7 +94,7 @@ fn archerie(state: &mut GameState, player_id: usize, _dogma_index: usize, input:
              // Auto
              let cid = candidates[0].clone();
              state.remove_from_hand(player_id, &cid)?;
-             state.players[activator_id].hand.push(cid);
+             state.add_to_hand(activator_id, &cid)?;
              return Ok(());
          }
          state.pending_input = Some(InputRequest::SelectCard {
@@ -105,7 +105,7 @@ fn archerie(state: &mut GameState, player_id: usize, _dogma_index: usize, input:
     } else {
         if let Some(cid) = input {
             state.remove_from_hand(player_id, &cid)?;
-            state.players[activator_id].hand.push(cid);
+            state.add_to_hand(activator_id, &cid)?;
         }
         Ok(())
     }
@@ -138,7 +138,7 @@ fn tissage(state: &mut GameState, player_id: usize, _dogma_index: usize, input:
     } else if sub_step == 1 {
         // Handle Meld if input
         if let Some(cid) = input {
-             state.meld(player_id, cid)?;
+             state.meld(player_id, &cid)?;
         }
         
         // Count colors unique to self
@@ -158,7 +158,7 @@ fn tissage(state: &mut GameState, player_id: usize, _dogma_index: usize, input:
         
         for _ in 0..count {
             if let Some(cid) = state.draw_age(player_id, 1)? {
-                state.remove_from_hand(player_id, &cid)?; // Draw usually puts in hand
+                state.remove_from_hand(player_id, cid)?; // Draw usually puts in hand
                 state.score_card(player_id, cid)?;
             }
         }
@@ -193,7 +193,7 @@ fn code_de_lois(state: &mut GameState, player_id: usize, _dogma_index: usize, in
             let color = db.get(&cid).unwrap().color;
             
             state.remove_from_hand(player_id, &cid)?;
-            state.tuck_card(player_id, cid)?;
+            state.tuck_card(player_id, &cid)?;
             
             // Splay Left
             state.splay(player_id, color, crate::model::SplayDirection::Left)?;
@@ -223,7 +223,7 @@ fn elevage(state: &mut GameState, player_id: usize, _idx: usize, input: Option<S
          if candidates.len() == 1 {
              let cid = candidates[0].clone();
              state.remove_from_hand(player_id, &cid)?;
-             state.meld(player_id, cid)?;
+             state.meld(player_id, &cid)?;
              state.draw_age(player_id, 1)?; 
              return Ok(());
          }
@@ -236,7 +236,7 @@ fn elevage(state: &mut GameState, player_id: usize, _idx: usize, input: Option<S
     } else {
         if let Some(cid) = input {
             state.remove_from_hand(player_id, &cid)?;
-            state.meld(player_id, cid)?;
+            state.meld(player_id, &cid)?;
         }
         state.draw_age(player_id, 1)?;
         Ok(())
@@ -286,8 +286,8 @@ fn maconnerie(state: &mut GameState, player_id: usize, _idx: usize, input: Optio
              let cards: Vec<&str> = s.split(',').filter(|x| !x.is_empty()).collect();
              let count = cards.len();
              for c in cards {
-                 state.remove_from_hand(player_id, &c.to_string())?;
-                 state.meld(player_id, c.to_string())?;
+                 state.remove_from_hand(player_id, c)?;
+                 state.meld(player_id, c)?;
              }
              if count >= 4 {
                  // Claim Technologies achievement (placeholder)
@@ -302,12 +302,12 @@ fn metallurgie(state: &mut GameState, player_id: usize, _idx: usize, _in: Option
      // Draw cards until one without Castle is found
      while let Some(cid) = state.draw_age(player_id, 1)? {
          let db = crate::db::load_all_cards();
-         let card = db.get(&cid).unwrap();
+         let card = db.get(cid).unwrap();
          let has_castle = card.icons.contains(&crate::model::Icon::Resource(crate::model::Symbol::Castle));
          if has_castle {
              // "Comptabilisez-la" = Score
              // Since draw puts in hand, remove from hand first.
-             state.remove_from_hand(player_id, &cid)?;
+             state.remove_from_hand(player_id, cid)?;
              state.score_card(player_id, cid)?;
          } else {
              // Keep in hand (already there from draw)
@@ -321,7 +321,7 @@ fn mysticisme(state: &mut GameState, player_id: usize, _idx: usize, _in: Option<
     // Draw 1 reveal. If same color as board, meld and draw 1. Else Hand.
     if let Some(cid) = state.draw_age(player_id, 1)? {
         let db = crate::db::load_all_cards();
-        let color = db.get(&cid).unwrap().color;
+        let color = db.get(cid).unwrap().color;
         let has_color = state.players[player_id].board.contains_key(&color);
         
         if has_color {
@@ -359,7 +359,7 @@ fn rames(state: &mut GameState, player_id: usize, _idx: usize, input: Option<Str
     } else {
         if let Some(cid) = input.filter(|s| !s.is_empty()) {
             state.remove_from_hand(player_id, &cid)?;
-            state.score_card(activator_id, cid)?;
+            state.score_card(activator_id, &cid)?;
             state.draw_age(player_id, 1)?;
         } else {
              state.draw_age(player_id, 1)?;
@@ -374,7 +374,7 @@ fn poterie(state: &mut GameState, player_id: usize, _idx: usize, input: Option<S
      if sub_step == 0 {
           let p = &state.players[player_id];
           state.pending_input = Some(InputRequest::SelectCard {
-              player_id, source: p.hand.clone(), min: 0, max: 3
+              player_id, source: p.hand.to_vec(), min: 0, max: 3
           });
           update_sub_step(state, 1);
           Ok(())
@@ -383,12 +383,12 @@ fn poterie(state: &mut GameState, player_id: usize, _idx: usize, input: Option<S
              let cards: Vec<&str> = s.split(',').filter(|x| !x.is_empty()).collect();
              let count = cards.len();
              for c in cards {
-                 state.remove_from_hand(player_id, &c.to_string())?;
-                 state.return_card(c.to_string())?;
+                 state.remove_from_hand(player_id, c)?;
+                 state.return_card(c)?;
              }
              if count > 0 {
                  if let Some(cid) = state.draw_age(player_id, count as u8)? {
-                     state.remove_from_hand(player_id, &cid)?;
+                     state.remove_from_hand(player_id, cid)?;
                      state.score_card(player_id, cid)?;
                  }
              }
@@ -407,7 +407,7 @@ fn outils(state: &mut GameState, player_id: usize, dogma_index: usize, input: Op
              let p = &state.players[player_id];
              if p.hand.len() < 3 { return Ok(()); } // "Recycler TROIS cartes". Exact count? Usually if not explicit "up to", means exact.
              state.pending_input = Some(InputRequest::SelectCard {
-                 player_id, source: p.hand.clone(), min: 3, max: 3
+                 player_id, source: p.hand.to_vec(), min: 3, max: 3
              });
              update_sub_step(state, 1);
              Ok(())
@@ -416,8 +416,8 @@ fn outils(state: &mut GameState, player_id: usize, dogma_index: usize, input: Op
                  let cards: Vec<&str> = s.split(',').filter(|x| !x.is_empty()).collect();
                  if cards.len() == 3 {
                      for c in cards {
-                         state.remove_from_hand(player_id, &c.to_string())?;
-                         state.return_card(c.to_string())?;
+                         state.remove_from_hand(player_id, c)?;
+                         state.return_card(c)?;
                      }
                      if let Some(cid) = state.draw_age(player_id, 3)? {
                          state.meld(player_id, cid)?;
@@ -444,7 +444,7 @@ fn outils(state: &mut GameState, player_id: usize, dogma_index: usize, input: Op
          } else {
              if let Some(cid) = input.filter(|s| !s.is_empty()) {
                   state.remove_from_hand(player_id, &cid)?;
-                  state.return_card(cid)?;
+                  state.return_card(&cid)?;
                   for _ in 0..3 { state.draw_age(player_id, 1)?; }
              }
              Ok(())
@@ -457,7 +457,7 @@ fn cites_etats(state: &mut GameState, player_id: usize, _idx: usize, input: Opti
     // Calc Castles
          let p = &state.players[player_id];
      let db = crate::db::load_all_cards();
-     let castle_count = p.icon_counts(db).get(&crate::model::Symbol::Castle).cloned().unwrap_or(0);
+     let castle_count = p.icon_totals().get(crate::model::Symbol::Castle);
      
      if castle_count < 4 { return Ok(()); }
      
@@ -481,13 +481,8 @@ fn cites_etats(state: &mut GameState, player_id: usize, _idx: usize, input: Opti
     } else {
          if let Some(cid) = input.filter(|s| !s.is_empty()) {
              // Transfer board -> board
-             // Need remove_from_board_top primitive or similar?
-             // Since we have ID and it's top, we can find stack by color.
-             let color = db.get(&cid).unwrap().color;
-             if let Some(stack) = state.players[player_id].board.get_mut(&color) {
-                 stack.cards.pop(); // Remove
-             }
-             state.meld(activator_id, cid)?;
+             let cid = state.remove_from_board(player_id, &cid)?;
+             state.place_on_board(activator_id, cid)?;
              state.draw_age(player_id, 1)?;
          }
          Ok(())
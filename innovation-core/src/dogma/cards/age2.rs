@@ -1,27 +1,28 @@
 #![allow(dead_code)] // Legacy implementations kept for reference/fallback
 use crate::game_state::GameState;
 use crate::errors::GameError;
+use super::Handler;
 use crate::dogma::flow::InputRequest;
 use crate::model::Color;
+use crate::zobrist::Zone;
 use std::collections::HashSet;
 
-pub fn execute(state: &mut GameState, player_id: usize, card_name: &str, dogma_index: usize, input: Option<String>) -> Result<(), GameError> {
-    match card_name {
-        "Calendrier" => calendrier(state, player_id, dogma_index, input),
-        "Philosophie" => philosophie(state, player_id, dogma_index, input),
-        "Construction de canaux" => construction_de_canaux(state, player_id, dogma_index, input),
-        "Monothéisme" => monotheisme(state, player_id, dogma_index, input),
-        "Monnaie" => monnaie(state, player_id, dogma_index, input),
-        "Construction" => construction(state, player_id, dogma_index, input),
-        "Mathématiques" => mathematiques(state, player_id, dogma_index, input),
-        "Cartographie" => cartographie(state, player_id, dogma_index, input),
-        "Réseau Routier" => reseau_routier(state, player_id, dogma_index, input),
-        "Fermentation" => fermentation(state, player_id, dogma_index, input),
-        _ => {
-            println!("Warning: Unimplemented card {}", card_name);
-            Ok(())
-        }
-    }
+/// Hand-written dogma of the age 2 card `card_name`, if it has one.
+pub fn handler(card_name: &str) -> Option<Handler> {
+    let handler: Handler = match card_name {
+        "Calendrier" => calendrier,
+        "Philosophie" => philosophie,
+        "Construction de canaux" => construction_de_canaux,
+        "Monothéisme" => monotheisme,
+        "Monnaie" => monnaie,
+        "Construction" => construction,
+        "Mathématiques" => mathematiques,
+        "Cartographie" => cartographie,
+        "Réseau Routier" => reseau_routier,
+        "Fermentation" => fermentation,
+        _ => return None,
+    };
+    Some(handler)
 }
 
 // --- Card Implementations ---
@@ -47,7 +48,7 @@ fn philosophie(state: &mut GameState, player_id: usize, _idx: usize, input: Opti
     if sub_step == 0 {
          let p = &state.players[player_id];
          let candidates: Vec<Color> = p.board.iter()
-            .filter(|(_, stack)| stack.cards.len() > 1)
+            .filter(|(_, stack)| stack.len() > 1)
             .map(|(c, _)| *c)
             .collect();
             
@@ -82,14 +83,14 @@ fn philosophie(state: &mut GameState, player_id: usize, _idx: usize, input: Opti
         if state.players[player_id].hand.is_empty() { return Ok(()); }
         
         state.pending_input = Some(InputRequest::SelectCard {
-             player_id, source: state.players[player_id].hand.clone(), min: 0, max: 1
+             player_id, source: state.players[player_id].hand.to_vec(), min: 0, max: 1
         });
         update_sub_step(state, 2);
         Ok(())
     } else {
         if let Some(cid) = input.filter(|s| !s.is_empty()) {
             state.remove_from_hand(player_id, &cid)?;
-            state.score_card(player_id, cid)?;
+            state.score_card(player_id, &cid)?;
         }
         Ok(())
     }
@@ -117,7 +118,7 @@ fn construction_de_canaux(state: &mut GameState, player_id: usize, _idx: usize,
            if db.get(cid).unwrap().age == mh { indexes.push(i); }
        }
        for i in indexes.iter().rev() {
-           hand_to_move.push(p.hand.remove(*i));
+           hand_to_move.push(p.hand.remove_index(*i));
        }
    }
    
@@ -128,10 +129,15 @@ fn construction_de_canaux(state: &mut GameState, player_id: usize, _idx: usize,
            if db.get(cid).unwrap().age == ms { indexes.push(i); }
        }
        for i in indexes.iter().rev() {
-           score_to_move.push(p.score_pile.remove(*i));
+           score_to_move.push(p.score_pile.remove_index(*i));
        }
    }
    
+   for &idx in hand_to_move.iter().chain(&score_to_move) {
+       state.hash_index(idx, Zone::Hand(player_id));
+       state.hash_index(idx, Zone::Score(player_id));
+   }
+   let p = &mut state.players[player_id];
    p.hand.extend(score_to_move);
    p.score_pile.extend(hand_to_move);
    Ok(())
@@ -177,22 +183,18 @@ fn monotheisme(state: &mut GameState, player_id: usize, _idx: usize, input: Opti
             // Check text: "vers mon Influence !" -> Score Pile.
             
             // Remove from board
-            let db = crate::db::load_all_cards();
-            let color = db.get(&cid).unwrap().color;
-            if let Some(stack) = state.players[player_id].board.get_mut(&color) {
-                stack.cards.pop();
-            }
+            let cid = state.remove_from_board(player_id, &cid)?;
             
             // Add to Activator Score Pile
             state.score_card(activator_id, cid)?;
             
             // Victim bonus: Draw 1 archive x2 (Tuck)
             if let Some(c1) = state.draw_age(player_id, 1)? {
-                state.remove_from_hand(player_id, &c1)?;
+                state.remove_from_hand(player_id, c1)?;
                 state.tuck_card(player_id, c1)?;
             }
             if let Some(c2) = state.draw_age(player_id, 1)? {
-                state.remove_from_hand(player_id, &c2)?;
+                state.remove_from_hand(player_id, c2)?;
                 state.tuck_card(player_id, c2)?;
             }
         }
@@ -220,7 +222,7 @@ fn construction(state: &mut GameState, player_id: usize, _idx: usize, input: Opt
              // But draw is mandatory.
          } else {
              state.pending_input = Some(InputRequest::SelectCard {
-                 player_id, source: p.hand.clone(), min: min_req, max: min_req
+                 player_id, source: p.hand.to_vec(), min: min_req, max: min_req
              });
              update_sub_step(state, 1);
              return Ok(());
@@ -231,8 +233,8 @@ fn construction(state: &mut GameState, player_id: usize, _idx: usize, input: Opt
         if let Some(s) = input {
             let cards: Vec<&str> = s.split(',').filter(|x| !x.is_empty()).collect();
             for c in cards {
-                state.remove_from_hand(player_id, &c.to_string())?;
-                state.players[activator_id].hand.push(c.to_string());
+                state.remove_from_hand(player_id, c)?;
+                state.add_to_hand(activator_id, c)?;
             }
         }
         
@@ -263,8 +265,9 @@ fn monnaie(state: &mut GameState, player_id: usize, _idx: usize, input: Option<S
     let sub_step = state.dogma_state.as_ref().map(|s| s.sub_step.child_step).unwrap_or(0);
     if sub_step == 0 {
          let p = &state.players[player_id];
+         if p.hand.is_empty() { return Ok(()); }
          state.pending_input = Some(InputRequest::SelectCard {
-             player_id, source: p.hand.clone(), min: 0, max: 99
+             player_id, source: p.hand.to_vec(), min: 0, max: 99
          });
          update_sub_step(state, 1);
          Ok(())
@@ -278,14 +281,14 @@ fn monnaie(state: &mut GameState, player_id: usize, _idx: usize, input: Option<S
                 if let Some(card_data) = db.get(*c) {
                     values_seen.insert(card_data.age);
                 }
-                state.remove_from_hand(player_id, &c.to_string())?;
-                state.return_card(c.to_string())?;
+                state.remove_from_hand(player_id, c.as_ref())?;
+                state.return_card(c)?;
             }
             
             let count = values_seen.len();
             for _ in 0..count {
                 if let Some(cid) = state.draw_age(player_id, 2)? {
-                    state.remove_from_hand(player_id, &cid)?;
+                    state.remove_from_hand(player_id, cid)?;
                     state.score_card(player_id, cid)?;
                 }
             }
@@ -302,7 +305,7 @@ fn mathematiques(state: &mut GameState, player_id: usize, _idx: usize, input: Op
         if p.hand.is_empty() { return Ok(()); } 
          
         state.pending_input = Some(InputRequest::SelectCard {
-             player_id, source: p.hand.clone(), min: 0, max: 1
+             player_id, source: p.hand.to_vec(), min: 0, max: 1
         });
         update_sub_step(state, 1);
         Ok(())
@@ -313,7 +316,7 @@ fn mathematiques(state: &mut GameState, player_id: usize, _idx: usize, input: Op
             let age = db.get(&cid).unwrap().age;
             
             state.remove_from_hand(player_id, &cid)?;
-            state.return_card(cid)?;
+            state.return_card(&cid)?;
             
             if let Some(new_cid) = state.draw_age(player_id, age + 1)? {
                 state.meld(player_id, new_cid)?;
@@ -347,15 +350,12 @@ fn cartographie(state: &mut GameState, player_id: usize, _idx: usize, input: Opt
      } else {
          if let Some(cid) = input.filter(|s| !s.is_empty()) {
              // Transfer (Score -> Activator Score)
-             let p = &mut state.players[player_id];
-             if let Some(idx) = p.score_pile.iter().position(|x| x == &cid) {
-                 p.score_pile.remove(idx);
-             }
-             state.score_card(activator_id, cid)?;
+             let _ = state.remove_from_score(player_id, &cid);
+             state.score_card(activator_id, &cid)?;
              
              // Draw 1 Score
              if let Some(c) = state.draw_age(player_id, 1)? {
-                 state.remove_from_hand(player_id, &c)?;
+                 state.remove_from_hand(player_id, c)?;
                  state.score_card(player_id, c)?;
              }
          }
@@ -373,7 +373,7 @@ fn reseau_routier(state: &mut GameState, player_id: usize, _idx: usize, input: O
         let p = &state.players[player_id];
         if p.hand.is_empty() { return Ok(()); }
         state.pending_input = Some(InputRequest::SelectCard {
-             player_id, source: p.hand.clone(), min: 1, max: 2
+             player_id, source: p.hand.to_vec(), min: 1, max: 2
         });
         update_sub_step(state, 1);
         Ok(())
@@ -382,8 +382,7 @@ fn reseau_routier(state: &mut GameState, player_id: usize, _idx: usize, input: O
             let cards: Vec<&str> = s.split(',').filter(|x| !x.is_empty()).collect();
             let count = cards.len();
             for c in cards {
-                state.remove_from_hand(player_id, &c.to_string())?;
-                state.meld(player_id, c.to_string())?;
+                state.meld(player_id, c)?;
             }
             
             if count == 2 {
@@ -417,8 +416,7 @@ fn reseau_routier(state: &mut GameState, player_id: usize, _idx: usize, input: O
 fn fermentation(state: &mut GameState, player_id: usize, _idx: usize, _in: Option<String>) -> Result<(), GameError> {
     // Draw 2 for every two [Leaf].
     let p = &state.players[player_id];
-    let db = crate::db::load_all_cards();
-    let leaf_count = p.icon_counts(db).get(&crate::model::Symbol::Leaf).cloned().unwrap_or(0);
+    let leaf_count = p.icon_totals().get(crate::model::Symbol::Leaf);
     
     let draws = leaf_count / 2;
     for _ in 0..draws {
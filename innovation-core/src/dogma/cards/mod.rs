@@ -3,20 +3,46 @@ pub mod age2;
 
 use crate::game_state::GameState;
 use crate::errors::GameError;
-use crate::db::load_all_cards;
+use once_cell::sync::Lazy;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::sync::Mutex;
+
+static WARNED: Lazy<Mutex<FxHashMap<&'static str, FxHashSet<String>>>> = Lazy::new(Default::default);
+
+/// Prints "Warning: `what` `card_name`" to stderr the first time it comes up:
+/// simulations activate the same unimplemented cards over and over.
+pub(crate) fn warn_once(what: &'static str, card_name: &str) {
+    let Ok(mut warned) = WARNED.lock() else { return };
+    let cards = warned.entry(what).or_default();
+    if !cards.contains(card_name) {
+        eprintln!("Warning: {} {}", what, card_name);
+        cards.insert(card_name.to_string());
+    }
+}
+
+/// A card's hand-written dogma: `(state, player_id, dogma_index, input)`.
+pub type Handler = fn(&mut GameState, usize, usize, Option<String>) -> Result<(), GameError>;
+
+/// The hand-written dogma of `card_name`, if it has one.
+pub fn handler(card_name: &str) -> Option<Handler> {
+    match crate::db::card(card_name).map(|card| card.age) {
+        Some(1) => age1::handler(card_name),
+        Some(2) => age2::handler(card_name),
+        _ => None,
+    }
+}
+
+/// Whether `execute_effect` has a handler for `card_name`.
+pub fn has_legacy(card_name: &str) -> bool {
+    handler(card_name).is_some()
+}
 
 pub fn execute_effect(state: &mut GameState, player_id: usize, card_name: &str, dogma_index: usize, input: Option<String>) -> Result<(), GameError> {
-    let db = load_all_cards();
-    if let Some(card) = db.get(card_name) {
-        if card.age == 1 {
-            return age1::execute(state, player_id, card_name, dogma_index, input);
-        } else if card.age == 2 {
-            return age2::execute(state, player_id, card_name, dogma_index, input);
+    match handler(card_name) {
+        Some(handler) => handler(state, player_id, dogma_index, input),
+        None => {
+            warn_once("No execution logic found for", card_name);
+            Ok(())
         }
     }
-    
-    // Fallback if not found or not age 1/2
-    // If not found, maybe it's a test card or error?
-    println!("Warning: No execution logic found for {}", card_name);
-    Ok(())
 }
@@ -0,0 +1,93 @@
+//! How much of the card database the engine can run.
+//!
+//! `continue_execution` looks a card's dogmas up in the registry, falls back to the
+//! hand-written handlers of `dogma::cards` when the card has no registry entry, and
+//! does nothing otherwise. The report follows that dispatch and flags the card data
+//! it cannot line up: registry entries with another number of effects than the card
+//! has dogmas, dogmas keyed to a symbol the card does not show, and Figures karmas
+//! or inspire effects the engine does not implement.
+
+use crate::dogma::{cards, figures, karma, registry};
+use crate::model::{Card, Icon, Symbol};
+use std::fmt;
+
+/// What runs a dogma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DogmaSource {
+    Registry,
+    Legacy,
+    /// Nothing: the dogma is skipped.
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoverageProblem {
+    /// The registry holds `registry` effects for `dogmas` dogmas; effects past the
+    /// last dogma never run, and dogmas past the last effect are skipped.
+    LengthMismatch { registry: usize, dogmas: usize },
+    /// Dogma `index` is keyed to `symbol`, which is not among the card's icons.
+    SymbolNotOnCard { index: usize, symbol: Symbol },
+    /// Figures: the engine holds `engine` karmas for the `printed` ones of the card.
+    KarmaMismatch { engine: usize, printed: usize },
+    /// Figures: the card prints an inspire effect the engine does not implement.
+    MissingInspire,
+}
+
+impl fmt::Display for CoverageProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoverageProblem::LengthMismatch { registry, dogmas } => {
+                write!(f, "registry has {} effect(s) for {} dogma(s)", registry, dogmas)
+            }
+            CoverageProblem::SymbolNotOnCard { index, symbol } => {
+                write!(f, "dogma {} uses [{:?}], which the card does not show", index + 1, symbol)
+            }
+            CoverageProblem::KarmaMismatch { engine, printed } => {
+                write!(f, "engine has {} karma(s) for {} printed", engine, printed)
+            }
+            CoverageProblem::MissingInspire => write!(f, "inspire effect not implemented"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCoverage {
+    pub name: String,
+    /// One entry per dogma of the card.
+    pub dogmas: Vec<DogmaSource>,
+    pub problems: Vec<CoverageProblem>,
+}
+
+impl CardCoverage {
+    pub fn is_complete(&self) -> bool {
+        self.dogmas.iter().all(|&source| source != DogmaSource::Missing)
+    }
+}
+
+pub fn card_coverage(card: &Card) -> CardCoverage {
+    let effects = registry::get_effects(&card.name);
+    let legacy = effects.is_none() && cards::has_legacy(&card.name);
+    let dogmas = (0..card.dogmas.len()).map(|idx| match effects {
+        Some(effects) if idx < effects.len() => DogmaSource::Registry,
+        None if legacy => DogmaSource::Legacy,
+        _ => DogmaSource::Missing,
+    }).collect();
+
+    let mut problems = Vec::new();
+    if let Some(effects) = effects.filter(|effects| effects.len() != card.dogmas.len()) {
+        problems.push(CoverageProblem::LengthMismatch { registry: effects.len(), dogmas: card.dogmas.len() });
+    }
+    for (index, dogma) in card.dogmas.iter().enumerate() {
+        if !card.icons.contains(&Icon::Resource(dogma.symbol)) {
+            problems.push(CoverageProblem::SymbolNotOnCard { index, symbol: dogma.symbol });
+        }
+    }
+    let karmas = karma::get_karmas(&card.name).map_or(0, |k| k.len());
+    if karmas != card.karma.len() {
+        problems.push(CoverageProblem::KarmaMismatch { engine: karmas, printed: card.karma.len() });
+    }
+    if card.inspire.is_some() && figures::get_inspire(&card.name).is_none() {
+        problems.push(CoverageProblem::MissingInspire);
+    }
+    CardCoverage { name: card.name.clone(), dogmas, problems }
+}
@@ -1,24 +1,50 @@
-use crate::game_state::GameState;
+use crate::card_list::CardList;
+use crate::game_state::{log_action, GameState};
 use crate::errors::GameError;
 use crate::dogma::flow::DogmaExecutionState;
+use crate::rules::SharingBonus;
+use crate::dogma::karma::{self, KarmaEvent, KarmaTiming, KarmaTrigger};
+use crate::zobrist::Zone;
+use crate::db::CardIdx;
+use once_cell::sync::Lazy;
+use std::sync::OnceLock;
 
 pub fn execute_dogma(state: &mut GameState, player_id: usize, card_id: String) -> Result<(), GameError> {
+    match crate::db::card_index(&card_id) {
+        Some(idx) => execute_dogma_at(state, player_id, idx),
+        None => Err(inactive(&card_id)),
+    }
+}
+
+fn inactive(card_id: &str) -> GameError {
+    GameError::InvalidAction(format!("Cannot activate dogma: '{}' is not an active card on your board", card_id))
+}
+
+/// `execute_dogma` for an interned card.
+pub fn execute_dogma_at(state: &mut GameState, player_id: usize, idx: CardIdx) -> Result<(), GameError> {
     // 1. Validate card is active (on top of a pile on player's board)
-    let player = &state.players[player_id];
-    let is_active = player.board.values()
-        .any(|pile| pile.top() == Some(&card_id));
+    let card = crate::db::card_at(idx);
+    let board = &state.players[player_id].board;
+    if board.get(&card.color).and_then(|pile| pile.top_index()) != Some(idx) {
+        return Err(inactive(&card.name));
+    }
+    let card_id = card.name.clone();
     
-    if !is_active {
-        return Err(GameError::InvalidAction(format!(
-            "Cannot activate dogma: '{}' is not an active card on your board", card_id
-        )));
+    let event = KarmaEvent::card_at(player_id, KarmaTrigger::Dogma, idx);
+    if karma::intercept(state, &event, KarmaTiming::Instead)? {
+        return Ok(());
     }
     
     // Log dogma activation
-    state.action_log.push(format!("P{} active le dogme de '{}'", player_id, card_id));
+    log_action!(state, "P{} active le dogme de '{}'", player_id, card_id);
     
     // 2. Initialize State
-    let execution_state = DogmaExecutionState::new(card_id.clone(), 0, player_id, Vec::new());
+    let mut execution_state = DogmaExecutionState::new(card_id, 0, player_id, Vec::new());
+    if !card.dogmas.is_empty() {
+        if let Some(pile) = state.players[player_id].board.get(&card.color) {
+            execution_state.echoes = pile.visible_echoes(crate::db::load_all_cards());
+        }
+    }
     state.dogma_state = Some(execution_state);
     
     // 3. Start Execution
@@ -31,74 +57,63 @@ pub fn continue_execution(state: &mut GameState, mut input: Option<String>) -> R
         state.pending_input = None;
     }
     
-    // Load state
-    let mut execution_state = state.dogma_state.clone().ok_or(GameError::InvalidAction("No dogma execution in progress".into()))?;
-    
-    // Load card
-    let card = {
-        let db = crate::db::load_all_cards();
-        db.get(&execution_state.card_id).ok_or_else(|| GameError::CardNotFound(execution_state.card_id.clone()))?.clone()
+    // `state.dogma_state` is the execution state throughout: the effects read and
+    // update its sub-step and context, and it is left in place when pausing for input.
+    let card_id = &state.dogma_state.as_ref().ok_or_else(|| GameError::InvalidAction("No dogma execution in progress".into()))?.card_id;
+    let card_idx = crate::db::card_index(card_id)
+        .ok_or_else(|| GameError::CardNotFound(card_id.clone()))?;
+    let card = crate::db::card_at(card_idx);
+    let (effects, legacy) = match dispatch(card_idx) {
+        Dispatch::Effects(effects) => (Some(effects), None),
+        Dispatch::Handler(handler) => (None, Some(handler)),
+        Dispatch::Missing => (None, None),
     };
 
-    // Loop through dogmas starting from current index
-    while execution_state.dogma_index < card.dogmas.len() {
-        let dogma = &card.dogmas[execution_state.dogma_index];
+    // Loop through echo effects, then dogmas, starting from current index
+    loop {
+        let ds = execution_state(state);
+        let in_echoes = ds.echo_index < ds.echoes.len();
+        if !in_echoes && ds.dogma_index >= card.dogmas.len() {
+            break;
+        }
+        // Echo effects are cooperative and keyed on the card's featured icon (that of its first dogma)
+        let dogma = &card.dogmas[if in_echoes { 0 } else { ds.dogma_index }];
+        let is_supremacy = dogma.is_supremacy && !in_echoes;
         
-        // Ensure eligible players are calculated for this dogma
-        // We might want to do this only once per dogma index.
-        // Simplified: If current_player_index == 0, recalculate eligible.
-        if execution_state.current_player_index == 0 {
-             let symbol = dogma.symbol;
-             
-             let counts: Vec<u32> = state.players.iter()
-                .map(|p| *p.icon_counts(crate::db::load_all_cards()).get(&symbol).unwrap_or(&0))
-                .collect();
-             let activator_count = counts[execution_state.activator_id];
-             
-             let player_count = state.players.len();
-             let mut eligible = Vec::new();
-             
-             // Calc processing order
-             for i in 1..=player_count {
-                 let pid = (execution_state.activator_id + i) % player_count;
-                 
-                 let is_eligible = if dogma.is_supremacy {
-                     pid != execution_state.activator_id && counts[pid] < activator_count
-                 } else {
-                     counts[pid] >= activator_count
-                 };
-                 
-                 if is_eligible {
-                     eligible.push(pid);
-                 }
-             }
-             execution_state.eligible_players = eligible;
-             
-             // Save state update
-             state.dogma_state = Some(execution_state.clone());
+        // Ensure eligible players are calculated for this dogma, once per dogma index:
+        // resuming after an input must not recompute it from the (already changed) boards.
+        if ds.current_player_index == 0 && ds.eligible_players.is_empty() {
+            let activator_id = ds.activator_id;
+            let mut eligible = std::mem::take(&mut ds.eligible_players);
+            extend_eligible_players(state, activator_id, dogma.symbol, is_supremacy, &mut eligible);
+            execution_state(state).eligible_players = eligible;
         }
         
-    // Loop through eligible players
-        while execution_state.current_player_index < execution_state.eligible_players.len() {
-            let pid = execution_state.eligible_players[execution_state.current_player_index];
+        // Loop through eligible players
+        loop {
+            let ds = execution_state(state);
+            let Some(&pid) = ds.eligible_players.get(ds.current_player_index) else { break };
+            let (activator_id, dogma_index) = (ds.activator_id, ds.dogma_index);
+            let echo_card = in_echoes.then(|| ds.echoes[ds.echo_index].clone());
             
             // Execute Effect
             let input_for_this_step = input.take(); 
             
-            // Sync state before execution so called function sees correct player/index
-            state.dogma_state = Some(execution_state.clone());
-
             // Hybrid Dispatch: Check Registry first
-            if let Some(effects) = crate::dogma::registry::get_effects(&card.name) {
-                if execution_state.dogma_index < effects.len() {
-                    let effect = &effects[execution_state.dogma_index];
+            if let Some(echo_card) = echo_card {
+                match crate::dogma::registry::get_echo_effect(&echo_card) {
+                    Some(effect) => execute_effect_tree(state, pid, effect, input_for_this_step)?,
+                    None => crate::dogma::cards::warn_once("No echo effect logic found for", &echo_card),
+                }
+            } else if let Some(effects) = effects {
+                if let Some(effect) = effects.get(dogma_index) {
                     execute_effect_tree(state, pid, effect, input_for_this_step)?;
                 } else {
                     // Registry has fewer effects than card? Warning or no-op.
                 }
-            } else {
+            } else if let Some(handler) = legacy {
                 // Fallback to legacy
-                crate::dogma::cards::execute_effect(state, pid, &card.name, execution_state.dogma_index, input_for_this_step)?;
+                handler(state, pid, dogma_index, input_for_this_step)?;
             }
             
             // If effect requested input, Pause.
@@ -106,48 +121,117 @@ pub fn continue_execution(state: &mut GameState, mut input: Option<String>) -> R
                  return Ok(());
             }
             
-            // Re-sync execution_state from state.dogma_state to capture any sub_step changes
-            // made by execute_effect_tree (critical for Sequence effects)
-            if let Some(ds) = state.dogma_state.as_ref() {
-                execution_state.sub_step = ds.sub_step.clone();
-                execution_state.context_color = ds.context_color;
-                execution_state.context_value = ds.context_value;
-            }
-            
-            execution_state.sub_step.reset();
-            
-            // Track sharing: if this player is NOT the activator and this is NOT a supremacy dogma
-            // then an opponent benefited from a cooperative dogma
-            if pid != execution_state.activator_id && !dogma.is_supremacy {
-                execution_state.anyone_shared = true;
-            }
+            // Track sharing: if this player is NOT on the activator's team and this is NOT a supremacy dogma
+            // then an opponent benefited from a cooperative dogma (a partner sharing earns no bonus)
+            let shared = !state.config.teammates(pid, activator_id) && !is_supremacy;
             
             // Advance player
-            execution_state.current_player_index += 1;
-            state.dogma_state = Some(execution_state.clone());
+            let ds = execution_state(state);
+            ds.sub_step.reset();
+            ds.anyone_shared |= shared;
+            ds.current_player_index += 1;
         }
         
-        // Advance dogma
-        execution_state.dogma_index += 1;
-        execution_state.current_player_index = 0;
-        execution_state.eligible_players.clear();
-        execution_state.sub_step.reset();
-        state.dogma_state = Some(execution_state.clone());
+        // Advance echo or dogma
+        let ds = execution_state(state);
+        if in_echoes {
+            ds.echo_index += 1;
+        } else {
+            ds.dogma_index += 1;
+        }
+        ds.current_player_index = 0;
+        ds.eligible_players.clear();
+        ds.sub_step.reset();
     }
     
     // Sharing bonus: if any opponent benefited from a cooperative dogma, activator draws a free card
-    if execution_state.anyone_shared {
-        let activator = execution_state.activator_id;
-        if let Some(card_id) = state.draw_age(activator, 1)? {
-            state.action_log.push(format!("P{} pioche '{}' (bonus de coopération)", activator, card_id));
+    let ds = execution_state(state);
+    let (activator, anyone_shared) = (ds.activator_id, ds.anyone_shared);
+    if anyone_shared {
+        match state.config.ruleset.sharing_bonus {
+            SharingBonus::None => {}
+            SharingBonus::DrawAge(age) => {
+                if let Some(card_id) = state.draw_age(activator, age)? {
+                    log_action!(state, "P{} pioche '{}' (bonus de coopération)", activator, card_id);
+                }
+            }
+            SharingBonus::Draw => {
+                log_action!(state, "P{} reçoit le bonus de coopération", activator);
+                state.draw(activator)?;
+            }
         }
     }
     
     // Finished
     state.dogma_state = None;
+    let event = KarmaEvent::card_at(activator, KarmaTrigger::Dogma, card_idx);
+    karma::intercept(state, &event, KarmaTiming::After)?;
     Ok(())
 }
 
+/// How a card's dogmas run: registry effects first, else a hand-written handler.
+#[derive(Clone, Copy)]
+enum Dispatch {
+    Effects(&'static Vec<crate::model::Effect>),
+    Handler(crate::dogma::cards::Handler),
+    Missing,
+}
+
+/// `Dispatch` of the data-file cards, worked out the first time each is activated
+/// rather than through name lookups on every dogma.
+static DISPATCH: Lazy<Vec<OnceLock<Dispatch>>> = Lazy::new(|| {
+    (0..crate::db::load_all_cards().len()).map(|_| OnceLock::new()).collect()
+});
+
+fn dispatch(idx: CardIdx) -> Dispatch {
+    let resolve = || {
+        let card = crate::db::card_at(idx);
+        if let Some(effects) = crate::dogma::registry::get_effects(&card.name) {
+            return Dispatch::Effects(effects);
+        }
+        if let Some(handler) = crate::dogma::cards::handler(&card.name) {
+            return Dispatch::Handler(handler);
+        }
+        if !card.dogmas.is_empty() {
+            crate::dogma::cards::warn_once("No execution logic found for", &card.name);
+        }
+        Dispatch::Missing
+    };
+    match DISPATCH.get(idx as usize) {
+        Some(cell) => *cell.get_or_init(resolve),
+        // Registered cards are few; `warn_once` keeps their warning to one
+        None => resolve(),
+    }
+}
+
+fn execution_state(state: &mut GameState) -> &mut DogmaExecutionState {
+    state.dogma_state.as_mut().expect("dogma execution in progress")
+}
+
+/// Players a dogma featuring `symbol` applies to, in execution order (the activator
+/// last): for a demand, the opponents with fewer `symbol` icons than the activator;
+/// otherwise everyone with at least as many.
+pub fn eligible_players(state: &GameState, activator_id: usize, symbol: crate::model::Symbol, is_supremacy: bool) -> Vec<usize> {
+    let mut eligible = Vec::new();
+    extend_eligible_players(state, activator_id, symbol, is_supremacy, &mut eligible);
+    eligible
+}
+
+/// `eligible_players`, appended to `eligible` (to reuse its allocation).
+fn extend_eligible_players(state: &GameState, activator_id: usize, symbol: crate::model::Symbol, is_supremacy: bool, eligible: &mut Vec<usize>) {
+    let count = |pid: usize| state.players[pid].board.icon_count(symbol);
+    let activator_count = count(activator_id);
+    let player_count = state.players.len();
+    eligible.extend((1..=player_count)
+        .map(|i| (activator_id + i) % player_count)
+        .filter(|&pid| if is_supremacy {
+            // Partners never demand from each other
+            !state.config.teammates(pid, activator_id) && count(pid) < activator_count
+        } else {
+            pid == activator_id || count(pid) >= activator_count
+        }));
+}
+
 fn execute_effect_tree(state: &mut GameState, player_id: usize, effect: &crate::model::Effect, input: Option<String>) -> Result<(), GameError> {
     use crate::model::Effect;
     
@@ -163,8 +247,7 @@ fn execute_effect_tree(state: &mut GameState, player_id: usize, effect: &crate::
                      cv > *v
                  },
                  Condition::IconCountGreaterThanOrEqual(symbol, count) => {
-                     let db = crate::db::load_all_cards();
-                     let icon_count = state.players[player_id].icon_counts(db).get(symbol).cloned().unwrap_or(0);
+                     let icon_count = state.players[player_id].board.icon_count(*symbol);
                      icon_count >= *count as u32
                  },
                  Condition::ScoreGreaterThanHand => {
@@ -184,7 +267,7 @@ fn execute_effect_tree(state: &mut GameState, player_id: usize, effect: &crate::
             let target_age = (*age).unwrap_or(1);
             for _ in 0..*amount {
                 if let Some(card_id) = state.draw_age(player_id, target_age)? {
-                    state.action_log.push(format!("P{} drew '{}'", player_id, card_id));
+                    log_action!(state, "P{} drew '{}'", player_id, card_id);
                 }
             }
             Ok(())
@@ -193,8 +276,11 @@ fn execute_effect_tree(state: &mut GameState, player_id: usize, effect: &crate::
              let target_age = (*age).unwrap_or(1);
              for _ in 0..*amount {
                  if let Some(cid) = state.draw_age(player_id, target_age)? {
-                     state.action_log.push(format!("P{} drew and melded '{}'", player_id, cid));
+                     log_action!(state, "P{} drew and melded '{}'", player_id, cid);
+                     let color = crate::db::card(cid).map(|c| c.color);
                      state.meld(player_id, cid)?;
+                     // Like Meld: a following Splay without a color acts on the melded card's pile
+                     if let Some(ds) = state.dogma_state.as_mut() { ds.context_color = color; }
                  }
              }
              Ok(())
@@ -203,7 +289,7 @@ fn execute_effect_tree(state: &mut GameState, player_id: usize, effect: &crate::
              let target_age = (*age).or(context_value).unwrap_or(1);
              for _ in 0..*amount {
                  if let Some(cid) = state.draw_age(player_id, target_age)? {
-                     state.remove_from_hand(player_id, &cid)?;
+                     state.remove_from_hand(player_id, cid)?;
                      state.score_card(player_id, cid)?;
                  }
              }
@@ -272,6 +358,12 @@ fn execute_sequence(state: &mut GameState, player_id: usize, effects: &[crate::m
         
         execute_effect_tree(state, player_id, current_effect, step_input)?;
         
+        // A draw past age 10 ended the game: the rest of the sequence does not happen
+        if !state.winners.is_empty() {
+            if let Some(ds) = state.dogma_state.as_mut() { ds.sub_step.reset(); }
+            return Ok(());
+        }
+        
         if state.pending_input.is_some() {
             // Paused waiting for input. Save current position.
             let current_child = state.dogma_state.as_ref().map(|s| s.sub_step.child_step).unwrap_or(0);
@@ -303,24 +395,25 @@ fn execute_transfer(state: &mut GameState, player_id: usize, source: &crate::mod
 
     if child_step == 0 {
         let p = &state.players[player_id];
-        let mut candidates = Vec::new();
+        let mut candidates = CardList::new();
         
         match source {
             crate::model::Location::Board => {
-                 for pile in p.board.values() {
-                     if let Some(top) = pile.top() {
-                         candidates.push(top.clone());
-                     }
-                 }
+                 candidates.extend(p.board.values().filter_map(|pile| pile.top_index()));
             },
             crate::model::Location::Hand => {
                 candidates = p.hand.clone();
             },
+            crate::model::Location::Score => {
+                candidates = p.score_pile.clone();
+            },
+            crate::model::Location::Foreshadow => {
+                candidates = p.foreshadow.clone();
+            },
             _ => {} 
         }
         
-        let db = crate::db::load_all_cards();
-        candidates = apply_filters(candidates, filters, db, p);
+        candidates = apply_filters(candidates, filters, p);
         
         if candidates.is_empty() { return Ok(()); }
 
@@ -330,28 +423,8 @@ fn execute_transfer(state: &mut GameState, player_id: usize, source: &crate::mod
              let transferred_count = count;
              if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(transferred_count as u8); }
 
-             for cid in candidates {
-                 // Remove
-                 match source {
-                    crate::model::Location::Board => {
-                         let card = db.get(&cid).ok_or_else(|| GameError::CardNotFound(cid.clone()))?;
-                         if let Some(pile) = state.players[player_id].board.get_mut(&card.color) {
-                             if let Some(top) = pile.top() {
-                                 if *top == cid { pile.pop(); }
-                             }
-                         }
-                    },
-                    crate::model::Location::Hand => {
-                        state.remove_from_hand(player_id, &cid)?;
-                    },
-                    _ => {}
-                 }
-                 // Add
-                 match dest {
-                    crate::model::Location::Score => { state.score_card(dest_pid, cid)?; },
-                    crate::model::Location::Hand => { state.players[dest_pid].hand.push(cid); },
-                    _ => {}
-                 }
+             for cid in &candidates {
+                 move_card(state, player_id, dest_pid, source, dest, cid)?;
              }
              return Ok(());
         }
@@ -359,7 +432,7 @@ fn execute_transfer(state: &mut GameState, player_id: usize, source: &crate::mod
         // Request Input
         state.pending_input = Some(crate::dogma::flow::InputRequest::SelectCard {
             player_id, 
-            source: candidates, 
+            source: candidates.to_vec(), 
             min, 
             max
         });
@@ -372,41 +445,55 @@ fn execute_transfer(state: &mut GameState, player_id: usize, source: &crate::mod
              if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(count as u8); }
              
              for c in cards {
-                 // Remove
-                 match source {
-                    crate::model::Location::Board => {
-                        let db = crate::db::load_all_cards();
-                        let card = db.get(c).ok_or_else(|| GameError::CardNotFound(c.to_string()))?;
-                        if let Some(pile) = state.players[player_id].board.get_mut(&card.color) {
-                             pile.pop(); 
-                        }
-                    },
-                    crate::model::Location::Hand => {
-                        state.remove_from_hand(player_id, &c.to_string())?;
-                    },
-                    _ => {}
-                 }
-                 // Add
-                 match dest {
-                    crate::model::Location::Score => { state.score_card(dest_pid, c.to_string())?; },
-                    crate::model::Location::Hand => { state.players[dest_pid].hand.push(c.to_string()); },
-                    _ => {}
-                 }
+                 move_card(state, player_id, dest_pid, source, dest, c)?;
              }
          }
          Ok(())
     }
 }
 
+/// Moves one card between zones for `Transfer`: taken from `from_pid`'s `source`, given to `to_pid`'s `dest`.
+fn move_card(state: &mut GameState, from_pid: usize, to_pid: usize, source: &crate::model::Location, dest: &crate::model::Location, card_id: &str) -> Result<(), GameError> {
+    use crate::model::Location;
+    let cid = match source {
+        Location::Board => state.remove_from_board(from_pid, card_id)?,
+        Location::Hand => state.remove_from_hand(from_pid, card_id)?,
+        Location::Score => state.remove_from_score(from_pid, card_id)?,
+        Location::Display => {
+            let cid = state.players[from_pid].display.take()
+                .filter(|c| c == card_id)
+                .and_then(|c| crate::db::card(&c))
+                .ok_or_else(|| GameError::InvalidAction("Card not on display".into()))?;
+            state.hash_card(&cid.name, Zone::Display(from_pid));
+            &cid.name
+        },
+        Location::Foreshadow => {
+            let cid = state.players[from_pid].foreshadow.remove_card(card_id)
+                .ok_or_else(|| GameError::InvalidAction("Card not foreshadowed".into()))?;
+            state.hash_card(cid, Zone::Foreshadow(from_pid));
+            cid
+        },
+        _ => return Err(GameError::InvalidAction(format!("Cannot transfer from {:?}", source))),
+    };
+    match dest {
+        Location::Score => state.score_card(to_pid, cid),
+        Location::Hand => state.add_to_hand(to_pid, cid),
+        Location::Board => state.place_on_board(to_pid, cid),
+        Location::Deck => state.return_card(cid),
+        Location::Foreshadow => state.foreshadow_card(to_pid, cid),
+        Location::Display => Err(GameError::InvalidAction("Only digs put artifacts on display".into())),
+        Location::Achievements => Err(GameError::InvalidAction("Cannot transfer to achievements".into())),
+    }
+}
+
 fn execute_recycle(state: &mut GameState, player_id: usize, min: usize, max: usize, source: &crate::model::Location, filters: &[crate::model::Filter], input: Option<String>) -> Result<(), GameError> {
-    let db = crate::db::load_all_cards();
     let mut p_cards = match source {
         crate::model::Location::Hand => state.players[player_id].hand.clone(),
         crate::model::Location::Score => state.players[player_id].score_pile.clone(),
-        _ => Vec::new(),
+        _ => CardList::new(),
     };
     
-    p_cards = apply_filters(p_cards, filters, db, &state.players[player_id]);
+    p_cards = apply_filters(p_cards, filters, &state.players[player_id]);
 
     let child_step = state.dogma_state.as_ref().map(|s| s.sub_step.child_step).unwrap_or(0);
 
@@ -418,7 +505,7 @@ fn execute_recycle(state: &mut GameState, player_id: usize, min: usize, max: usi
         }
         state.pending_input = Some(crate::dogma::flow::InputRequest::SelectCard {
             player_id, 
-            source: p_cards, 
+            source: p_cards.to_vec(), 
             min, 
             max 
         });
@@ -432,16 +519,11 @@ fn execute_recycle(state: &mut GameState, player_id: usize, min: usize, max: usi
 
              for c in cards {
                  match source {
-                    crate::model::Location::Hand => { state.remove_from_hand(player_id, &c.to_string())?; },
-                    crate::model::Location::Score => { 
-                        let p = &mut state.players[player_id];
-                        if let Some(idx) = p.score_pile.iter().position(|x| x == c) {
-                            p.score_pile.remove(idx);
-                        }
-                    },
+                    crate::model::Location::Hand => { state.remove_from_hand(player_id, c)?; },
+                    crate::model::Location::Score => { state.remove_from_score(player_id, c)?; },
                     _ => {}
                  }
-                 state.return_card(c.to_string())?;
+                 state.return_card(c)?;
              }
         }
         Ok(())
@@ -453,10 +535,7 @@ fn execute_score(state: &mut GameState, player_id: usize, min: usize, max: usize
     
     if child_step == 0 {
         let p = &state.players[player_id];
-        let mut candidates = p.hand.clone();
-        let db = crate::db::load_all_cards();
-        
-        candidates = apply_filters(candidates, filters, db, p);
+        let candidates = apply_filters(p.hand.clone(), filters, p);
         
         if candidates.is_empty() { 
             if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(0); }
@@ -465,7 +544,7 @@ fn execute_score(state: &mut GameState, player_id: usize, min: usize, max: usize
         
         state.pending_input = Some(crate::dogma::flow::InputRequest::SelectCard {
             player_id, 
-            source: candidates, 
+            source: candidates.to_vec(), 
             min, 
             max 
         });
@@ -478,8 +557,8 @@ fn execute_score(state: &mut GameState, player_id: usize, min: usize, max: usize
              if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(count as u8); }
 
              for c in cards {
-                 state.remove_from_hand(player_id, &c.to_string())?;
-                 state.score_card(player_id, c.to_string())?;
+                 state.remove_from_hand(player_id, c)?;
+                 state.score_card(player_id, c)?;
              }
          }
          Ok(())
@@ -490,10 +569,7 @@ fn execute_meld(state: &mut GameState, player_id: usize, min: usize, max: usize,
     let child_step = state.dogma_state.as_ref().map(|s| s.sub_step.child_step).unwrap_or(0);
     if child_step == 0 {
         let p = &state.players[player_id];
-        let mut candidates = p.hand.clone();
-        let db = crate::db::load_all_cards();
-        
-        candidates = apply_filters(candidates, filters, db, p);
+        let candidates = apply_filters(p.hand.clone(), filters, p);
         
         if candidates.is_empty() { 
             if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(0); }
@@ -501,8 +577,8 @@ fn execute_meld(state: &mut GameState, player_id: usize, min: usize, max: usize,
         }
         
         if candidates.len() == 1 && min == 1 && max == 1 {
-            let cid = candidates[0].clone();
-             let card_color = db.get(&cid).ok_or_else(|| GameError::CardNotFound(cid.clone()))?.color;
+            let cid = &candidates[0];
+             let card_color = crate::db::card(cid).ok_or_else(|| GameError::CardNotFound(cid.clone()))?.color;
              if let Some(ds) = state.dogma_state.as_mut() { 
                  ds.context_color = Some(card_color); 
                  ds.context_value = Some(1);
@@ -514,7 +590,7 @@ fn execute_meld(state: &mut GameState, player_id: usize, min: usize, max: usize,
         
         state.pending_input = Some(crate::dogma::flow::InputRequest::SelectCard {
             player_id, 
-            source: candidates, 
+            source: candidates.to_vec(), 
             min, 
             max 
         });
@@ -526,15 +602,14 @@ fn execute_meld(state: &mut GameState, player_id: usize, min: usize, max: usize,
              let count = cards.len();
              
              for cid in cards {
-                 let db = crate::db::load_all_cards();
-                 let card_color = db.get(cid).ok_or_else(|| GameError::CardNotFound(cid.to_string()))?.color;
+                 let card_color = crate::db::card(cid).ok_or_else(|| GameError::CardNotFound(cid.to_string()))?.color;
                  if let Some(ds) = state.dogma_state.as_mut() { 
                      ds.context_color = Some(card_color); 
                      // Since loop updates, keep context_value accumulating or strict count?
                      // Usually dogma says "If you meld X..." so total count matters.
                      // But strictly speaking, context_value is usually set to TOTAL count.
                  }
-                 state.meld(player_id, cid.to_string())?;
+                 state.meld(player_id, cid)?;
              }
              // Set final count
              if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(count as u8); }
@@ -547,10 +622,7 @@ fn execute_tuck(state: &mut GameState, player_id: usize, min: usize, max: usize,
      let child_step = state.dogma_state.as_ref().map(|s| s.sub_step.child_step).unwrap_or(0);
      if child_step == 0 {
          let p = &state.players[player_id];
-         let mut candidates = p.hand.clone();
-         let db = crate::db::load_all_cards();
-         
-         candidates = apply_filters(candidates, filters, db, p);
+         let candidates = apply_filters(p.hand.clone(), filters, p);
          
          if candidates.is_empty() { 
              if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(0); }
@@ -559,7 +631,7 @@ fn execute_tuck(state: &mut GameState, player_id: usize, min: usize, max: usize,
          
          state.pending_input = Some(crate::dogma::flow::InputRequest::SelectCard {
              player_id, 
-             source: candidates, 
+             source: candidates.to_vec(), 
              min, 
              max 
          });
@@ -571,11 +643,10 @@ fn execute_tuck(state: &mut GameState, player_id: usize, min: usize, max: usize,
               let count = cards.len();
               
               for c in cards {
-                  let db = crate::db::load_all_cards();
-                  let card_color = db.get(c).ok_or_else(|| GameError::CardNotFound(c.to_string()))?.color;
+                  let card_color = crate::db::card(c).ok_or_else(|| GameError::CardNotFound(c.to_string()))?.color;
                   if let Some(ds) = state.dogma_state.as_mut() { ds.context_color = Some(card_color); }
-                  state.remove_from_hand(player_id, &c.to_string())?;
-                  state.tuck_card(player_id, c.to_string())?;
+                  state.remove_from_hand(player_id, c)?;
+                  state.tuck_card(player_id, c)?;
               }
               if let Some(ds) = state.dogma_state.as_mut() { ds.context_value = Some(count as u8); }
           }
@@ -588,56 +659,46 @@ fn execute_splay(state: &mut GameState, player_id: usize, direction: &crate::mod
         Some(c) => *c,
         None => {
             state.dogma_state.as_ref().and_then(|ds| ds.context_color)
-                .ok_or(GameError::InvalidAction("No context color for splay".into()))?
+                .ok_or_else(|| GameError::InvalidAction("No context color for splay".into()))?
         }
     };
     state.splay(player_id, target_color, *direction)?;
     Ok(())
 }
 // Helper for filtering candidates
-fn apply_filters(mut candidates: Vec<String>, filters: &[crate::model::Filter], db: &std::collections::HashMap<String, crate::model::Card>, player: &crate::player::Player) -> Vec<String> {
-    // eprintln!("Applying filters: {:?} to candidates: {:?}", filters, candidates);
+fn apply_filters(mut candidates: CardList, filters: &[crate::model::Filter], player: &crate::player::Player) -> CardList {
+    let card = |id: &str| crate::db::card(id);
     for f in filters {
         match f {
             crate::model::Filter::ColorsPresentOnBoard => {
-                candidates.retain(|id| {
-                    if let Some(c) = db.get(id) {
-                        player.board.contains_key(&c.color)
-                    } else { false }
-                });
+                candidates.retain(|id| card(id).is_some_and(|c| player.board.contains_key(&c.color)));
             },
             crate::model::Filter::MinAge => {
                 if candidates.is_empty() { continue; }
-                let min_age = candidates.iter().filter_map(|id| db.get(id)).map(|c| c.age).min().unwrap_or(0);
-                candidates.retain(|id| db.get(id).map(|c| c.age).unwrap_or(0) == min_age);
+                let min_age = candidates.iter().filter_map(|id| card(id)).map(|c| c.age).min().unwrap_or(0);
+                candidates.retain(|id| card(id).map(|c| c.age).unwrap_or(0) == min_age);
             },
             crate::model::Filter::MaxAge => {
                 if candidates.is_empty() { continue; }
-                let max_age = candidates.iter().filter_map(|id| db.get(id)).map(|c| c.age).max().unwrap_or(0);
-                candidates.retain(|id| db.get(id).map(|c| c.age).unwrap_or(0) == max_age);
+                let max_age = candidates.iter().filter_map(|id| card(id)).map(|c| c.age).max().unwrap_or(0);
+                candidates.retain(|id| card(id).map(|c| c.age).unwrap_or(0) == max_age);
             },
             crate::model::Filter::Value(v) => {
-                 candidates.retain(|id| db.get(id).map(|c| c.age).unwrap_or(0) == *v);
+                 candidates.retain(|id| card(id).map(|c| c.age).unwrap_or(0) == *v);
             },
             crate::model::Filter::Color(col) => {
-                 candidates.retain(|id| db.get(id).map(|c| c.color).unwrap_or(crate::model::Color::Red) == *col);
+                 candidates.retain(|id| card(id).map(|c| c.color).unwrap_or(crate::model::Color::Red) == *col);
             },
             crate::model::Filter::Icon(target_sym) => {
                  candidates.retain(|id| {
-                     if let Some(c) = db.get(id) {
-                         c.icons.iter().any(|icon| match icon {
-                             crate::model::Icon::Resource(s) => s == target_sym,
-                             _ => false
-                         })
-                     } else { false }
+                     card(id).is_some_and(|c| c.icons.iter().any(|icon| match icon {
+                         crate::model::Icon::Resource(s) => s == target_sym,
+                         _ => false
+                     }))
                  });
             },
             crate::model::Filter::ColorsNotOnBoard => {
-                candidates.retain(|id| {
-                    if let Some(c) = db.get(id) {
-                        !player.board.contains_key(&c.color)
-                    } else { false }
-                });
+                candidates.retain(|id| card(id).is_some_and(|c| !player.board.contains_key(&c.color)));
             },
             _ => {} // Implement others as needed
         }
@@ -654,11 +715,9 @@ fn execute_draw_until_no_match(
     on_match: &crate::model::Effect
 ) -> Result<(), GameError> {
     use crate::model::DrawnCardCondition;
-    let db = crate::db::load_all_cards();
-    
     loop {
         if let Some(cid) = state.draw_age(player_id, age)? {
-            let card = db.get(&cid).ok_or_else(|| GameError::CardNotFound(cid.clone()))?;
+            let card = crate::db::card(cid).ok_or_else(|| GameError::CardNotFound(cid.clone()))?;
             
             // Get condition symbol for logging
             let condition_symbol = match condition {
@@ -678,42 +737,33 @@ fn execute_draw_until_no_match(
                 },
             };
             
-            // Format card icons for logging
-            let card_icons: String = card.icons.iter()
-                .filter_map(|icon| match icon {
-                    crate::model::Icon::Resource(s) => Some(format!("{:?}", s)),
-                    _ => None
-                })
-                .collect::<Vec<_>>()
-                .join(", ");
-            
             if matches {
-                state.action_log.push(format!(
+                log_action!(state, 
                     "P{} pioche '{}'. Elle produit {} → comptabilisée!",
                     player_id, cid, condition_symbol
-                ));
+                );
                 
                 // Execute on_match effect (typically Score)
                 match on_match {
                     crate::model::Effect::Score { .. } => {
-                        state.remove_from_hand(player_id, &cid)?;
+                        state.remove_from_hand(player_id, cid)?;
                         state.score_card(player_id, cid)?;
                     },
                     _ => {
                         execute_effect_tree(state, player_id, on_match, None)?;
                     }
                 }
-                state.action_log.push(format!("P{} répète l'effet...", player_id));
+                log_action!(state, "P{} répète l'effet...", player_id);
             } else {
-                state.action_log.push(format!(
+                log_action!(state, 
                     "P{} pioche '{}'. Elle ne produit pas {} → gardée en main.",
                     player_id, cid, condition_symbol
-                ));
+                );
                 // Keep in hand and stop
                 break;
             }
         } else {
-            state.action_log.push(format!("P{}: plus de cartes à piocher.", player_id));
+            log_action!(state, "P{}: plus de cartes à piocher.", player_id);
             break;
         }
     }
@@ -730,10 +780,8 @@ fn execute_draw_and_check(
     on_fail: Option<&crate::model::Effect>
 ) -> Result<(), GameError> {
     use crate::model::DrawnCardCondition;
-    let db = crate::db::load_all_cards();
-    
     if let Some(cid) = state.draw_age(player_id, age)? {
-        let card = db.get(&cid).ok_or_else(|| GameError::CardNotFound(cid.clone()))?;
+        let card = crate::db::card(cid).ok_or_else(|| GameError::CardNotFound(cid.clone()))?;
         
         let matches = match condition {
             DrawnCardCondition::HasIcon(sym) => {
@@ -801,7 +849,7 @@ fn execute_draw_and_score_unique_colors(
     // Draw and score that many cards
     for _ in 0..unique_count {
         if let Some(cid) = state.draw_age(player_id, age)? {
-            state.remove_from_hand(player_id, &cid)?;
+            state.remove_from_hand(player_id, cid)?;
             state.score_card(player_id, cid)?;
         }
     }
@@ -817,8 +865,7 @@ fn execute_draw_for_icon_pairs(
     symbol: &crate::model::Symbol,
     divisor: u8
 ) -> Result<(), GameError> {
-    let db = crate::db::load_all_cards();
-    let icon_count = state.players[player_id].icon_counts(db).get(symbol).cloned().unwrap_or(0);
+    let icon_count = state.players[player_id].board.icon_count(*symbol);
     let draw_count = icon_count / divisor as u32;
     
     for _ in 0..draw_count {
@@ -0,0 +1,330 @@
+//! Plain-language rendering of `Effect` trees.
+//!
+//! The sentences describe what the executor does with an effect, not what the card
+//! says, so printing both side by side shows where the registry drifts from the card
+//! text. Parts the executor skips (`Return`, `Exchange`, `Choice`, the `Symbol`
+//! filter) are rendered and marked as such. The French wording follows the card texts
+//! of `data/cards.json`.
+
+use crate::model::{Color, Condition, DrawnCardCondition, Effect, Filter, Location, SplayDirection};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Fr,
+}
+
+impl Lang {
+    pub const NAMES: [&'static str; 2] = ["en", "fr"];
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "en" => Some(Lang::En),
+            "fr" => Some(Lang::Fr),
+            _ => None,
+        }
+    }
+
+    fn pick(self, en: &str, fr: &str) -> String {
+        match self {
+            Lang::En => en.to_string(),
+            Lang::Fr => fr.to_string(),
+        }
+    }
+}
+
+/// `effect` in plain words: one sentence per step of a top-level sequence.
+pub fn explain(effect: &Effect, lang: Lang) -> String {
+    match effect {
+        Effect::Sequence(effects) if !effects.is_empty() => {
+            effects.iter().map(|e| sentence(clause(e, lang))).collect::<Vec<_>>().join(" ")
+        }
+        _ => sentence(clause(effect, lang)),
+    }
+}
+
+/// A whole dogma: demands are addressed to the players they hit.
+pub fn explain_dogma(effect: &Effect, is_supremacy: bool, lang: Lang) -> String {
+    if !is_supremacy {
+        return explain(effect, lang);
+    }
+    let text = explain(effect, lang);
+    let mut chars = text.chars();
+    let first = chars.next().map(|c| c.to_lowercase().to_string()).unwrap_or_default();
+    format!("{} {}{}", lang.pick("I demand:", "J'exige :"), first, chars.as_str())
+}
+
+fn sentence(clause: String) -> String {
+    let mut chars = clause.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+fn skipped(lang: Lang) -> String {
+    lang.pick(" (not run by the engine)", " (ignoré par le moteur)")
+}
+
+fn number(n: u8, lang: Lang) -> String {
+    const EN: [&str; 11] = ["no", "a", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten"];
+    const FR: [&str; 11] = ["aucune", "une", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix"];
+    match lang {
+        Lang::En => EN.get(n as usize).map_or_else(|| n.to_string(), |w| w.to_string()),
+        Lang::Fr => FR.get(n as usize).map_or_else(|| n.to_string(), |w| w.to_string()),
+    }
+}
+
+/// "a 1", "two 3s" / "une 1", "deux 3".
+fn ages(amount: u8, age: u8, lang: Lang) -> String {
+    match lang {
+        Lang::En if amount != 1 => format!("{} {}s", number(amount, lang), age),
+        _ => format!("{} {}", number(amount, lang), age),
+    }
+}
+
+fn color(color: Color, lang: Lang) -> String {
+    let (en, fr) = match color {
+        Color::Red => ("red", "rouge"),
+        Color::Blue => ("blue", "bleue"),
+        Color::Green => ("green", "verte"),
+        Color::Yellow => ("yellow", "jaune"),
+        Color::Purple => ("purple", "violette"),
+    };
+    lang.pick(en, fr)
+}
+
+fn location(location: Location, activator: bool, lang: Lang) -> String {
+    let (en, fr) = match (location, activator) {
+        (Location::Hand, false) => ("your hand", "votre Main"),
+        (Location::Hand, true) => ("my hand", "ma Main"),
+        (Location::Score, false) => ("your score pile", "votre Influence"),
+        (Location::Score, true) => ("my score pile", "mon Influence"),
+        (Location::Board, false) => ("your board", "votre Zone de Jeu"),
+        (Location::Board, true) => ("my board", "ma Zone de Jeu"),
+        (Location::Deck, _) => ("the deck", "la pioche"),
+        (Location::Achievements, false) => ("your achievements", "vos Dominations"),
+        (Location::Achievements, true) => ("my achievements", "mes Dominations"),
+        (Location::Foreshadow, false) => ("your forecast", "vos cartes en prévision"),
+        (Location::Foreshadow, true) => ("my forecast", "mes cartes en prévision"),
+        (Location::Display, false) => ("your display", "votre exposition"),
+        (Location::Display, true) => ("my display", "mon exposition"),
+    };
+    lang.pick(en, fr)
+}
+
+/// Where cards are taken from: on a board, only top cards can be.
+fn source(location: Location, lang: Lang) -> String {
+    match location {
+        Location::Board => lang.pick("your top cards", "vos cartes Actives"),
+        other => self::location(other, false, lang),
+    }
+}
+
+/// Cards picked by an effect, around the place they come from: "a card" / "une
+/// carte" goes before it, and in French most filters go after it, as on the cards.
+fn cards(min: u8, max: u8, filters: &[Filter], lang: Lang) -> (String, String) {
+    let plural = max > 1;
+    let count = match (min, max) {
+        (_, u8::MAX) => lang.pick("any number of", "autant de"),
+        (0, 1) => lang.pick("up to one", "jusqu'à une"),
+        (0, n) => format!("{} {}", lang.pick("up to", "jusqu'à"), number(n, lang)),
+        (a, b) if a == b => number(a, lang),
+        (a, b) => match lang {
+            Lang::En => format!("{} to {}", number(a, lang), number(b, lang)),
+            Lang::Fr => format!("de {} à {}", number(a, lang), number(b, lang)),
+        },
+    };
+    let noun = match (lang, plural) {
+        (Lang::En, false) => "card",
+        (Lang::En, true) => "cards",
+        (Lang::Fr, false) => "carte",
+        (Lang::Fr, true) => "cartes",
+    };
+    let mut head = format!("{} {}", count, noun);
+    let mut tail = String::new();
+    for filter in filters {
+        let qualifier = match filter {
+            Filter::Color(c) => lang.pick(&format!(" of color {}", color(*c, lang)), &format!(" de couleur {}", color(*c, lang))),
+            Filter::Value(v) => lang.pick(&format!(" of value {}", v), &format!(" de valeur {}", v)),
+            Filter::Icon(s) => match (lang, plural) {
+                (Lang::En, _) => format!(" with a [{:?}]", s),
+                (Lang::Fr, false) => format!(" qui produit du [{:?}]", s),
+                (Lang::Fr, true) => format!(" qui produisent du [{:?}]", s),
+            },
+            Filter::Symbol(s) => format!("{}{}", lang.pick(&format!(" with a [{:?}]", s), &format!(" avec du [{:?}]", s)), skipped(lang)),
+            Filter::MaxAge => lang.pick(" of the highest value", " de la valeur la plus élevée"),
+            Filter::MinAge => lang.pick(" of the lowest value", " de la valeur la plus basse"),
+            Filter::ColorsPresentOnBoard => lang.pick(" of a color on your board", " d'une couleur présente dans votre Zone de Jeu"),
+            Filter::ColorsNotOnBoard => lang.pick(" of a color not on your board", " d'une couleur absente de votre Zone de Jeu"),
+        };
+        match (lang, filter) {
+            (Lang::En, _) | (Lang::Fr, Filter::Icon(_)) => head += &qualifier,
+            (Lang::Fr, _) => tail += &qualifier,
+        }
+    }
+    if lang == Lang::Fr && max == u8::MAX {
+        tail += " que vous voulez";
+    }
+    (head, tail)
+}
+
+/// "{verb} {cards} from {place}", the cards taken from the player's own `place`.
+fn take(verb: &str, min: u8, max: u8, filters: &[Filter], place: &str, lang: Lang) -> String {
+    let (head, tail) = cards(min, max, filters, lang);
+    format!("{} {} {} {}{}", verb, head, lang.pick("from", "de"), place, tail)
+}
+
+fn condition(condition: &Condition, lang: Lang) -> String {
+    match condition {
+        Condition::ContextValueGreaterThan(0) => lang.pick("you do", "vous le faites"),
+        Condition::ContextValueGreaterThan(n) => match lang {
+            Lang::En => format!("you do with more than {} cards", n),
+            Lang::Fr => format!("vous le faites avec plus de {} cartes", n),
+        },
+        Condition::IconCountGreaterThanOrEqual(s, n) => match lang {
+            Lang::En => format!("you have at least {} [{:?}]", n, s),
+            Lang::Fr => format!("vous produisez au moins {} [{:?}]", n, s),
+        },
+        Condition::ScoreGreaterThanHand => lang.pick(
+            "you have more cards in your score pile than in your hand",
+            "vous avez plus de cartes dans votre Influence que dans votre Main",
+        ),
+        Condition::True => lang.pick("always", "toujours"),
+    }
+}
+
+fn drawn_condition(condition: &DrawnCardCondition, lang: Lang) -> String {
+    match condition {
+        DrawnCardCondition::HasIcon(s) => lang.pick(&format!("has a [{:?}]", s), &format!("produit du [{:?}]", s)),
+        DrawnCardCondition::ColorOnBoard => lang.pick("is of a color on your board", "est d'une couleur présente dans votre Zone de Jeu"),
+    }
+}
+
+fn then(lang: Lang) -> String {
+    lang.pick(", then ", ", puis ")
+}
+
+fn clause(effect: &Effect, lang: Lang) -> String {
+    match effect {
+        // Without an age, the executor draws 1s
+        Effect::Draw { amount, age } => format!("{} {}", lang.pick("draw", "piochez"), ages(*amount, age.unwrap_or(1), lang)),
+        Effect::DrawAndMeld { amount, age } => {
+            let drawn = ages(*amount, age.unwrap_or(1), lang);
+            match lang {
+                Lang::En => format!("draw and meld {}", drawn),
+                Lang::Fr => format!("piochez {} et mettez-{} en jeu", drawn, if *amount > 1 { "les" } else { "la" }),
+            }
+        }
+        Effect::DrawAndScore { amount, age } => {
+            let drawn = match age {
+                Some(age) => ages(*amount, *age, lang),
+                None => lang.pick(
+                    "a card of value equal to the number of cards of the previous step (1 if none)",
+                    "une carte de valeur égale au nombre de cartes de l'étape précédente (1 à défaut)",
+                ),
+            };
+            match lang {
+                Lang::En => format!("draw and score {}", drawn),
+                Lang::Fr => format!("piochez {} et comptabilisez-{}", drawn, if *amount > 1 { "les" } else { "la" }),
+            }
+        }
+        Effect::Return { count, source: from } => {
+            format!("{}{}", take(&lang.pick("return", "recyclez"), *count, *count, &[], &source(*from, lang), lang), skipped(lang))
+        }
+        Effect::Score { min, max, filters } => {
+            take(&lang.pick("score", "comptabilisez"), *min, *max, filters, &location(Location::Hand, false, lang), lang)
+        }
+        Effect::Exchange { source: from, dest } => match lang {
+            Lang::En => format!("exchange the cards of {} with those of {}{}", source(*from, lang), location(*dest, false, lang), skipped(lang)),
+            Lang::Fr => format!("échangez les cartes de {} avec celles de {}{}", source(*from, lang), location(*dest, false, lang), skipped(lang)),
+        },
+        // Melds and tucks always take cards from the hand
+        Effect::Tuck { min, max, filters, .. } => {
+            take(&lang.pick("tuck", "archivez"), *min, *max, filters, &location(Location::Hand, false, lang), lang)
+        }
+        Effect::Meld { min, max, filters, .. } => {
+            take(&lang.pick("meld", "mettez en jeu"), *min, *max, filters, &location(Location::Hand, false, lang), lang)
+        }
+        Effect::Splay { direction, color: pile } => {
+            let way = match direction {
+                SplayDirection::None => None,
+                SplayDirection::Left => Some(lang.pick("left", "à gauche")),
+                SplayDirection::Right => Some(lang.pick("right", "à droite")),
+                SplayDirection::Up => Some(lang.pick("up", "vers le haut")),
+            };
+            let pile = match (pile, lang) {
+                (Some(c), Lang::En) => format!("your {} cards", color(*c, lang)),
+                (Some(c), Lang::Fr) => format!("votre pile {}", color(*c, lang)),
+                (None, _) => lang.pick("the color of the last card handled", "la couleur de la dernière carte jouée"),
+            };
+            match (way, lang) {
+                (Some(way), Lang::En) => format!("splay {} {}", pile, way),
+                (Some(way), Lang::Fr) => format!("décalez {} {}", pile, way),
+                (None, Lang::En) => format!("unsplay {}", pile),
+                (None, Lang::Fr) => format!("annulez le décalage de {}", pile),
+            }
+        }
+        Effect::Recycle { min, max, source: from, filters } => {
+            take(&lang.pick("return", "recyclez"), *min, *max, filters, &source(*from, lang), lang)
+        }
+        Effect::Transfer { source: from, dest, dest_is_activator, filters, min, max } => {
+            let taken = take(&lang.pick("transfer", "transférez"), *min, *max, filters, &source(*from, lang), lang);
+            format!("{} {} {}", taken, lang.pick("to", "vers"), location(*dest, *dest_is_activator, lang))
+        }
+        Effect::Choice { options } => {
+            let options: Vec<String> = options.iter().map(|o| clause(o, lang)).collect();
+            match lang {
+                Lang::En => format!("choose one: {}{}", options.join("; or "), skipped(lang)),
+                Lang::Fr => format!("au choix : {}{}", options.join(" ; ou "), skipped(lang)),
+            }
+        }
+        Effect::Conditional { condition: Condition::True, success, .. } => clause(success, lang),
+        Effect::Conditional { condition: test, success, failure } => {
+            let mut text = format!("{} {}, {}", lang.pick("if", "si"), condition(test, lang), clause(success, lang));
+            if let Some(failure) = failure {
+                text += &format!("{} {}", lang.pick("; otherwise,", " ; sinon,"), clause(failure, lang));
+            }
+            text
+        }
+        Effect::Sequence(effects) => effects.iter().map(|e| clause(e, lang)).collect::<Vec<_>>().join(&then(lang)),
+        Effect::DrawUntilNoMatch { age, condition: test, on_match } => {
+            // A Score on match scores the revealed card itself
+            let action = match on_match.as_ref() {
+                Effect::Score { .. } => lang.pick("score it", "comptabilisez-la"),
+                other => clause(other, lang),
+            };
+            match lang {
+                Lang::En => format!("draw and reveal a {}; while it {}, {} and draw again; keep the last card in your hand", age, drawn_condition(test, lang), action),
+                Lang::Fr => format!("piochez une {} et montrez-la ; tant qu'elle {}, {} et recommencez ; gardez la dernière carte dans votre Main", age, drawn_condition(test, lang), action),
+            }
+        }
+        Effect::DrawAndCheck { age, condition: test, on_match, on_fail } => {
+            // A Sequence on match melds the revealed card in place of its first step
+            let action = match on_match.as_ref() {
+                Effect::Sequence(effects) => std::iter::once(lang.pick("meld it", "mettez-la en jeu"))
+                    .chain(effects.iter().skip(1).map(|e| clause(e, lang)))
+                    .collect::<Vec<_>>()
+                    .join(&then(lang)),
+                other => clause(other, lang),
+            };
+            let otherwise = match on_fail {
+                Some(fail) => clause(fail, lang),
+                None => lang.pick("keep it in your hand", "gardez-la dans votre Main"),
+            };
+            match lang {
+                Lang::En => format!("draw and reveal a {}; if it {}, {}; otherwise, {}", age, drawn_condition(test, lang), action, otherwise),
+                Lang::Fr => format!("piochez une {} et montrez-la ; si elle {}, {} ; sinon, {}", age, drawn_condition(test, lang), action, otherwise),
+            }
+        }
+        Effect::DrawAndScoreForUniqueColors { age } => match lang {
+            Lang::En => format!("draw and score a {} for each color on your board that no other player has", age),
+            Lang::Fr => format!("piochez une {} pour chaque couleur de votre Zone de Jeu absente de la Zone de Jeu des autres joueurs et comptabilisez-la", age),
+        },
+        Effect::DrawForIconPairs { age, symbol, divisor } => match lang {
+            Lang::En => format!("draw a {} for every {} [{:?}] you have", age, number(*divisor, lang), symbol),
+            Lang::Fr => format!("piochez une {} pour chaque {} [{:?}] que vous produisez", age, number(*divisor, lang), symbol),
+        },
+        Effect::None => lang.pick("do nothing", "ne faites rien"),
+    }
+}
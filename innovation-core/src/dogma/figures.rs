@@ -0,0 +1,360 @@
+//! Figures in the Sand: the karmas, inspire effects and decrees of `data/figures.json`.
+//!
+//! Karmas and inspire effects run inside engine primitives, which cannot ask for input,
+//! so every choice they make is fixed (highest card, first in name order).
+
+use super::karma::{Karma, KarmaEvent, KarmaTiming::{After, Instead}, KarmaTrigger::{Achieve, Dogma, Draw, Meld, Score, Tuck}};
+use crate::card_list::CardList;
+use crate::db;
+use crate::errors::GameError;
+use crate::game_state::{log_action, GameState};
+use crate::model::{Color, Expansion};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// An inspire effect, run for `player_id` when they meld onto the pile showing it.
+pub type InspireFn = fn(&mut GameState, usize) -> Result<(), GameError>;
+
+/// The decree achievements and the color of figure each one asks for.
+pub const DECREES: [(&str, Color); 5] = [
+    ("Décret : Guerre", Color::Red),
+    ("Décret : Progrès", Color::Blue),
+    ("Décret : Commerce", Color::Green),
+    ("Décret : Expansion", Color::Yellow),
+    ("Décret : Rivalité", Color::Purple),
+];
+
+/// Figures in hand it takes to issue a decree, one of them of the decree's color.
+pub const DECREE_FIGURES: usize = 3;
+
+pub fn is_figure(card_id: &str) -> bool {
+    db::card(card_id).is_some_and(|c| c.expansion == Expansion::Figures)
+}
+
+pub fn decree_color(name: &str) -> Option<Color> {
+    DECREES.iter().find(|(decree, _)| *decree == name).map(|&(_, color)| color)
+}
+
+pub fn decree_name(color: Color) -> &'static str {
+    DECREES.iter().find(|&&(_, c)| c == color).map_or("", |&(decree, _)| decree)
+}
+
+/// Whether `player_id` holds the figures to issue the decree of `color`.
+pub fn can_issue_decree(state: &GameState, player_id: usize, color: Color) -> bool {
+    let figures = || state.players[player_id].hand.indices().iter()
+        .map(|&idx| db::card_at(idx))
+        .filter(|c| c.expansion == Expansion::Figures);
+    figures().count() >= DECREE_FIGURES && figures().any(|c| c.color == color)
+}
+
+/// Runs the effect of the decree of `color` for `player_id`, once it is claimed.
+pub fn apply_decree(state: &mut GameState, player_id: usize, color: Color) -> Result<(), GameError> {
+    let top_age = state.players[player_id].max_top_age().max(1);
+    match color {
+        // Each opponent returns their highest score card
+        Color::Red => {
+            for opp in opponents(state, player_id) {
+                if let Some(card_id) = highest(&state.players[opp].score_pile) {
+                    let card_id = state.remove_from_score(opp, card_id)?;
+                    log_action!(state, "P{} renvoie '{}'", opp, card_id);
+                    state.return_card(card_id)?;
+                }
+            }
+        }
+        Color::Blue => {
+            for _ in 0..2 {
+                state.draw_age(player_id, top_age + 1)?;
+            }
+        }
+        Color::Green => draw_and_score_age(state, player_id, top_age)?,
+        Color::Yellow => draw_and_meld_age(state, player_id, top_age + 1)?,
+        // Each opponent returns their highest card in hand
+        Color::Purple => {
+            for opp in opponents(state, player_id) {
+                if let Some(card_id) = highest(&state.players[opp].hand) {
+                    let card_id = state.remove_from_hand(opp, card_id)?;
+                    log_action!(state, "P{} renvoie '{}'", opp, card_id);
+                    state.return_card(card_id)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn opponents(state: &GameState, player_id: usize) -> Vec<usize> {
+    (0..state.players.len()).filter(|&pid| !state.config.teammates(pid, player_id)).collect()
+}
+
+/// Highest card of `cards`, the first in name order among equals.
+fn highest(cards: &CardList) -> Option<&'static String> {
+    cards.names().max_by(|a, b| {
+        let age = |id: &str| db::card(id).map_or(0, |c| c.age);
+        age(a).cmp(&age(b)).then_with(|| b.cmp(a))
+    })
+}
+
+// --- Karma actions ---
+
+fn draw_and_score_age(state: &mut GameState, player_id: usize, age: u8) -> Result<(), GameError> {
+    if let Some(card_id) = state.draw_age(player_id, age)? {
+        state.remove_from_hand(player_id, card_id)?;
+        state.score_card(player_id, card_id)?;
+    }
+    Ok(())
+}
+
+fn draw_and_tuck_age(state: &mut GameState, player_id: usize, age: u8) -> Result<(), GameError> {
+    if let Some(card_id) = state.draw_age(player_id, age)? {
+        state.remove_from_hand(player_id, card_id)?;
+        state.tuck_card(player_id, card_id)?;
+    }
+    Ok(())
+}
+
+fn draw_and_meld_age(state: &mut GameState, player_id: usize, age: u8) -> Result<(), GameError> {
+    if let Some(card_id) = state.draw_age(player_id, age)? {
+        state.meld(player_id, card_id)?;
+    }
+    Ok(())
+}
+
+fn draw<const AGE: u8>(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+    state.draw_age(event.player_id, AGE).map(|_| ())
+}
+
+fn draw_and_score<const AGE: u8>(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+    draw_and_score_age(state, event.player_id, AGE)
+}
+
+fn draw_and_meld<const AGE: u8>(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+    draw_and_meld_age(state, event.player_id, AGE)
+}
+
+/// Instead of a draw: draws the same age and melds the card.
+fn meld_drawn(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+    draw_and_meld_age(state, event.player_id, event.age.unwrap_or(1))
+}
+
+/// Instead of a meld, whose card is still in hand: tucks it, then draws.
+fn tuck_melded_and_draw<const AGE: u8>(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+    let Some(card_id) = &event.card_id else { return Ok(()) };
+    let card_id = state.remove_from_hand(event.player_id, card_id)?;
+    log_action!(state, "P{} archive '{}' (karma)", event.player_id, card_id);
+    state.tuck_card(event.player_id, card_id)?;
+    state.draw_age(event.player_id, AGE).map(|_| ())
+}
+
+/// Instead of a tuck, whose card is already out of its zone: scores it.
+fn score_tucked(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+    let Some(card_id) = event.card_id else { return Ok(()) };
+    log_action!(state, "P{} comptabilise '{}' (karma)", event.player_id, card_id);
+    state.score_card(event.player_id, card_id)
+}
+
+/// Instead of a score, whose card is already out of its zone: tucks it.
+fn tuck_scored(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+    let Some(card_id) = event.card_id else { return Ok(()) };
+    log_action!(state, "P{} archive '{}' (karma)", event.player_id, card_id);
+    state.tuck_card(event.player_id, card_id)
+}
+
+// --- Karma conditions ---
+
+fn card_color(event: &KarmaEvent) -> Option<Color> {
+    event.card_id.and_then(|id| db::card(id)).map(|c| c.color)
+}
+
+fn blue(_: &GameState, event: &KarmaEvent) -> bool {
+    card_color(event) == Some(Color::Blue)
+}
+
+fn green(_: &GameState, event: &KarmaEvent) -> bool {
+    card_color(event) == Some(Color::Green)
+}
+
+fn yellow(_: &GameState, event: &KarmaEvent) -> bool {
+    card_color(event) == Some(Color::Yellow)
+}
+
+fn purple(_: &GameState, event: &KarmaEvent) -> bool {
+    card_color(event) == Some(Color::Purple)
+}
+
+fn figure(_: &GameState, event: &KarmaEvent) -> bool {
+    event.card_id.is_some_and(|id| is_figure(id))
+}
+
+fn age_at_least<const AGE: u8>(_: &GameState, event: &KarmaEvent) -> bool {
+    event.age.is_some_and(|age| age >= AGE)
+}
+
+pub(super) fn karmas() -> HashMap<String, Vec<Karma>> {
+    let mut map = HashMap::new();
+    // --- Age 1 ---
+    map.insert("Sargon".to_string(), vec![Karma::new(Dogma, After, draw_and_score::<1>).when(blue)]);
+    map.insert("Imhotep".to_string(), vec![Karma::new(Meld, After, draw::<2>).when(green)]);
+    map.insert("Hammurabi".to_string(), vec![Karma::new(Tuck, Instead, score_tucked).when(green)]);
+    map.insert("Gilgamesh".to_string(), vec![Karma::new(Meld, Instead, tuck_melded_and_draw::<1>).when(yellow)]);
+    map.insert("Homère".to_string(), vec![Karma::new(Dogma, Instead, draw_and_score::<1>).when(figure)]);
+
+    // --- Age 2 ---
+    map.insert("Alexandre le Grand".to_string(), vec![Karma::new(Achieve, After, draw_and_meld::<3>)]);
+    map.insert("Euclide".to_string(), vec![Karma::new(Draw, Instead, meld_drawn).when(age_at_least::<2>)]);
+    map.insert("Crésus".to_string(), vec![Karma::new(Score, After, draw::<2>)]);
+    map.insert("Qin Shi Huang".to_string(), vec![Karma::new(Tuck, After, draw_and_score::<2>)]);
+    map.insert("Confucius".to_string(), vec![Karma::new(Score, Instead, tuck_scored).when(purple)]);
+
+    // --- Age 3 ---
+    map.insert("Hannibal".to_string(), vec![Karma::new(Dogma, After, draw_and_score::<3>).when(blue)]);
+    map.insert("Archimède".to_string(), vec![Karma::new(Meld, After, draw::<4>).when(green)]);
+    map.insert("Jules César".to_string(), vec![Karma::new(Tuck, Instead, score_tucked).when(green)]);
+    map.insert("Cléopâtre".to_string(), vec![Karma::new(Meld, Instead, tuck_melded_and_draw::<3>).when(yellow)]);
+    map.insert("Aristote".to_string(), vec![Karma::new(Dogma, Instead, draw_and_score::<3>).when(figure)]);
+
+    // --- Age 4 ---
+    map.insert("Gengis Khan".to_string(), vec![Karma::new(Achieve, After, draw_and_meld::<5>)]);
+    map.insert("Avicenne".to_string(), vec![Karma::new(Draw, Instead, meld_drawn).when(age_at_least::<4>)]);
+    map.insert("Marco Polo".to_string(), vec![Karma::new(Score, After, draw::<4>)]);
+    map.insert("Aliénor d'Aquitaine".to_string(), vec![Karma::new(Tuck, After, draw_and_score::<4>)]);
+    map.insert("Thomas d'Aquin".to_string(), vec![Karma::new(Score, Instead, tuck_scored).when(purple)]);
+
+    // --- Age 5 ---
+    map.insert("Jeanne d'Arc".to_string(), vec![Karma::new(Dogma, After, draw_and_score::<5>).when(blue)]);
+    map.insert("Léonard de Vinci".to_string(), vec![Karma::new(Meld, After, draw::<6>).when(green)]);
+    map.insert("Christophe Colomb".to_string(), vec![Karma::new(Tuck, Instead, score_tucked).when(green)]);
+    map.insert("Johannes Gutenberg".to_string(), vec![Karma::new(Meld, Instead, tuck_melded_and_draw::<5>).when(yellow)]);
+    map.insert("Michel-Ange".to_string(), vec![Karma::new(Dogma, Instead, draw_and_score::<5>).when(figure)]);
+
+    // --- Age 6 ---
+    map.insert("Gustave Adolphe".to_string(), vec![Karma::new(Achieve, After, draw_and_meld::<7>)]);
+    map.insert("Galilée".to_string(), vec![Karma::new(Draw, Instead, meld_drawn).when(age_at_least::<6>)]);
+    map.insert("Élisabeth Ire".to_string(), vec![Karma::new(Score, After, draw::<6>)]);
+    map.insert("Colbert".to_string(), vec![Karma::new(Tuck, After, draw_and_score::<6>)]);
+    map.insert("Molière".to_string(), vec![Karma::new(Score, Instead, tuck_scored).when(purple)]);
+
+    // --- Age 7 ---
+    map.insert("Napoléon Bonaparte".to_string(), vec![Karma::new(Dogma, After, draw_and_score::<7>).when(blue)]);
+    map.insert("Charles Darwin".to_string(), vec![Karma::new(Meld, After, draw::<8>).when(green)]);
+    map.insert("Adam Smith".to_string(), vec![Karma::new(Tuck, Instead, score_tucked).when(green)]);
+    map.insert("James Watt".to_string(), vec![Karma::new(Meld, Instead, tuck_melded_and_draw::<7>).when(yellow)]);
+    map.insert("Beethoven".to_string(), vec![Karma::new(Dogma, Instead, draw_and_score::<7>).when(figure)]);
+
+    // --- Age 8 ---
+    map.insert("Winston Churchill".to_string(), vec![Karma::new(Achieve, After, draw_and_meld::<9>)]);
+    map.insert("Marie Curie".to_string(), vec![Karma::new(Draw, Instead, meld_drawn).when(age_at_least::<8>)]);
+    map.insert("Henry Ford".to_string(), vec![Karma::new(Score, After, draw::<8>)]);
+    map.insert("Thomas Edison".to_string(), vec![Karma::new(Tuck, After, draw_and_score::<8>)]);
+    map.insert("Picasso".to_string(), vec![Karma::new(Score, Instead, tuck_scored).when(purple)]);
+
+    // --- Age 9 ---
+    map.insert("Eisenhower".to_string(), vec![Karma::new(Dogma, After, draw_and_score::<9>).when(blue)]);
+    map.insert("Albert Einstein".to_string(), vec![Karma::new(Meld, After, draw::<10>).when(green)]);
+    map.insert("John Maynard Keynes".to_string(), vec![Karma::new(Tuck, Instead, score_tucked).when(green)]);
+    map.insert("Norman Borlaug".to_string(), vec![Karma::new(Meld, Instead, tuck_melded_and_draw::<9>).when(yellow)]);
+    map.insert("Martin Luther King".to_string(), vec![Karma::new(Dogma, Instead, draw_and_score::<9>).when(figure)]);
+
+    // --- Age 10 ---
+    map.insert("Nelson Mandela".to_string(), vec![Karma::new(Achieve, After, draw_and_meld::<11>)]);
+    map.insert("Stephen Hawking".to_string(), vec![Karma::new(Draw, Instead, meld_drawn).when(age_at_least::<10>)]);
+    map.insert("Steve Jobs".to_string(), vec![Karma::new(Score, After, draw::<10>)]);
+    map.insert("Wangari Maathai".to_string(), vec![Karma::new(Tuck, After, draw_and_score::<10>)]);
+    map.insert("Tim Berners-Lee".to_string(), vec![Karma::new(Score, Instead, tuck_scored).when(purple)]);
+
+    map
+}
+
+// --- Inspire effects ---
+
+fn inspire_draw<const AGE: u8>(state: &mut GameState, player_id: usize) -> Result<(), GameError> {
+    state.draw_age(player_id, AGE).map(|_| ())
+}
+
+fn inspire_draw_and_score<const AGE: u8>(state: &mut GameState, player_id: usize) -> Result<(), GameError> {
+    draw_and_score_age(state, player_id, AGE)
+}
+
+fn inspire_draw_and_tuck<const AGE: u8>(state: &mut GameState, player_id: usize) -> Result<(), GameError> {
+    draw_and_tuck_age(state, player_id, AGE)
+}
+
+static INSPIRES: OnceLock<HashMap<String, InspireFn>> = OnceLock::new();
+
+pub fn get_inspire(card_name: &str) -> Option<InspireFn> {
+    INSPIRES.get_or_init(inspires).get(card_name).copied()
+}
+
+fn inspires() -> HashMap<String, InspireFn> {
+    let mut map = HashMap::new();
+    // --- Age 1 ---
+    map.insert("Sargon".to_string(), inspire_draw::<1> as InspireFn);
+    map.insert("Imhotep".to_string(), inspire_draw::<2> as InspireFn);
+    map.insert("Hammurabi".to_string(), inspire_draw_and_score::<1> as InspireFn);
+    map.insert("Gilgamesh".to_string(), inspire_draw_and_tuck::<1> as InspireFn);
+    map.insert("Homère".to_string(), inspire_draw_and_score::<1> as InspireFn);
+
+    // --- Age 2 ---
+    map.insert("Alexandre le Grand".to_string(), inspire_draw::<2> as InspireFn);
+    map.insert("Euclide".to_string(), inspire_draw::<3> as InspireFn);
+    map.insert("Crésus".to_string(), inspire_draw_and_score::<2> as InspireFn);
+    map.insert("Qin Shi Huang".to_string(), inspire_draw_and_tuck::<2> as InspireFn);
+    map.insert("Confucius".to_string(), inspire_draw_and_score::<1> as InspireFn);
+
+    // --- Age 3 ---
+    map.insert("Hannibal".to_string(), inspire_draw::<3> as InspireFn);
+    map.insert("Archimède".to_string(), inspire_draw::<4> as InspireFn);
+    map.insert("Jules César".to_string(), inspire_draw_and_score::<3> as InspireFn);
+    map.insert("Cléopâtre".to_string(), inspire_draw_and_tuck::<3> as InspireFn);
+    map.insert("Aristote".to_string(), inspire_draw_and_score::<2> as InspireFn);
+
+    // --- Age 4 ---
+    map.insert("Gengis Khan".to_string(), inspire_draw::<4> as InspireFn);
+    map.insert("Avicenne".to_string(), inspire_draw::<5> as InspireFn);
+    map.insert("Marco Polo".to_string(), inspire_draw_and_score::<4> as InspireFn);
+    map.insert("Aliénor d'Aquitaine".to_string(), inspire_draw_and_tuck::<4> as InspireFn);
+    map.insert("Thomas d'Aquin".to_string(), inspire_draw_and_score::<3> as InspireFn);
+
+    // --- Age 5 ---
+    map.insert("Jeanne d'Arc".to_string(), inspire_draw::<5> as InspireFn);
+    map.insert("Léonard de Vinci".to_string(), inspire_draw::<6> as InspireFn);
+    map.insert("Christophe Colomb".to_string(), inspire_draw_and_score::<5> as InspireFn);
+    map.insert("Johannes Gutenberg".to_string(), inspire_draw_and_tuck::<5> as InspireFn);
+    map.insert("Michel-Ange".to_string(), inspire_draw_and_score::<4> as InspireFn);
+
+    // --- Age 6 ---
+    map.insert("Gustave Adolphe".to_string(), inspire_draw::<6> as InspireFn);
+    map.insert("Galilée".to_string(), inspire_draw::<7> as InspireFn);
+    map.insert("Élisabeth Ire".to_string(), inspire_draw_and_score::<6> as InspireFn);
+    map.insert("Colbert".to_string(), inspire_draw_and_tuck::<6> as InspireFn);
+    map.insert("Molière".to_string(), inspire_draw_and_score::<5> as InspireFn);
+
+    // --- Age 7 ---
+    map.insert("Napoléon Bonaparte".to_string(), inspire_draw::<7> as InspireFn);
+    map.insert("Charles Darwin".to_string(), inspire_draw::<8> as InspireFn);
+    map.insert("Adam Smith".to_string(), inspire_draw_and_score::<7> as InspireFn);
+    map.insert("James Watt".to_string(), inspire_draw_and_tuck::<7> as InspireFn);
+    map.insert("Beethoven".to_string(), inspire_draw_and_score::<6> as InspireFn);
+
+    // --- Age 8 ---
+    map.insert("Winston Churchill".to_string(), inspire_draw::<8> as InspireFn);
+    map.insert("Marie Curie".to_string(), inspire_draw::<9> as InspireFn);
+    map.insert("Henry Ford".to_string(), inspire_draw_and_score::<8> as InspireFn);
+    map.insert("Thomas Edison".to_string(), inspire_draw_and_tuck::<8> as InspireFn);
+    map.insert("Picasso".to_string(), inspire_draw_and_score::<7> as InspireFn);
+
+    // --- Age 9 ---
+    map.insert("Eisenhower".to_string(), inspire_draw::<9> as InspireFn);
+    map.insert("Albert Einstein".to_string(), inspire_draw::<10> as InspireFn);
+    map.insert("John Maynard Keynes".to_string(), inspire_draw_and_score::<9> as InspireFn);
+    map.insert("Norman Borlaug".to_string(), inspire_draw_and_tuck::<9> as InspireFn);
+    map.insert("Martin Luther King".to_string(), inspire_draw_and_score::<8> as InspireFn);
+
+    // --- Age 10 ---
+    map.insert("Nelson Mandela".to_string(), inspire_draw::<10> as InspireFn);
+    map.insert("Stephen Hawking".to_string(), inspire_draw::<11> as InspireFn);
+    map.insert("Steve Jobs".to_string(), inspire_draw_and_score::<10> as InspireFn);
+    map.insert("Wangari Maathai".to_string(), inspire_draw_and_tuck::<10> as InspireFn);
+    map.insert("Tim Berners-Lee".to_string(), inspire_draw_and_score::<9> as InspireFn);
+
+    map
+}
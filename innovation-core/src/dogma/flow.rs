@@ -1,7 +1,8 @@
 use serde::{Serialize, Deserialize};
+use crate::errors::GameError;
 use crate::model::Color;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum InputRequest {
     SelectCard {
         player_id: usize,
@@ -19,9 +20,86 @@ pub enum InputRequest {
     },
 }
 
+impl InputRequest {
+    /// Player who must answer.
+    pub fn player_id(&self) -> usize {
+        match self {
+            InputRequest::SelectCard { player_id, .. }
+            | InputRequest::SelectColor { player_id, .. }
+            | InputRequest::Confirm { player_id, .. } => *player_id,
+        }
+    }
+    
+    /// Valid answers, at most `limit` of them: card picks are listed by increasing count.
+    pub fn responses(&self, limit: usize) -> Vec<String> {
+        match self {
+            InputRequest::SelectCard { source, min, max, .. } => {
+                let mut out = Vec::new();
+                for count in (*min).min(source.len())..=(*max).min(source.len()) {
+                    push_combinations(source, count, 0, &mut Vec::new(), &mut out, limit);
+                }
+                out
+            }
+            InputRequest::SelectColor { colors, .. } => {
+                colors.iter().take(limit).map(|c| format!("{:?}", c)).collect()
+            }
+            InputRequest::Confirm { .. } => ["yes", "no"].iter().take(limit).map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl InputRequest {
+    /// Checks `answer` before it is applied: picked cards must be distinct cards of
+    /// `source`, as many as `min..=max` allows; a color or a yes/no answer must be one
+    /// of the options offered.
+    pub fn check(&self, answer: &str) -> Result<(), GameError> {
+        match self {
+            InputRequest::SelectCard { source, min, max, .. } => {
+                let picked: Vec<&str> = answer.split(',').filter(|c| !c.is_empty()).collect();
+                if let Some(card) = picked.iter().find(|c| !source.iter().any(|s| s == *c)) {
+                    return Err(GameError::InvalidAction(format!("'{}' cannot be picked", card)));
+                }
+                if picked.iter().enumerate().any(|(idx, c)| picked[..idx].contains(c)) {
+                    return Err(GameError::InvalidAction("A card is picked twice".into()));
+                }
+                let (lo, hi) = ((*min).min(source.len()), (*max).min(source.len()));
+                if !(lo..=hi).contains(&picked.len()) {
+                    return Err(GameError::InvalidAction(format!("Pick {} to {} cards, not {}", lo, hi, picked.len())));
+                }
+            }
+            InputRequest::SelectColor { colors, .. } => {
+                if !colors.iter().any(|c| format!("{:?}", c).eq_ignore_ascii_case(answer.trim())) {
+                    return Err(GameError::InvalidAction(format!("'{}' is not one of the colors offered", answer)));
+                }
+            }
+            InputRequest::Confirm { .. } => {
+                if !matches!(answer.trim().to_lowercase().as_str(), "yes" | "oui" | "y" | "no" | "non" | "n") {
+                    return Err(GameError::InvalidAction(format!("'{}' is neither yes nor no", answer)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn push_combinations(source: &[String], count: usize, start: usize, picked: &mut Vec<usize>, out: &mut Vec<String>, limit: usize) {
+    if out.len() >= limit {
+        return;
+    }
+    if picked.len() == count {
+        out.push(picked.iter().map(|&i| source[i].as_str()).collect::<Vec<_>>().join(","));
+        return;
+    }
+    for i in start..source.len() {
+        picked.push(i);
+        push_combinations(source, count, i + 1, picked, out, limit);
+        picked.pop();
+    }
+}
+
 /// Tracks position within nested effect sequences.
 /// Replaces magic number encoding (index * 100 + child_step).
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct SubStep {
     /// Index within a Sequence effect (0-based)
     pub sequence_index: usize,
@@ -47,7 +125,7 @@ impl SubStep {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct DogmaExecutionState {
     pub card_id: String,
     pub dogma_index: usize,
@@ -69,6 +147,16 @@ pub struct DogmaExecutionState {
     // Context for consecutive effects ("If you do, ...")
     pub context_color: Option<Color>, 
     pub context_value: Option<u8>,
+    
+    /// Echoes: cards whose visible echo effect runs before the dogmas, bottom to top.
+    #[serde(default)]
+    pub echoes: Vec<String>,
+    /// Next entry of `echoes` to execute; dogmas start once all echoes ran.
+    #[serde(default)]
+    pub echo_index: usize,
+    /// Artifacts: free dogma of a melded artifact, which does not use up an action.
+    #[serde(default)]
+    pub free_action: bool,
 }
 
 impl DogmaExecutionState {
@@ -83,6 +171,9 @@ impl DogmaExecutionState {
             anyone_shared: false,
             context_color: None,
             context_value: None,
+            echoes: Vec::new(),
+            echo_index: 0,
+            free_action: false,
         }
     }
 }
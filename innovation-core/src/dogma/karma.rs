@@ -0,0 +1,144 @@
+//! Karma effects (Figures in the Sand): "If you would meld…" / "Each time you score…".
+//!
+//! The engine primitives (`draw`, `meld`, `score_card`, `tuck_card`, `achieve` and dogma
+//! activation) report a `KarmaEvent` before and after they act. Karmas on the acting
+//! player's top Figures cards that match the trigger then run: an `Instead` karma
+//! replaces the action, an `After` karma follows it. Karmas do not trigger while another
+//! karma is resolving, so a karma that draws or melds cannot loop on itself.
+
+use crate::db::{self, CardIdx};
+use crate::errors::GameError;
+use crate::game_state::GameState;
+use crate::model::Expansion;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KarmaTrigger {
+    Draw,
+    Meld,
+    Score,
+    Tuck,
+    Achieve,
+    Dogma,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KarmaTiming {
+    /// "If you would …, instead …": the action does not happen.
+    Instead,
+    /// "Each time you …": runs once the action is done.
+    After,
+}
+
+/// What the acting player is doing when karmas are checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KarmaEvent {
+    pub player_id: usize,
+    pub trigger: KarmaTrigger,
+    /// Card being melded, scored, tucked or activated.
+    pub card_id: Option<&'static String>,
+    /// Age being drawn or achieved.
+    pub age: Option<u8>,
+}
+
+impl KarmaEvent {
+    /// Event about `card_id`; a card the database does not know leaves both the card
+    /// and its age unset.
+    pub fn card(player_id: usize, trigger: KarmaTrigger, card_id: &str) -> Self {
+        let card = db::card(card_id);
+        Self { player_id, trigger, card_id: card.map(|c| &c.name), age: card.map(|c| c.age) }
+    }
+
+    /// `card` for an interned card.
+    pub fn card_at(player_id: usize, trigger: KarmaTrigger, idx: CardIdx) -> Self {
+        let card = db::card_at(idx);
+        Self { player_id, trigger, card_id: Some(&card.name), age: Some(card.age) }
+    }
+
+    pub fn age(player_id: usize, trigger: KarmaTrigger, age: u8) -> Self {
+        Self { player_id, trigger, card_id: None, age: Some(age) }
+    }
+}
+
+/// Karmas run inside engine primitives, so they cannot ask for input.
+pub type KarmaFn = fn(&mut GameState, &KarmaEvent) -> Result<(), GameError>;
+
+/// Narrows a karma to some events ("a blue card", "of value 5 or more").
+pub type KarmaCondition = fn(&GameState, &KarmaEvent) -> bool;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Karma {
+    pub trigger: KarmaTrigger,
+    pub timing: KarmaTiming,
+    pub action: KarmaFn,
+    pub applies: KarmaCondition,
+}
+
+impl Karma {
+    /// A karma answering every `trigger` event; narrow it with [`Karma::when`].
+    pub fn new(trigger: KarmaTrigger, timing: KarmaTiming, action: KarmaFn) -> Self {
+        Self { trigger, timing, action, applies: |_, _| true }
+    }
+
+    pub fn when(self, applies: KarmaCondition) -> Self {
+        Self { applies, ..self }
+    }
+}
+
+// Karmas by Figures card name
+static KARMAS: OnceLock<HashMap<String, Vec<Karma>>> = OnceLock::new();
+
+pub fn get_karmas(card_name: &str) -> Option<&'static Vec<Karma>> {
+    KARMAS.get_or_init(load_karmas).get(card_name)
+}
+
+fn load_karmas() -> HashMap<String, Vec<Karma>> {
+    super::figures::karmas()
+}
+
+/// Karmas of `player_id`'s top Figures cards that answer `trigger` at `timing`, in color order.
+pub fn active_karmas(state: &GameState, player_id: usize, trigger: KarmaTrigger, timing: KarmaTiming) -> Vec<Karma> {
+    let Some(p) = state.players.get(player_id) else { return Vec::new() };
+    crate::model::Color::ALL.iter()
+        .filter_map(|color| p.board.get(color).and_then(|pile| pile.top_index()))
+        .map(db::card_at)
+        .filter(|card| card.expansion == Expansion::Figures)
+        .filter_map(|card| get_karmas(&card.name))
+        .flatten()
+        .filter(|k| k.trigger == trigger && k.timing == timing)
+        .copied()
+        .collect()
+}
+
+/// Runs the matching karmas for `event`. Returns true when an `Instead` karma
+/// replaced the action, so the caller must skip it.
+pub fn intercept(state: &mut GameState, event: &KarmaEvent, timing: KarmaTiming) -> Result<bool, GameError> {
+    if state.resolving_karma || !state.config.has_expansion(Expansion::Figures) {
+        return Ok(false);
+    }
+    let mut karmas = active_karmas(state, event.player_id, event.trigger, timing);
+    karmas.retain(|k| (k.applies)(state, event));
+    run_karmas(state, event, &karmas)
+}
+
+/// Runs `karmas` for `event`, blocking nested karmas meanwhile. Only the first
+/// `Instead` karma applies: the action it replaces can be replaced only once.
+pub fn run_karmas(state: &mut GameState, event: &KarmaEvent, karmas: &[Karma]) -> Result<bool, GameError> {
+    let mut replaced = false;
+    state.resolving_karma = true;
+    for karma in karmas {
+        if karma.timing == KarmaTiming::Instead {
+            if replaced {
+                continue;
+            }
+            replaced = true;
+        }
+        if let Err(e) = (karma.action)(state, event) {
+            state.resolving_karma = false;
+            return Err(e);
+        }
+    }
+    state.resolving_karma = false;
+    Ok(replaced)
+}
@@ -2,3 +2,8 @@ pub mod flow;
 pub mod executor;
 pub mod cards;
 pub mod registry;
+pub mod karma;
+pub mod figures;
+pub mod preview;
+pub mod explain;
+pub mod coverage;
@@ -0,0 +1,155 @@
+//! Dry run of a dogma, for players weighing whether to activate a card.
+//!
+//! The preview compares icon counts the way `continue_execution` does, then plays
+//! the dogma out on a copy of the game. The copy is the activator's view with the
+//! hidden cards dealt again at random, so the preview shows a likely result without
+//! telling which cards really lie in the draw piles or in other hands.
+
+use crate::actions::Action;
+use crate::agent::{Agent, Observation};
+use crate::db::load_all_cards;
+use crate::dogma::executor::eligible_players;
+use crate::errors::GameError;
+use crate::game_state::{GamePhase, GameState};
+use crate::model::Symbol;
+use crate::player::Player;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Most inputs answered during a simulation, in case a policy never settles.
+const MAX_INPUTS: usize = 200;
+
+/// One player's count of a dogma's symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconComparison {
+    pub player: usize,
+    pub icons: u32,
+    /// Whether the dogma applies to this player.
+    pub reached: bool,
+}
+
+/// One dogma of the card, and who it reaches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DogmaLine {
+    pub symbol: Symbol,
+    pub text: String,
+    pub is_supremacy: bool,
+    pub activator_icons: u32,
+    /// The other players, in execution order.
+    pub players: Vec<IconComparison>,
+}
+
+/// How a player came out of the simulated dogma.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlayerChange {
+    pub score: i64,
+    pub hand: i64,
+    pub board: i64,
+    pub achievements: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DogmaOutcome {
+    /// Inputs the policy answered, with the player who answered them.
+    pub inputs: Vec<(usize, String)>,
+    /// Log lines written while the dogma ran.
+    pub log: Vec<String>,
+    /// By seat.
+    pub changes: Vec<PlayerChange>,
+    pub winners: Vec<usize>,
+    /// The copy once the dogma is over.
+    pub state: GameState,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DogmaPreview {
+    pub card_id: String,
+    pub activator: usize,
+    pub dogmas: Vec<DogmaLine>,
+    /// Other players sharing at least one dogma, by seat.
+    pub sharers: Vec<usize>,
+    /// Players hit by at least one demand, by seat.
+    pub demanded: Vec<usize>,
+    pub outcome: DogmaOutcome,
+}
+
+/// The dogmas of `card_id` with the players they reach when `activator` uses them.
+pub fn dogma_lines(state: &GameState, activator: usize, card_id: &str) -> Result<Vec<DogmaLine>, GameError> {
+    let card = load_all_cards().get(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?;
+    Ok(card.dogmas.iter().map(|dogma| {
+        let icons = |pid: usize| state.players[pid].icon_totals().get(dogma.symbol);
+        let reached = eligible_players(state, activator, dogma.symbol, dogma.is_supremacy);
+        let count = state.players.len();
+        DogmaLine {
+            symbol: dogma.symbol,
+            text: dogma.text.clone(),
+            is_supremacy: dogma.is_supremacy,
+            activator_icons: icons(activator),
+            players: (1..count).map(|i| (activator + i) % count)
+                .map(|pid| IconComparison { player: pid, icons: icons(pid), reached: reached.contains(&pid) })
+                .collect(),
+        }
+    }).collect())
+}
+
+/// Players reached by the cooperative dogmas and by the demands among `lines`, by seat.
+pub fn reached(lines: &[DogmaLine]) -> (Vec<usize>, Vec<usize>) {
+    let (mut sharers, mut demanded) = (Vec::new(), Vec::new());
+    for line in lines {
+        let list = if line.is_supremacy { &mut demanded } else { &mut sharers };
+        list.extend(line.players.iter().filter(|p| p.reached).map(|p| p.player));
+    }
+    for list in [&mut sharers, &mut demanded] {
+        list.sort_unstable();
+        list.dedup();
+    }
+    (sharers, demanded)
+}
+
+/// Previews the acting player's dogma of `card_id`, answering the inputs it asks
+/// for with `policy`. `state` is left as it is.
+pub fn preview(state: &GameState, card_id: &str, policy: &mut dyn Agent) -> Result<DogmaPreview, GameError> {
+    let action = Action::Dogma(card_id.to_string());
+    if !state.legal_actions().contains(&action) {
+        return Err(GameError::InvalidAction(format!("'{}' cannot be activated now", card_id)));
+    }
+    let activator = state.current_player;
+    let dogmas = dogma_lines(state, activator, card_id)?;
+    let (sharers, demanded) = reached(&dogmas);
+
+    let obs = Observation::new(state, activator);
+    let mut sim = obs.determinize(&mut StdRng::seed_from_u64(state.zobrist()));
+    let logged = sim.action_log.len();
+    sim.apply_action(action)?;
+    let mut inputs = Vec::new();
+    while sim.dogma_state.is_some() && sim.pending_input.is_some() && sim.phase != GamePhase::GameOver {
+        if inputs.len() >= MAX_INPUTS {
+            return Err(GameError::InvalidAction(format!("the dogma of '{}' did not end after {} inputs", card_id, MAX_INPUTS)));
+        }
+        let actor = sim.acting_player();
+        let answer = match policy.choose(&Observation::new(&sim, actor)) {
+            Action::ResolveInput(answer) => answer,
+            other => return Err(GameError::InvalidAction(format!("policy answered an input with {:?}", other))),
+        };
+        sim.apply_action(Action::ResolveInput(answer.clone()))?;
+        inputs.push((actor, answer));
+    }
+
+    let board = |p: &Player| p.board.values().map(|pile| pile.len() as i64).sum::<i64>();
+    let changes = state.players.iter().zip(&sim.players).enumerate().map(|(pid, (before, after))| {
+        PlayerChange {
+            score: after.calculate_score() as i64 - before.calculate_score() as i64,
+            hand: after.hand.len() as i64 - before.hand.len() as i64,
+            board: board(after) - board(before),
+            achievements: sim.achievement_count(pid) as i64 - state.achievement_count(pid) as i64,
+        }
+    }).collect();
+    let outcome = DogmaOutcome {
+        inputs,
+        log: sim.action_log.to_vec_from(logged),
+        changes,
+        winners: sim.winners.clone(),
+        state: sim,
+    };
+    Ok(DogmaPreview { card_id: card_id.to_string(), activator, dogmas, sharers, demanded, outcome })
+}
@@ -1,16 +1,30 @@
 use crate::model::{Effect, Location, Filter, Symbol, SplayDirection};
-use std::collections::HashMap;
+use rustc_hash::FxHashMap;
 use std::sync::OnceLock;
 
+mod artifacts;
+mod echoes;
+
 // Global Registry
-static REGISTRY: OnceLock<HashMap<String, Vec<Effect>>> = OnceLock::new();
+static REGISTRY: OnceLock<FxHashMap<String, Vec<Effect>>> = OnceLock::new();
 
 pub fn get_effects(card_name: &str) -> Option<&'static Vec<Effect>> {
     REGISTRY.get_or_init(load_registry).get(card_name)
 }
 
-fn load_registry() -> HashMap<String, Vec<Effect>> {
-    let mut map = HashMap::new();
+// Echo effects (Echoes of the Past), one per card
+static ECHO_REGISTRY: OnceLock<FxHashMap<String, Effect>> = OnceLock::new();
+
+pub fn get_echo_effect(card_name: &str) -> Option<&'static Effect> {
+    ECHO_REGISTRY.get_or_init(load_echo_registry).get(card_name)
+}
+
+fn load_echo_registry() -> FxHashMap<String, Effect> {
+    echoes::echo_effects()
+}
+
+fn load_registry() -> FxHashMap<String, Vec<Effect>> {
+    let mut map = FxHashMap::default();
 
     // --- Age 1 ---
     
@@ -263,5 +277,7 @@ fn load_registry() -> HashMap<String, Vec<Effect>> {
         ])
     ]);
 
+    echoes::register_dogmas(&mut map);
+    artifacts::register_dogmas(&mut map);
     map
 }
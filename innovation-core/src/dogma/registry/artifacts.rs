@@ -0,0 +1,460 @@
+//! Artifacts of History: the dogmas of `data/artifacts.json`.
+
+use crate::model::{Color, Condition, DrawnCardCondition, Effect, Filter, Location, SplayDirection, Symbol};
+use rustc_hash::FxHashMap;
+
+pub(super) fn register_dogmas(map: &mut FxHashMap<String, Vec<Effect>>) {
+    // --- Age 1 ---
+    map.insert("Arcs de Holmegaard".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Hand,
+            dest: Location::Hand,
+            dest_is_activator: true,
+            filters: vec![Filter::MaxAge],
+            min: 1,
+            max: 1,
+        },
+        Effect::Draw { amount: 1, age: Some(2) },
+    ]);
+
+    map.insert("Tablette de Kish".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndScore { amount: 1, age: Some(1) },
+            Effect::Conditional {
+                condition: Condition::IconCountGreaterThanOrEqual(Symbol::Lightbulb, 3),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(2) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Collier de Jiskairumoko".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 1, max: 1, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(1) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Grenier de Jerf el Ahmar".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 1, max: 1, source: Location::Hand, filters: vec![] },
+            Effect::DrawForIconPairs { age: 1, symbol: Symbol::Leaf, divisor: 2 },
+        ]),
+    ]);
+
+    map.insert("Danseuse de Mohenjo-daro".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndMeld { amount: 1, age: Some(1) },
+            Effect::Splay { direction: SplayDirection::Left, color: None },
+        ]),
+    ]);
+
+    // --- Age 2 ---
+    map.insert("Char de Toutânkhamon".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Board,
+            dest: Location::Board,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Castle)],
+            min: 1,
+            max: 1,
+        },
+    ]);
+
+    map.insert("Pierre de Rosette".to_string(), vec![
+        Effect::DrawUntilNoMatch {
+            age: 2,
+            condition: DrawnCardCondition::HasIcon(Symbol::Lightbulb),
+            on_match: Box::new(Effect::Score { min: 1, max: 1, filters: vec![] }),
+        },
+    ]);
+
+    map.insert("Code d'Hammurabi".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Hand,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Crown)],
+            min: 1,
+            max: 1,
+        },
+        Effect::Splay { direction: SplayDirection::Left, color: Some(Color::Green) },
+    ]);
+
+    map.insert("Amphore de Cnossos".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 0, max: 1, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::DrawAndMeld { amount: 1, age: Some(3) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Masque de Toutânkhamon".to_string(), vec![
+        Effect::DrawAndScoreForUniqueColors { age: 2 },
+    ]);
+
+    // --- Age 3 ---
+    map.insert("Guerriers de terre cuite".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Score,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::MaxAge],
+            min: 1,
+            max: 1,
+        },
+        Effect::Splay { direction: SplayDirection::Left, color: Some(Color::Red) },
+    ]);
+
+    map.insert("Machine d'Anticythère".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Draw { amount: 1, age: Some(4) },
+            Effect::Splay { direction: SplayDirection::Left, color: Some(Color::Blue) },
+        ]),
+    ]);
+
+    map.insert("Monnaie de Crésus".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 0, max: 2, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(3) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Charrue de Zhou".to_string(), vec![
+        Effect::DrawAndCheck {
+            age: 3,
+            condition: DrawnCardCondition::ColorOnBoard,
+            on_match: Box::new(Effect::Sequence(vec![
+                Effect::Meld { source: Location::Hand, filters: vec![], min: 1, max: 1 },
+                Effect::Draw { amount: 1, age: Some(3) },
+            ])),
+            on_fail: None,
+        },
+    ]);
+
+    map.insert("Manuscrits de la mer Morte".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 0, max: 2, source: Location::Hand, filters: vec![] },
+            Effect::Draw { amount: 1, age: Some(4) },
+        ]),
+    ]);
+
+    // --- Age 4 ---
+    map.insert("Tapisserie de Bayeux".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Board,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Castle)],
+            min: 1,
+            max: 1,
+        },
+        Effect::Draw { amount: 1, age: Some(4) },
+    ]);
+
+    map.insert("Astrolabe de Tolède".to_string(), vec![
+        Effect::Conditional {
+            condition: Condition::ScoreGreaterThanHand,
+            success: Box::new(Effect::Draw { amount: 2, age: Some(5) }),
+            failure: None,
+        },
+    ]);
+
+    map.insert("Livre du Jugement dernier".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![Filter::ColorsNotOnBoard], min: 1, max: 1 },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(4) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Calice d'Ardagh".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 0, max: 255, source: Location::Hand, filters: vec![Filter::Icon(Symbol::Leaf)] },
+            Effect::Splay { direction: SplayDirection::Right, color: Some(Color::Yellow) },
+        ]),
+    ]);
+
+    map.insert("Livre de Kells".to_string(), vec![
+        Effect::Conditional {
+            condition: Condition::IconCountGreaterThanOrEqual(Symbol::Crown, 4),
+            success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(5) }),
+            failure: None,
+        },
+        Effect::Splay { direction: SplayDirection::Right, color: Some(Color::Purple) },
+    ]);
+
+    // --- Age 5 ---
+    map.insert("Épée de Jeanne d'Arc".to_string(), vec![
+        Effect::Recycle { min: 1, max: 1, source: Location::Hand, filters: vec![Filter::Icon(Symbol::Crown)] },
+        Effect::Draw { amount: 1, age: Some(5) },
+    ]);
+
+    map.insert("Bible de Gutenberg".to_string(), vec![
+        Effect::DrawForIconPairs { age: 5, symbol: Symbol::Lightbulb, divisor: 2 },
+    ]);
+
+    map.insert("Carte de Piri Reis".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndMeld { amount: 1, age: Some(6) },
+            Effect::Splay { direction: SplayDirection::Right, color: None },
+        ]),
+    ]);
+
+    map.insert("Mary Rose".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 1, max: 1, source: Location::Score, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(6) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("La Joconde".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 1, max: 1, filters: vec![Filter::MaxAge] },
+            Effect::Draw { amount: 1, age: Some(5) },
+        ]),
+    ]);
+
+    // --- Age 6 ---
+    map.insert("Tsar-Canon".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Board,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Factory)],
+            min: 1,
+            max: 1,
+        },
+    ]);
+
+    map.insert("Principia de Newton".to_string(), vec![
+        Effect::DrawUntilNoMatch {
+            age: 7,
+            condition: DrawnCardCondition::HasIcon(Symbol::Lightbulb),
+            on_match: Box::new(Effect::Score { min: 1, max: 1, filters: vec![] }),
+        },
+    ]);
+
+    map.insert("Action de la VOC".to_string(), vec![
+        Effect::DrawAndScoreForUniqueColors { age: 6 },
+    ]);
+
+    map.insert("Cloche de la Liberté".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![Filter::ColorsPresentOnBoard], min: 1, max: 1 },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Splay { direction: SplayDirection::Up, color: None }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Codex Leicester".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 0, max: 3, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(7) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    // --- Age 7 ---
+    map.insert("Épée de Napoléon".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Transfer {
+                source: Location::Score,
+                dest: Location::Score,
+                dest_is_activator: true,
+                filters: vec![Filter::MaxAge],
+                min: 1,
+                max: 1,
+            },
+            Effect::Draw { amount: 1, age: Some(7) },
+        ]),
+    ]);
+
+    map.insert("Carnet de Darwin".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Draw { amount: 1, age: Some(8) },
+            Effect::Splay { direction: SplayDirection::Up, color: Some(Color::Blue) },
+        ]),
+    ]);
+
+    map.insert("Acte de la Louisiane".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 0, max: 2, filters: vec![] },
+            Effect::Splay { direction: SplayDirection::Up, color: Some(Color::Green) },
+        ]),
+    ]);
+
+    map.insert("Locomotive Rocket".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndMeld { amount: 1, age: Some(7) },
+            Effect::Conditional {
+                condition: Condition::IconCountGreaterThanOrEqual(Symbol::Factory, 6),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(8) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Statue de la Liberté".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 1, max: 1, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(8) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    // --- Age 8 ---
+    map.insert("Machine Enigma".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Hand,
+            dest: Location::Hand,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Factory)],
+            min: 1,
+            max: 1,
+        },
+        Effect::Draw { amount: 1, age: Some(8) },
+    ]);
+
+    map.insert("Flyer des Wright".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndMeld { amount: 1, age: Some(9) },
+            Effect::Splay { direction: SplayDirection::Up, color: None },
+        ]),
+    ]);
+
+    map.insert("Ford T".to_string(), vec![
+        Effect::DrawForIconPairs { age: 8, symbol: Symbol::Factory, divisor: 2 },
+    ]);
+
+    map.insert("Titanic".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 1, max: 1, source: Location::Hand, filters: vec![Filter::MaxAge] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(9) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Guernica".to_string(), vec![
+        Effect::Conditional {
+            condition: Condition::ScoreGreaterThanHand,
+            success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(9) }),
+            failure: None,
+        },
+    ]);
+
+    // --- Age 9 ---
+    map.insert("Fusée V2".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Board,
+            dest: Location::Board,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Factory)],
+            min: 1,
+            max: 1,
+        },
+        Effect::Splay { direction: SplayDirection::Up, color: Some(Color::Red) },
+    ]);
+
+    map.insert("Spoutnik".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Draw { amount: 1, age: Some(10) },
+            Effect::Conditional {
+                condition: Condition::IconCountGreaterThanOrEqual(Symbol::Clock, 4),
+                success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(10) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("ENIAC".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 1, max: 1, filters: vec![Filter::MaxAge] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(9) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Mur de Berlin".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 0, max: 2, source: Location::Hand, filters: vec![] },
+            Effect::DrawAndMeld { amount: 1, age: Some(9) },
+        ]),
+    ]);
+
+    map.insert("Disque d'or de Voyager".to_string(), vec![
+        Effect::DrawAndScoreForUniqueColors { age: 9 },
+    ]);
+
+    // --- Age 10 ---
+    map.insert("Rover Curiosity".to_string(), vec![
+        Effect::Recycle { min: 1, max: 1, source: Location::Score, filters: vec![Filter::Icon(Symbol::Factory)] },
+        Effect::Draw { amount: 1, age: Some(10) },
+    ]);
+
+    map.insert("Télescope Hubble".to_string(), vec![
+        Effect::DrawUntilNoMatch {
+            age: 10,
+            condition: DrawnCardCondition::HasIcon(Symbol::Lightbulb),
+            on_match: Box::new(Effect::Score { min: 1, max: 1, filters: vec![] }),
+        },
+    ]);
+
+    map.insert("Serveur du Web".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![], min: 1, max: 1 },
+            Effect::Draw { amount: 1, age: Some(10) },
+        ]),
+    ]);
+
+    map.insert("Sarcophage de Tchernobyl".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 0, max: 3, source: Location::Score, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(10) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Image du trou noir".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 1, max: 1, filters: vec![] },
+            Effect::Draw { amount: 1, age: Some(10) },
+        ]),
+    ]);
+}
@@ -0,0 +1,612 @@
+//! Echoes of the Past: the dogmas and echo effects of `data/echoes.json`.
+
+use crate::model::{Color, Condition, DrawnCardCondition, Effect, Filter, Location, SplayDirection, Symbol};
+use rustc_hash::FxHashMap;
+
+pub(super) fn register_dogmas(map: &mut FxHashMap<String, Vec<Effect>>) {
+    // --- Age 1 ---
+    map.insert("Bracelet".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Transfer {
+                source: Location::Hand,
+                dest: Location::Hand,
+                dest_is_activator: true,
+                filters: vec![Filter::Icon(Symbol::Castle)],
+                min: 1,
+                max: 1,
+            },
+            Effect::Draw { amount: 1, age: Some(1) },
+        ]),
+    ]);
+
+    map.insert("Règle".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 0, max: 2, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(2) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Peigne".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![Filter::ColorsNotOnBoard], min: 1, max: 1 },
+            Effect::Draw { amount: 1, age: Some(1) },
+        ]),
+    ]);
+
+    map.insert("Baguettes".to_string(), vec![
+        Effect::DrawAndCheck {
+            age: 1,
+            condition: DrawnCardCondition::ColorOnBoard,
+            on_match: Box::new(Effect::Sequence(vec![
+                Effect::Meld { source: Location::Hand, filters: vec![], min: 1, max: 1 },
+                Effect::Draw { amount: 1, age: Some(1) },
+            ])),
+            on_fail: None,
+        },
+    ]);
+
+    map.insert("Flûte".to_string(), vec![
+        Effect::Conditional {
+            condition: Condition::IconCountGreaterThanOrEqual(Symbol::Crown, 3),
+            success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(2) }),
+            failure: None,
+        },
+    ]);
+
+    // --- Age 2 ---
+    map.insert("Arbalète".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Score,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::MaxAge],
+            min: 1,
+            max: 1,
+        },
+    ]);
+
+    map.insert("Boulier".to_string(), vec![
+        Effect::Conditional {
+            condition: Condition::ScoreGreaterThanHand,
+            success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(3) }),
+            failure: None,
+        },
+    ]);
+
+    map.insert("Balance".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Splay { direction: SplayDirection::Left, color: Some(Color::Green) },
+            Effect::Draw { amount: 1, age: Some(2) },
+        ]),
+    ]);
+
+    map.insert("Savon".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 0, max: 255, source: Location::Hand, filters: vec![Filter::Icon(Symbol::Leaf)] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(2) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Marionnette".to_string(), vec![
+        Effect::DrawAndMeld { amount: 1, age: Some(2) },
+        Effect::Splay { direction: SplayDirection::Left, color: Some(Color::Purple) },
+    ]);
+
+    // --- Age 3 ---
+    map.insert("Catapulte".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Board,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Castle)],
+            min: 1,
+            max: 1,
+        },
+    ]);
+
+    map.insert("Lunettes".to_string(), vec![
+        Effect::DrawUntilNoMatch {
+            age: 3,
+            condition: DrawnCardCondition::HasIcon(Symbol::Lightbulb),
+            on_match: Box::new(Effect::Score { min: 1, max: 1, filters: vec![] }),
+        },
+    ]);
+
+    map.insert("Fourchette".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Draw { amount: 1, age: Some(3) },
+            Effect::Splay { direction: SplayDirection::Right, color: Some(Color::Yellow) },
+        ]),
+    ]);
+
+    map.insert("Moulin à vent".to_string(), vec![
+        Effect::DrawForIconPairs { age: 3, symbol: Symbol::Leaf, divisor: 2 },
+    ]);
+
+    map.insert("Jeu d'échecs".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![Filter::ColorsPresentOnBoard], min: 1, max: 1 },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Splay { direction: SplayDirection::Left, color: None }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    // --- Age 4 ---
+    map.insert("Arquebuse".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Transfer {
+                source: Location::Board,
+                dest: Location::Board,
+                dest_is_activator: true,
+                filters: vec![Filter::Icon(Symbol::Castle)],
+                min: 1,
+                max: 1,
+            },
+            Effect::Draw { amount: 1, age: Some(4) },
+        ]),
+    ]);
+
+    map.insert("Crayon".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 0, max: 3, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(5) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Chèque".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 1, max: 1, filters: vec![Filter::MaxAge] },
+            Effect::Splay { direction: SplayDirection::Right, color: Some(Color::Green) },
+        ]),
+    ]);
+
+    map.insert("Sucre".to_string(), vec![
+        Effect::DrawAndCheck {
+            age: 4,
+            condition: DrawnCardCondition::HasIcon(Symbol::Leaf),
+            on_match: Box::new(Effect::Sequence(vec![
+                Effect::Meld { source: Location::Hand, filters: vec![], min: 1, max: 1 },
+                Effect::Splay { direction: SplayDirection::Right, color: None },
+            ])),
+            on_fail: None,
+        },
+    ]);
+
+    map.insert("Opéra".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 1, max: 1, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Splay { direction: SplayDirection::Right, color: None }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    // --- Age 5 ---
+    map.insert("Baïonnette".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Hand,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::MaxAge],
+            min: 1,
+            max: 1,
+        },
+    ]);
+
+    map.insert("Baromètre".to_string(), vec![
+        Effect::DrawUntilNoMatch {
+            age: 5,
+            condition: DrawnCardCondition::HasIcon(Symbol::Lightbulb),
+            on_match: Box::new(Effect::Score { min: 1, max: 1, filters: vec![] }),
+        },
+    ]);
+
+    map.insert("Bourse".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 0, max: 2, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(6) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Parapluie".to_string(), vec![
+        Effect::DrawAndScoreForUniqueColors { age: 5 },
+    ]);
+
+    map.insert("Piano".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![Filter::ColorsPresentOnBoard], min: 1, max: 1 },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Splay { direction: SplayDirection::Up, color: None }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    // --- Age 6 ---
+    map.insert("Montgolfière".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndMeld { amount: 1, age: Some(6) },
+            Effect::Splay { direction: SplayDirection::Up, color: Some(Color::Red) },
+        ]),
+    ]);
+
+    map.insert("Stéthoscope".to_string(), vec![
+        Effect::Conditional {
+            condition: Condition::IconCountGreaterThanOrEqual(Symbol::Lightbulb, 6),
+            success: Box::new(Effect::Draw { amount: 2, age: Some(7) }),
+            failure: None,
+        },
+        Effect::Splay { direction: SplayDirection::Right, color: Some(Color::Blue) },
+    ]);
+
+    map.insert("Timbre-poste".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Transfer {
+                source: Location::Score,
+                dest: Location::Hand,
+                dest_is_activator: false,
+                filters: vec![],
+                min: 1,
+                max: 1,
+            },
+            Effect::DrawAndScore { amount: 1, age: Some(6) },
+        ]),
+    ]);
+
+    map.insert("Ciment".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 0, max: 255, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Splay { direction: SplayDirection::Up, color: None }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Photographie".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Hand,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Lightbulb)],
+            min: 1,
+            max: 1,
+        },
+    ]);
+
+    // --- Age 7 ---
+    map.insert("Mitrailleuse".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Transfer {
+                source: Location::Board,
+                dest: Location::Score,
+                dest_is_activator: true,
+                filters: vec![Filter::Icon(Symbol::Castle)],
+                min: 1,
+                max: 1,
+            },
+            Effect::Draw { amount: 1, age: Some(7) },
+        ]),
+    ]);
+
+    map.insert("Phonographe".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndMeld { amount: 1, age: Some(7) },
+            Effect::Conditional {
+                condition: Condition::ScoreGreaterThanHand,
+                success: Box::new(Effect::Draw { amount: 1, age: Some(8) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Machine à écrire".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 0, max: 255, source: Location::Hand, filters: vec![] },
+            Effect::DrawForIconPairs { age: 7, symbol: Symbol::Clock, divisor: 2 },
+        ]),
+    ]);
+
+    map.insert("Fil barbelé".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 0, max: 255, source: Location::Hand, filters: vec![] },
+            Effect::Splay { direction: SplayDirection::Right, color: Some(Color::Yellow) },
+        ]),
+    ]);
+
+    map.insert("Cinéma".to_string(), vec![
+        Effect::Splay { direction: SplayDirection::Right, color: Some(Color::Purple) },
+    ]);
+
+    // --- Age 8 ---
+    map.insert("Char d'assaut".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Board,
+            dest: Location::Board,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Factory)],
+            min: 1,
+            max: 1,
+        },
+    ]);
+
+    map.insert("Radar".to_string(), vec![
+        Effect::DrawUntilNoMatch {
+            age: 8,
+            condition: DrawnCardCondition::HasIcon(Symbol::Lightbulb),
+            on_match: Box::new(Effect::Score { min: 1, max: 1, filters: vec![] }),
+        },
+    ]);
+
+    map.insert("Autoroute".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::DrawAndMeld { amount: 1, age: Some(8) },
+            Effect::Splay { direction: SplayDirection::Up, color: Some(Color::Green) },
+        ]),
+    ]);
+
+    map.insert("Plastique".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 0, max: 255, filters: vec![Filter::Icon(Symbol::Factory)] },
+            Effect::Draw { amount: 1, age: Some(8) },
+        ]),
+    ]);
+
+    map.insert("Télévision".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![], min: 0, max: 1 },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Splay { direction: SplayDirection::Up, color: None }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    // --- Age 9 ---
+    map.insert("Hélicoptère".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Transfer {
+                source: Location::Score,
+                dest: Location::Score,
+                dest_is_activator: true,
+                filters: vec![Filter::MaxAge],
+                min: 1,
+                max: 1,
+            },
+            Effect::Draw { amount: 1, age: Some(9) },
+        ]),
+    ]);
+
+    map.insert("Laser".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Recycle { min: 0, max: 2, source: Location::Score, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::DrawAndMeld { amount: 1, age: Some(10) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Code-barres".to_string(), vec![
+        Effect::DrawAndScoreForUniqueColors { age: 9 },
+    ]);
+
+    map.insert("Four à micro-ondes".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Tuck { min: 1, max: 1, source: Location::Hand, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Splay { direction: SplayDirection::Up, color: None }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Jeux vidéo".to_string(), vec![
+        Effect::Conditional {
+            condition: Condition::IconCountGreaterThanOrEqual(Symbol::Crown, 8),
+            success: Box::new(Effect::DrawAndScore { amount: 1, age: Some(10) }),
+            failure: None,
+        },
+        Effect::Splay { direction: SplayDirection::Up, color: Some(Color::Purple) },
+    ]);
+
+    // --- Age 10 ---
+    map.insert("Drone".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Hand,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![],
+            min: 2,
+            max: 2,
+        },
+    ]);
+
+    map.insert("Imprimante 3D".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Meld { source: Location::Hand, filters: vec![Filter::MaxAge], min: 1, max: 1 },
+            Effect::Draw { amount: 1, age: Some(10) },
+        ]),
+    ]);
+
+    map.insert("Cryptomonnaie".to_string(), vec![
+        Effect::Sequence(vec![
+            Effect::Score { min: 0, max: 255, filters: vec![] },
+            Effect::Conditional {
+                condition: Condition::ContextValueGreaterThan(0),
+                success: Box::new(Effect::Draw { amount: 1, age: Some(10) }),
+                failure: None,
+            },
+        ]),
+    ]);
+
+    map.insert("Smartphone".to_string(), vec![
+        Effect::DrawAndCheck {
+            age: 10,
+            condition: DrawnCardCondition::ColorOnBoard,
+            on_match: Box::new(Effect::Sequence(vec![
+                Effect::Meld { source: Location::Hand, filters: vec![], min: 1, max: 1 },
+                Effect::Draw { amount: 1, age: Some(10) },
+            ])),
+            on_fail: None,
+        },
+    ]);
+
+    map.insert("Réseaux sociaux".to_string(), vec![
+        Effect::Transfer {
+            source: Location::Score,
+            dest: Location::Score,
+            dest_is_activator: true,
+            filters: vec![Filter::Icon(Symbol::Crown)],
+            min: 1,
+            max: 1,
+        },
+    ]);
+}
+
+pub(super) fn echo_effects() -> FxHashMap<String, Effect> {
+    let mut map = FxHashMap::default();
+    // --- Age 1 ---
+    map.insert("Bracelet".to_string(), Effect::Tuck { min: 1, max: 1, source: Location::Hand, filters: vec![] });
+    map.insert("Règle".to_string(), Effect::Draw { amount: 1, age: Some(1) });
+    map.insert("Baguettes".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Flûte".to_string(), Effect::Tuck { min: 0, max: 1, source: Location::Hand, filters: vec![] });
+
+    // --- Age 2 ---
+    map.insert("Boulier".to_string(), Effect::Draw { amount: 1, age: Some(2) });
+    map.insert("Balance".to_string(), Effect::Score { min: 1, max: 1, filters: vec![] });
+    map.insert("Marionnette".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+
+    // --- Age 3 ---
+    map.insert("Catapulte".to_string(), Effect::Draw { amount: 1, age: Some(3) });
+    map.insert("Fourchette".to_string(), Effect::Score { min: 0, max: 1, filters: vec![] });
+    map.insert("Moulin à vent".to_string(), Effect::Draw { amount: 1, age: Some(2) });
+
+    // --- Age 4 ---
+    map.insert("Arquebuse".to_string(), Effect::Draw { amount: 1, age: Some(4) });
+    map.insert("Crayon".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Sucre".to_string(), Effect::DrawAndScore { amount: 1, age: Some(3) });
+
+    // --- Age 5 ---
+    map.insert("Baïonnette".to_string(), Effect::Draw { amount: 1, age: Some(5) });
+    map.insert("Baromètre".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Parapluie".to_string(), Effect::Tuck { min: 0, max: 1, source: Location::Hand, filters: vec![] });
+    map.insert("Piano".to_string(), Effect::Draw { amount: 1, age: Some(5) });
+
+    // --- Age 6 ---
+    map.insert("Montgolfière".to_string(), Effect::Draw { amount: 1, age: Some(6) });
+    map.insert("Timbre-poste".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Photographie".to_string(), Effect::Draw { amount: 1, age: Some(6) });
+
+    // --- Age 7 ---
+    map.insert("Mitrailleuse".to_string(), Effect::Draw { amount: 1, age: Some(7) });
+    map.insert("Phonographe".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Fil barbelé".to_string(), Effect::Tuck { min: 0, max: 1, source: Location::Hand, filters: vec![] });
+    map.insert("Cinéma".to_string(), Effect::DrawAndScore { amount: 1, age: Some(7) });
+
+    // --- Age 8 ---
+    map.insert("Char d'assaut".to_string(), Effect::Draw { amount: 1, age: Some(8) });
+    map.insert("Autoroute".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Télévision".to_string(), Effect::Draw { amount: 1, age: Some(8) });
+
+    // --- Age 9 ---
+    map.insert("Hélicoptère".to_string(), Effect::Draw { amount: 1, age: Some(9) });
+    map.insert("Laser".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Four à micro-ondes".to_string(), Effect::DrawAndScore { amount: 1, age: Some(9) });
+
+    // --- Age 10 ---
+    map.insert("Drone".to_string(), Effect::Draw { amount: 1, age: Some(10) });
+    map.insert("Imprimante 3D".to_string(), Effect::Transfer {
+        source: Location::Hand,
+        dest: Location::Foreshadow,
+        dest_is_activator: false,
+        filters: vec![],
+        min: 1,
+        max: 1,
+    });
+    map.insert("Smartphone".to_string(), Effect::Draw { amount: 1, age: Some(10) });
+    map.insert("Réseaux sociaux".to_string(), Effect::Score { min: 0, max: 1, filters: vec![] });
+    map
+}
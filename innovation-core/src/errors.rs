@@ -7,4 +7,10 @@ pub enum GameError {
 
     #[error("Invalid action: {0}")]
     InvalidAction(String),
+
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+
+    #[error("Invalid scenario: {0}")]
+    InvalidScenario(String),
 }
@@ -1,32 +1,78 @@
 // use crate::model::Card;
 use crate::player::Player;
-use crate::actions::Action;
-use crate::db::load_all_cards;
+use crate::actions::{Action, Move};
+use crate::card_list::Decks;
+use crate::db::{self, load_all_cards, CardIdx};
 use crate::errors::GameError;
+use crate::config::{GameConfig, HouseRule};
+use crate::dogma::figures;
+use crate::dogma::karma::{self, KarmaEvent, KarmaTiming, KarmaTrigger};
+use crate::model::{Card, Color, Expansion, Icon, SpecialIcon, SplayDirection};
+use crate::shared::{ChunkedVec, Shared};
+use crate::zobrist::{self, Zone};
 use rand::rngs::StdRng;
-use rand::seq::SliceRandom;
 use std::collections::{HashSet, HashMap};
 
 use serde::{Serialize, Deserialize};
 
+/// Appends an entry to `$state.action_log`, formatted like `format!`, unless the
+/// game was configured without a log (see `GameConfig::log_actions`).
+macro_rules! log_action {
+    ($state:expr, $($arg:tt)*) => {
+        if $state.config.log_actions {
+            let entry = format!($($arg)*);
+            $state.action_log.push(entry);
+        }
+    };
+}
+pub(crate) use log_action;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// IDs of the standard achievements, age 1 to 10.
+const AGE_ACHIEVEMENTS: [&str; 10] = ["Age 1", "Age 2", "Age 3", "Age 4", "Age 5", "Age 6", "Age 7", "Age 8", "Age 9", "Age 10"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GamePhase {
     Setup,
     Main,
     GameOver,
 }
 
-#[derive(Serialize, Deserialize)]
+/// How a finished game was decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EndCondition {
+    /// A team reached the needed number of achievements.
+    Achievements,
+    /// Someone had to draw above age 10: the highest score won.
+    Score,
+    /// A card effect named the winners.
+    Special,
+}
+
+/// Complete state of a game. Clones share the draw piles, board piles and log until
+/// they change, so snapshots for search or undo are cheap.
+///
+/// Piles, hands, score piles and draw piles hold interned cards; card names are only
+/// built at the API boundary (actions, input requests, the log). Random 2-player games
+/// played through [`GameState::legal_moves`] and [`GameState::apply_move`], with
+/// `GameConfig::log_actions` off, run at five to eight thousand games per second on one
+/// core (1.6 to 2.6 million actions). The target remains tens of thousands: dogma
+/// execution and input answers still go through card names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameState {
     #[serde(skip, default = "default_rng")]
     pub rng: StdRng,
-    pub players: Vec<Player>,
-    pub deck: HashMap<u8, Vec<String>>, // Age -> Card IDs
+    pub players: Vec<Shared<Player>>,
+    pub deck: Decks, // Age -> Card IDs
+    /// Draw piles of the enabled expansions, by expansion then age.
+    #[serde(default)]
+    pub expansion_decks: HashMap<Expansion, Decks>,
     pub achievements: HashSet<String>,
     pub current_player: usize,
     pub turn_number: u32,
     pub winners: Vec<usize>,
+    /// How the game ended, once it has.
+    #[serde(default)]
+    pub end_condition: Option<EndCondition>,
     
     pub phase: GamePhase,
     pub initial_melds: HashMap<usize, String>, // Track initial melds during Setup
@@ -34,232 +80,391 @@ pub struct GameState {
     
     pub pending_input: Option<crate::dogma::flow::InputRequest>,
     pub dogma_state: Option<crate::dogma::flow::DogmaExecutionState>,
-    pub action_log: Vec<String>, // Log of recent actions for display
+    pub action_log: ChunkedVec<String>, // Log of recent actions for display
+    
+    /// Rules this game was created with.
+    #[serde(default)]
+    pub config: GameConfig,
+    
+    /// Artifacts: `pending_input` asks the current player what to do with their artifact on display.
+    #[serde(default)]
+    pub artifact_pending: bool,
+    
+    /// Set while a karma effect runs, so it cannot trigger further karmas.
+    #[serde(skip)]
+    pub resolving_karma: bool,
+    
+    /// Hash of the card locations, updated as cards move; see [`GameState::zobrist`].
+    #[serde(skip)]
+    zobrist: u64,
 }
 
 impl GameState {
+    /// Official rules for `player_count` players.
+    ///
+    /// # Panics
+    /// If `player_count` is not between 2 and 4; use [`GameState::from_config`] to handle that.
     pub fn new(seed: u64, player_count: usize) -> Self {
-        Self::new_with_max_age(seed, player_count, 10) // Default: all ages
+        let config = GameConfig::new(player_count).expect("invalid player count");
+        Self::from_config(seed, config).expect("default config is valid")
     }
     
-    pub fn new_with_max_age(seed: u64, player_count: usize, max_age: u8) -> Self {
+    pub fn from_config(seed: u64, config: GameConfig) -> Result<Self, GameError> {
         use rand::SeedableRng;
+        config.validate()?;
         let mut rng = StdRng::seed_from_u64(seed);
+        let (player_count, max_age) = (config.player_count, config.max_age);
         
-        // Load Cards
-        let cards_db = load_all_cards();
-        let mut deck: HashMap<u8, Vec<String>> = HashMap::new();
+        // Load Cards, interned in age then name order, so each pile starts sorted
+        let mut deck = Decks::default();
+        let mut expansion_decks: HashMap<Expansion, Decks> = HashMap::new();
         
-        for card in cards_db.values() {
+        for idx in 0..db::card_count() as CardIdx {
+            let card = db::card_at(idx);
             // Filter by max_age
-            if card.age <= max_age {
-                deck.entry(card.age).or_default().push(card.name.clone());
+            if card.age > max_age {
+                continue;
+            }
+            if card.expansion == Expansion::Base {
+                deck.pile_mut(card.age).push_index(idx);
+            } else if config.has_expansion(card.expansion) {
+                expansion_decks.entry(card.expansion).or_default().pile_mut(card.age).push_index(idx);
             }
         }
         
         // Shuffle
-        for pile in deck.values_mut() {
-            pile.shuffle(&mut rng);
+        let mut shuffle = |decks: &mut Decks| {
+            for pile in decks.values_mut() {
+                pile.shuffle(&mut rng);
+            }
+        };
+        shuffle(&mut deck);
+        for expansion in &config.expansions {
+            if let Some(decks) = expansion_decks.get_mut(expansion) {
+                shuffle(decks);
+            }
         }
         
         // Create Players
         let mut players = Vec::with_capacity(player_count);
         for i in 0..player_count {
-            players.push(Player::new(i));
+            players.push(Shared::new(Player::new(i)));
         }
         
         // Logging for initial draws
-        let mut action_log = Vec::new();
-        action_log.push("=== Début de la partie ===".to_string());
+        let log = config.log_actions;
+        let mut action_log = ChunkedVec::new();
+        if log {
+            action_log.push("=== Début de la partie ===".to_string());
+        }
         
         // Deal Initial Hands (2 cards of Age 1)
         for (i, p) in players.iter_mut().enumerate() {
             if let Some(pile) = deck.get_mut(&1) {
                 for _ in 0..2 {
-                    if let Some(card_id) = pile.pop() {
-                        action_log.push(format!("P{} pioche '{}' (Age 1)", i, card_id));
-                        p.hand.push(card_id);
+                    if let Some(idx) = pile.pop_index() {
+                        if log {
+                            action_log.push(format!("P{} pioche '{}' (Age 1)", i, db::card_at(idx).name));
+                        }
+                        p.hand.push_index(idx);
                     }
                 }
             }
         }
         
-        action_log.push("--- Phase de Setup: chaque joueur pose une carte ---".to_string());
+        if log && !config.skip_setup {
+            action_log.push("--- Phase de Setup: chaque joueur pose une carte ---".to_string());
+        }
         
-        Self {
+        let mut state = Self {
             rng,
             players,
             deck,
+            expansion_decks,
             achievements: HashSet::new(),
             current_player: 0, 
             turn_number: 1,
             winners: Vec::new(),
+            end_condition: None,
             pending_input: None,
             dogma_state: None,
-            phase: if cfg!(test) { GamePhase::Main } else { GamePhase::Setup },
+            phase: if config.skip_setup { GamePhase::Main } else { GamePhase::Setup },
             initial_melds: HashMap::new(),
             actions_taken: 0,
             action_log,
+            config,
+            artifact_pending: false,
+            resolving_karma: false,
+            zobrist: 0,
+        };
+        // Figures: each player starts with a figure in hand
+        if state.config.has_expansion(Expansion::Figures) {
+            for pid in 0..state.players.len() {
+                state.draw_figure(pid, 1);
+            }
+        }
+        state.rehash();
+        Ok(state)
+    }
+    
+    /// Zobrist hash of the position: card locations, splays, achievement holders, whose
+    /// turn it is, actions taken and any pending input or dogma. Equal positions hash
+    /// equally; the order of the draw piles is not part of it.
+    pub fn zobrist(&self) -> u64 {
+        self.zobrist ^ zobrist::turn_key(self)
+    }
+    
+    /// Recomputes the hash from scratch. Needed after editing cards through the public
+    /// fields rather than the game methods, and after deserializing a state.
+    pub fn rehash(&mut self) {
+        self.zobrist = zobrist::card_locations(self);
+    }
+    
+    /// Adds or removes (the same call does both) `card_id` in `zone` from the hash.
+    pub(crate) fn hash_card(&mut self, card_id: &str, zone: Zone) {
+        self.zobrist ^= zobrist::card_key(card_id, zone);
+    }
+    
+    /// `hash_card` for an interned card.
+    pub(crate) fn hash_index(&mut self, idx: CardIdx, zone: Zone) {
+        self.zobrist ^= zobrist::index_key(idx, zone);
+    }
+    
+    /// Adds or removes a whole board pile from the hash: call it before and after
+    /// changing the pile.
+    pub(crate) fn hash_pile(&mut self, player_id: usize, color: Color) {
+        if let Some(pile) = self.players.get(player_id).and_then(|p| p.board.get(&color)) {
+            self.zobrist ^= zobrist::pile_key(player_id, pile);
         }
     }
     
+    /// Debug builds check that the action updated the hash like a full recompute would.
+    /// The check is skipped when the hash was already stale, after fields were edited
+    /// directly: such callers should call `rehash` for `zobrist` to be right again.
     pub fn apply_action(&mut self, action: Action) -> Result<(), GameError> {
-        // Handle Setup Phase
+        #[cfg(debug_assertions)]
+        let (played, in_sync) = (action.clone(), self.zobrist == zobrist::card_locations(self));
+        let result = self.play(action);
+        #[cfg(debug_assertions)]
+        if in_sync {
+            assert_eq!(self.zobrist, zobrist::card_locations(self), "Zobrist hash out of sync after {:?}", played);
+        }
+        result
+    }
+    
+    fn play(&mut self, action: Action) -> Result<(), GameError> {
+        let input = match action {
+            Action::ResolveInput(input) => input,
+            action => return match Move::from_action(&action) {
+                Some(mv) => self.play_move(mv),
+                None => Err(self.unknown_move_error(&action)),
+            },
+        };
+        if self.phase == GamePhase::GameOver {
+            return Err(GameError::InvalidAction("Game is over".into()));
+        }
         if self.phase == GamePhase::Setup {
-            match action {
-                Action::Meld(card_id) => {
-                    // 1. Verify card in hand
-                    let p = &mut self.players[self.current_player];
-                    if !p.hand.contains(&card_id) {
-                        return Err(GameError::InvalidAction("Card not in hand".into()));
-                    }
-                    
-                    // Log setup meld
-                    self.action_log.push(format!("P{} choisit de poser '{}'", self.current_player, card_id));
-                    
-                    // 2. Meld logic (reuse generic meld or inline)
-                    self.meld(self.current_player, card_id.clone())?;
-                    
-                    // 3. Track
-                    self.initial_melds.insert(self.current_player, card_id);
-                    
-                    // 4. Advance
-                    self.current_player += 1;
-                    
-                    // 5. Check if all players ready
-                    if self.current_player >= self.players.len() {
-                        // Determine First Player
-                        // "First alphabetically"
-                        let mut first_player = 0;
-                        let mut first_card = self.initial_melds.get(&0).unwrap().clone();
-                        
-                        for (pid, cid) in &self.initial_melds {
-                             if *cid < first_card {
-                                 first_card = cid.clone();
-                                 first_player = *pid;
-                             } else if *cid == first_card && *pid < first_player {
-                                 // Tie-breaker? Usually not possible with unique names.
-                                 // Or maybe "closer to A"? A < B is true.
-                                 // So min string is correct.
-                             }
-                        }
-                        
-                        // Log first player determination
-                        self.action_log.push(format!("--- P{} commence (carte '{}' première alphabétiquement) ---", first_player, first_card));
-                        self.action_log.push(format!("=== Tour {} - P{} ===", self.turn_number, first_player));
-                        
-                        self.current_player = first_player;
-                        self.phase = GamePhase::Main;
-                        self.actions_taken = 0;
-                    }
-                    
+            return Err(GameError::InvalidAction("Only Meld is allowed during Setup".into()));
+        }
+        let request = self.pending_input.as_ref().ok_or_else(|| GameError::InvalidAction("No input is pending".into()))?;
+        request.check(&input)?;
+        if self.artifact_pending {
+            return self.resolve_artifact_step(&input);
+        }
+        let free = self.dogma_state.as_ref().is_some_and(|ds| ds.free_action);
+        crate::dogma::executor::continue_execution(self, Some(input))?;
+        // Count action when dogma fully resolves
+        if self.pending_input.is_none() && self.dogma_state.is_none() && !free {
+            self.finish_action()?;
+        }
+        Ok(())
+    }
+    
+    /// What playing `action` fails with when it names a card or achievement the
+    /// database does not know: the error the move would give for a card not in play.
+    fn unknown_move_error(&self, action: &Action) -> GameError {
+        let reason = match (&self.phase, action) {
+            (GamePhase::GameOver, _) => "Game is over".to_string(),
+            (GamePhase::Setup, Action::Meld(_)) => "Card not in hand".to_string(),
+            (GamePhase::Setup, _) => "Only Meld is allowed during Setup".to_string(),
+            _ if self.artifact_pending => "Decide on the artifact on display first".to_string(),
+            (_, Action::Dogma(card_id)) => format!("Cannot activate dogma: '{}' is not an active card on your board", card_id),
+            (_, Action::Achieve(_)) => "Invalid achievement target".to_string(),
+            _ => "Card not in hand".to_string(),
+        };
+        GameError::InvalidAction(reason)
+    }
+    
+    fn play_move(&mut self, mv: Move) -> Result<(), GameError> {
+        if self.phase == GamePhase::GameOver {
+            return Err(GameError::InvalidAction("Game is over".into()));
+        }
+        if self.phase == GamePhase::Setup {
+            return match mv {
+                Move::Meld(idx) => self.play_initial_meld(idx),
+                _ => Err(GameError::InvalidAction("Only Meld is allowed during Setup".into())),
+            };
+        }
+        if self.artifact_pending {
+            return Err(GameError::InvalidAction("Decide on the artifact on display first".into()));
+        }
+        let pid = self.current_player;
+        match mv {
+            Move::Draw => self.draw(pid)?,
+            Move::Meld(idx) => {
+                let card_id = &db::card_at(idx).name;
+                log_action!(self, "P{} pose '{}'", pid, card_id);
+                self.inspire(pid, card_id)?;
+                if self.phase != GamePhase::GameOver {
+                    self.meld_index(pid, idx)?;
+                }
+            }
+            Move::Achieve(age) => self.achieve_age(pid, age)?,
+            Move::Decree(color) => {
+                if !self.config.has_expansion(Expansion::Figures) {
+                    return Err(GameError::InvalidAction("Invalid achievement target".into()));
+                }
+                self.issue_decree(pid, color)?;
+            }
+            Move::Dogma(idx) => {
+                crate::dogma::executor::execute_dogma_at(self, pid, idx)?;
+                // Only count as action if dogma completed (no pending input)
+                if self.pending_input.is_some() {
                     return Ok(());
-                },
-                _ => {
-                    return Err(GameError::InvalidAction("Only Meld is allowed during Setup".into()));
                 }
             }
         }
+        self.finish_action()
+    }
+    
+    /// Setup: the current player melds their first card; once everyone has, the
+    /// player whose card comes first alphabetically starts.
+    fn play_initial_meld(&mut self, idx: CardIdx) -> Result<(), GameError> {
+        if !self.players[self.current_player].hand.indices().contains(&idx) {
+            return Err(GameError::InvalidAction("Card not in hand".into()));
+        }
+        let card_id = &db::card_at(idx).name;
+        log_action!(self, "P{} choisit de poser '{}'", self.current_player, card_id);
+        self.meld_index(self.current_player, idx)?;
+        self.initial_melds.insert(self.current_player, card_id.clone());
+        self.current_player += 1;
         
-        // Main Phase
-        match action {
-            Action::Draw => {
-                 self.draw(self.current_player)?;
-                 self.finish_action()?;
-                 Ok(())
-            },
-            Action::Meld(card_id) => {
-                 self.action_log.push(format!("P{} pose '{}'", self.current_player, card_id));
-                 self.meld(self.current_player, card_id)?;
-                 self.finish_action()?;
-                 Ok(())
-            },
-            Action::Achieve(target) => {
-                 self.achieve(self.current_player, target)?;
-                 self.finish_action()?;
-                 Ok(())
-            },
-            Action::Dogma(card_id) => {
-                crate::dogma::executor::execute_dogma(self, self.current_player, card_id)?;
-                // Only count as action if dogma completed (no pending input)
-                if self.pending_input.is_none() {
-                    self.finish_action()?;
-                }
-                Ok(())
-            },
-            Action::ResolveInput(input) => {
-                crate::dogma::executor::continue_execution(self, Some(input))?;
-                // Count action when dogma fully resolves
-                if self.pending_input.is_none() && self.dogma_state.is_none() {
-                    self.finish_action()?;
-                }
-                Ok(())
-            },
+        if self.current_player >= self.players.len() {
+            let first_player = self.first_player();
+            log_action!(self, "--- P{} commence (carte '{}' première alphabétiquement) ---", first_player, self.initial_melds[&first_player]);
+            log_action!(self, "=== Tour {} - P{} ===", self.turn_number, first_player);
+            
+            self.current_player = first_player;
+            self.phase = GamePhase::Main;
+            self.actions_taken = 0;
         }
+        Ok(())
     }
     
     fn finish_action(&mut self) -> Result<(), GameError> {
         self.actions_taken += 1;
         
-        // Action limits per rules:
-        // - 2 players: 1st player gets 1 action on first turn
-        // - 4 players: 1st AND 2nd player get 1 action on first turn
-        // - After turn 1, everyone gets 2 actions
-        let actions_limit = if self.turn_number == 1 {
-            let first_player_id = self.initial_melds.iter()
-                .min_by_key(|(_, card)| card.clone())
-                .map(|(pid, _)| *pid)
-                .unwrap_or(0);
-            
+        // Action limits per rules: the first player (and the second in a 4-player
+        // game, under the standard rules) get a single action on turn 1
+        let rules = &self.config.ruleset;
+        let actions_limit = if self.turn_number == 1 && !self.config.has_house_rule(HouseRule::NoFirstTurnLimit) {
             let player_count = self.players.len();
-            let current_pos = (self.current_player + player_count - first_player_id) % player_count;
-            
-            // 4 players: positions 0 and 1 get 1 action
-            // 2-3 players: position 0 gets 1 action
-            if player_count == 4 && current_pos <= 1 {
-                1
-            } else if current_pos == 0 {
-                1
-            } else {
-                2
-            }
+            let current_pos = (self.current_player + player_count - self.first_player()) % player_count;
+            rules.first_turn_actions(player_count, current_pos)
         } else {
-            2
+            rules.actions_per_turn
         };
         
         if self.actions_taken >= actions_limit {
             self.actions_taken = 0;
-            let prev_player = self.current_player;
             self.current_player = (self.current_player + 1) % self.players.len();
             
-            let first_player = self.initial_melds.iter()
-                .min_by_key(|(_, card)| card.clone())
-                .map(|(pid, _)| *pid)
-                .unwrap_or(0);
-            
-            if self.current_player == first_player { // Full round
+            if self.current_player == self.first_player() { // Full round
                  self.turn_number += 1;
-                 self.action_log.push(format!("=== Tour {} ===", self.turn_number));
+                 log_action!(self, "=== Tour {} ===", self.turn_number);
             }
-            self.action_log.push(format!("--- Au tour de P{} ---", self.current_player));
+            log_action!(self, "--- Au tour de P{} ---", self.current_player);
+            self.start_turn();
         }
         Ok(())
     }
     
+    /// Seat that played first: the one whose initial meld came first alphabetically.
+    fn first_player(&self) -> usize {
+        self.initial_melds.iter()
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map_or(0, |(pid, _)| *pid)
+    }
+    
+    /// Artifacts: a player starting their turn with an artifact on display first
+    /// decides to meld it (and dogma it for free) or to return it.
+    pub(crate) fn start_turn(&mut self) {
+        if self.phase != GamePhase::Main {
+            return;
+        }
+        let pid = self.current_player;
+        if let Some(artifact) = &self.players[pid].display {
+            self.pending_input = Some(crate::dogma::flow::InputRequest::Confirm {
+                player_id: pid,
+                message: format!("Meld '{}' from display and dogma it for free (yes), or return it (no)?", artifact),
+            });
+            self.artifact_pending = true;
+        }
+    }
+    
+    fn resolve_artifact_step(&mut self, input: &str) -> Result<(), GameError> {
+        self.pending_input = None;
+        self.artifact_pending = false;
+        let pid = self.current_player;
+        let Some(artifact) = self.players[pid].display.take() else { return Ok(()) };
+        self.hash_card(&artifact, Zone::Display(pid));
+        
+        if !matches!(input.trim().to_lowercase().as_str(), "yes" | "oui" | "y") {
+            log_action!(self, "P{} recycle l'artefact '{}'", pid, artifact);
+            return self.return_card(&artifact);
+        }
+        log_action!(self, "P{} pose l'artefact '{}'", pid, artifact);
+        self.add_to_hand(pid, &artifact)?;
+        self.meld(pid, &artifact)?;
+        
+        // A karma may have kept the artifact off the top of its pile
+        let on_top = self.players[pid].board.values().any(|p| p.top() == Some(&artifact));
+        if on_top && self.phase == GamePhase::Main {
+            crate::dogma::executor::execute_dogma(self, pid, artifact)?;
+            if let Some(ds) = self.dogma_state.as_mut() {
+                ds.free_action = true;
+            }
+        }
+        Ok(())
+    }
+    
+    /// Artifacts: takes an artifact of `age` (or the next age that has one) onto
+    /// `player_id`'s display.
+    pub fn dig(&mut self, player_id: usize, age: u8) -> Option<&'static String> {
+        let idx = self.pop_expansion_pile(Expansion::Artifacts, age)?;
+        let card_id = &db::card_at(idx).name;
+        log_action!(self, "P{} déterre l'artefact '{}'", player_id, card_id);
+        self.hash_index(idx, Zone::Display(player_id));
+        self.players[player_id].display = Some(card_id.clone());
+        Some(card_id)
+    }
+    
+    /// Takes the top card of `expansion`'s pile of `age`, or of the next age that has
+    /// one, for the expansions whose running out does not end the game.
+    fn pop_expansion_pile(&mut self, expansion: Expansion, age: u8) -> Option<CardIdx> {
+        let decks = self.expansion_decks.get_mut(&expansion)?;
+        let idx = (age.max(1)..=10).find_map(|a| decks.get_mut(&a).and_then(|pile| pile.pop_index()))?;
+        self.hash_index(idx, Zone::Deck);
+        Some(idx)
+    }
+    
     pub fn draw(&mut self, player_id: usize) -> Result<(), GameError> {
         // Simple draw logic: Max(TopCardAge, 1)
-        let mut age_to_draw = 1; 
-        if let Some(p) = self.players.get(player_id) {
-            // Find max top card age
-            for pile in p.board.values() {
-                if let Some(top_id) = pile.top() {
-                    if let Some(card) = load_all_cards().get(top_id) {
-                        if card.age > age_to_draw {
-                            age_to_draw = card.age;
-                        }
-                    }
-                }
-            }
+        let age_to_draw = self.players.get(player_id).map_or(0, |p| p.max_top_age()).max(1);
+        
+        let event = KarmaEvent::age(player_id, KarmaTrigger::Draw, age_to_draw);
+        if karma::intercept(self, &event, KarmaTiming::Instead)? {
+            return Ok(());
         }
         
         // Try drawing from Age, then Age+1, ...
@@ -270,138 +475,449 @@ impl GameState {
                 return Ok(());
             }
             
-            if let Some(deck_pile) = self.deck.get_mut(&age) {
-                if let Some(card_id) = deck_pile.pop() {
-                    self.action_log.push(format!("P{} pioche '{}' (Age {})", player_id, card_id, age));
-                    if let Some(p) = self.players.get_mut(player_id) {
-                        p.hand.push(card_id);
+            if let Some(idx) = self.pop_draw_pile(player_id, age) {
+                log_action!(self, "P{} pioche '{}' (Age {})", player_id, db::card_at(idx).name, age);
+                self.add_index_to_hand(player_id, idx)?;
+                karma::intercept(self, &event, KarmaTiming::After)?;
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+    
+    pub fn meld(&mut self, player_id: usize, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        let card_id = card_id.as_ref();
+        if player_id >= self.players.len() {
+            return Err(GameError::InvalidAction("Invalid Player".into()));
+        }
+        let idx = db::card_index(card_id).ok_or_else(|| GameError::InvalidAction("Card not in hand".into()))?;
+        self.meld_index(player_id, idx)
+    }
+    
+    /// `meld` for an interned card.
+    fn meld_index(&mut self, player_id: usize, idx: CardIdx) -> Result<(), GameError> {
+        let card = db::card_at(idx);
+        let event = KarmaEvent::card_at(player_id, KarmaTrigger::Meld, idx);
+        let p = self.players.get_mut(player_id).ok_or_else(|| GameError::InvalidAction("Invalid Player".into()))?;
+        let at = p.hand.indices().iter().position(|&i| i == idx);
+        if at.is_some() && karma::intercept(self, &event, KarmaTiming::Instead)? {
+            return Ok(());
+        }
+        let p = &mut self.players[player_id];
+        // A karma may have moved the card, so look it up again
+        let at = p.hand.indices().iter().position(|&i| i == idx).ok_or_else(|| GameError::InvalidAction("Card not in hand".into()))?;
+        p.hand.remove_index(at);
+        self.hash_index(idx, Zone::Hand(player_id));
+        let covered = self.players[player_id].board.get(&card.color).and_then(|pile| pile.top_index());
+        let dig = covered.is_some_and(|top| db::card_stats(top).age == card.age);
+        self.place_index_on_board(player_id, idx);
+        
+        // Artifacts: covering a card of the same value digs up an artifact, if the display is free
+        if dig && self.config.has_expansion(Expansion::Artifacts) && self.players[player_id].display.is_none() {
+            self.dig(player_id, card.age);
+        }
+        
+        if card.expansion == Expansion::Cities {
+            self.apply_city_icons(player_id, card)?;
+        }
+        // Cities: starting a new color pile draws a city, if none is in hand yet
+        let new_pile = self.players[player_id].board.get(&card.color).map(|p| p.len()) == Some(1);
+        if new_pile && self.config.has_expansion(Expansion::Cities) && !self.has_city_in_hand(player_id) {
+            self.draw_city(player_id, card.age);
+        }
+        self.promote_foreshadowed(player_id, card.age)?;
+        karma::intercept(self, &event, KarmaTiming::After)?;
+        Ok(())
+    }
+    
+    /// Cities: the plus and arrow icons of a city act when it is melded.
+    fn apply_city_icons(&mut self, player_id: usize, city: &Card) -> Result<(), GameError> {
+        for icon in &city.icons {
+            match icon {
+                Icon::Special(SpecialIcon::Plus) => {
+                    if let Some(card_id) = self.draw_age(player_id, city.age + 1)? {
+                        log_action!(self, "P{} pioche '{}' (cité)", player_id, card_id);
                     }
-                    return Ok(());
                 }
+                Icon::Special(SpecialIcon::SplayLeft) => self.splay(player_id, city.color, SplayDirection::Left)?,
+                Icon::Special(SpecialIcon::SplayRight) => self.splay(player_id, city.color, SplayDirection::Right)?,
+                Icon::Special(SpecialIcon::SplayUp) => self.splay(player_id, city.color, SplayDirection::Up)?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    
+    fn has_city_in_hand(&self, player_id: usize) -> bool {
+        self.players[player_id].hand.indices().iter().any(|&idx| db::card_at(idx).expansion == Expansion::Cities)
+    }
+    
+    /// Cities: draws a city of `age` (or the next age that has one). Running out of
+    /// cities does not end the game.
+    pub fn draw_city(&mut self, player_id: usize, age: u8) -> Option<&'static String> {
+        let idx = self.pop_expansion_pile(Expansion::Cities, age)?;
+        log_action!(self, "P{} pioche la cité '{}'", player_id, db::card_at(idx).name);
+        self.add_index_to_hand(player_id, idx).ok()?;
+        Some(&db::card_at(idx).name)
+    }
+    
+    /// Figures: draws a figure of `age` (or the next age that has one). Running out of
+    /// figures does not end the game.
+    pub fn draw_figure(&mut self, player_id: usize, age: u8) -> Option<&'static String> {
+        let idx = self.pop_expansion_pile(Expansion::Figures, age)?;
+        log_action!(self, "P{} pioche le personnage '{}'", player_id, db::card_at(idx).name);
+        self.add_index_to_hand(player_id, idx).ok()?;
+        Some(&db::card_at(idx).name)
+    }
+    
+    /// Figures: before a Meld action, the inspire effects visible on the pile of the
+    /// card's color run, bottom to top.
+    pub fn inspire(&mut self, player_id: usize, card_id: &str) -> Result<(), GameError> {
+        if !self.config.has_expansion(Expansion::Figures) || !self.players[player_id].hand.contains(card_id) {
+            return Ok(());
+        }
+        let db = load_all_cards();
+        let color = db::card(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?.color;
+        let inspiring = self.players[player_id].board.get(&color).map(|pile| pile.visible_inspires(db)).unwrap_or_default();
+        for figure in inspiring {
+            let Some(effect) = crate::dogma::figures::get_inspire(&figure) else { continue };
+            log_action!(self, "P{} est inspiré par '{}'", player_id, figure);
+            effect(self, player_id)?;
+            if self.phase == GamePhase::GameOver {
+                break;
             }
         }
         Ok(())
     }
     
-    pub fn meld(&mut self, player_id: usize, card_id: String) -> Result<(), GameError> {
+    /// Puts a card on top of its color pile (meld, or a card transferred onto a board).
+    pub fn place_on_board(&mut self, player_id: usize, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        let card_id = card_id.as_ref();
+        let idx = db::card_index(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?;
+        if player_id >= self.players.len() {
+            return Err(GameError::InvalidAction("Invalid Player".into()));
+        }
+        self.place_index_on_board(player_id, idx);
+        Ok(())
+    }
+    
+    /// `place_on_board` for an interned card, for a seat that exists. The cards
+    /// below keep their depth, so only the new one enters the hash.
+    fn place_index_on_board(&mut self, player_id: usize, idx: CardIdx) {
+        let pile = self.players[player_id].board.pile_mut(db::card_stats(idx).color);
+        let depth = pile.len();
+        pile.push_index(idx);
+        self.hash_index(idx, Zone::Board { player: player_id, depth });
+    }
+    
+    /// Removes a top card from `player_id`'s board. Emptied piles are dropped, and a pile
+    /// left with a single card loses its splay.
+    pub fn remove_from_board(&mut self, player_id: usize, card_id: &str) -> Result<&'static String, GameError> {
+        let card = db::card(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?;
+        let p = self.players.get(player_id).ok_or_else(|| GameError::InvalidAction("Invalid Player".into()))?;
+        if p.board.get(&card.color).and_then(|pile| pile.top()).map(|t| t.as_str()) != Some(card_id) {
+            return Err(GameError::InvalidAction("Card not on top of a pile".into()));
+        }
+        self.hash_pile(player_id, card.color);
         let p = &mut self.players[player_id];
-        if let Some(idx) = p.hand.iter().position(|c| c == &card_id) {
-            let card_id = p.hand.remove(idx);
-            let cards_db = load_all_cards();
-            let card = cards_db.get(&card_id).ok_or_else(|| GameError::CardNotFound(card_id.clone()))?;
-            
-            p.board.entry(card.color).or_insert_with(|| crate::player::Pile::new(card.color))
-                .cards.push(card_id);
-            Ok(())
-        } else {
-            Err(GameError::InvalidAction("Card not in hand".into()))
+        let pile = p.board.get_mut(&card.color).expect("checked above");
+        let removed = pile.pop().expect("checked above");
+        if pile.is_empty() {
+            p.board.remove(&card.color);
+        } else if pile.len() < 2 {
+            pile.set_splay(crate::model::SplayDirection::None);
         }
+        self.hash_pile(player_id, card.color);
+        Ok(removed)
     }
 
     pub fn achieve(&mut self, player_id: usize, target: String) -> Result<(), GameError> {
         // Parse target. Ex: "Age 1", "Age 2"...
-        if let Some(age_str) = target.strip_prefix("Age ") {
-            if let Ok(age) = age_str.parse::<u8>() {
-                // Check if already claimed
-                let ach_id = format!("Age {}", age);
-                if self.achievements.contains(&ach_id) {
-                    return Err(GameError::InvalidAction("Achievement already claimed".into()));
+        if let Some(age) = target.strip_prefix("Age ").and_then(|age| age.parse::<u8>().ok()) {
+            return self.achieve_age(player_id, age);
+        }
+        if let Some(color) = figures::decree_color(&target).filter(|_| self.config.has_expansion(Expansion::Figures)) {
+            return self.issue_decree(player_id, color);
+        }
+        Err(GameError::InvalidAction("Invalid achievement target".into()))
+    }
+    
+    fn achieve_age(&mut self, player_id: usize, age: u8) -> Result<(), GameError> {
+        self.check_achievement(player_id, age)?;
+        let event = KarmaEvent::age(player_id, KarmaTrigger::Achieve, age);
+        if karma::intercept(self, &event, KarmaTiming::Instead)? {
+            return Ok(());
+        }
+        
+        // Claim
+        let ach_id = format!("Age {}", age);
+        self.achievements.insert(ach_id.clone());
+        self.hash_card(&ach_id, Zone::Achievements(player_id));
+        self.players[player_id].achievements.push(ach_id);
+        
+        self.check_victory();
+        karma::intercept(self, &event, KarmaTiming::After)?;
+        self.deal_figures_to_opponents(player_id);
+        Ok(())
+    }
+    
+    /// Figures: claims a decree by returning every figure in hand, then runs its effect.
+    fn issue_decree(&mut self, player_id: usize, color: Color) -> Result<(), GameError> {
+        let decree = figures::decree_name(color);
+        if self.achievements.contains(decree) {
+            return Err(GameError::InvalidAction("Achievement already claimed".into()));
+        }
+        if !figures::can_issue_decree(self, player_id, color) {
+            return Err(GameError::InvalidAction("Not enough figures in hand for this decree".into()));
+        }
+        let returned: Vec<&'static String> = self.players[player_id].hand.names()
+            .filter(|id| figures::is_figure(id))
+            .collect();
+        for card_id in returned {
+            self.remove_from_hand(player_id, card_id)?;
+            self.return_card(card_id)?;
+        }
+        log_action!(self, "P{} promulgue '{}'", player_id, decree);
+        self.claim_special_achievement(player_id, decree)?;
+        if self.phase != GamePhase::GameOver {
+            figures::apply_decree(self, player_id, color)?;
+        }
+        self.deal_figures_to_opponents(player_id);
+        Ok(())
+    }
+    
+    /// Figures: after `player_id` achieves, each opponent draws a figure of their highest top card.
+    fn deal_figures_to_opponents(&mut self, player_id: usize) {
+        if self.phase == GamePhase::GameOver || !self.config.has_expansion(Expansion::Figures) {
+            return;
+        }
+        for opp in 0..self.players.len() {
+            if !self.config.teammates(opp, player_id) {
+                let age = self.players[opp].max_top_age().max(1);
+                self.draw_figure(opp, age);
+            }
+        }
+    }
+    
+    /// Checks whether `player_id` may claim the standard achievement of `age`.
+    pub fn check_achievement(&self, player_id: usize, age: u8) -> Result<(), GameError> {
+        let p = &self.players[player_id];
+        let claimed = self.achievements.contains(&format!("Age {}", age));
+        match self.achievement_blocker(age, claimed, p.calculate_score(), p.max_top_age()) {
+            Some(reason) => Err(GameError::InvalidAction(reason.into())),
+            None => Ok(()),
+        }
+    }
+
+    /// Why a player with the given score and highest top card may not claim the
+    /// standard achievement of `age`, if they may not.
+    fn achievement_blocker(&self, age: u8, claimed: bool, score: u32, max_top_age: u8) -> Option<&'static str> {
+        if claimed {
+            return Some("Achievement already claimed");
+        }
+        // Check eligibility
+        let rules = &self.config.ruleset;
+        let required_score = (age as u32) * rules.achievement_cost_per_age;
+        if score < required_score {
+            return Some("Insufficient score");
+        }
+        if rules.achievement_needs_top_card && max_top_age < age {
+            return Some("No top card of sufficient age");
+        }
+        None
+    }
+    
+    /// Lists the actions the current player may take, in a deterministic order.
+    /// Empty once the game is over or while an input is pending (answer it with `Action::ResolveInput`).
+    pub fn legal_actions(&self) -> Vec<Action> {
+        self.legal_moves().into_iter().map(Action::from).collect()
+    }
+    
+    /// `legal_actions` as moves, in the same order.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.phase == GamePhase::GameOver || self.pending_input.is_some() {
+            return Vec::new();
+        }
+        let p = &self.players[self.current_player];
+        let mut moves = Vec::with_capacity(p.hand.len() + 8);
+        
+        if self.phase == GamePhase::Setup {
+            moves.extend(p.hand.indices().iter().map(|&idx| Move::Meld(idx)));
+            return moves;
+        }
+        
+        moves.push(Move::Draw);
+        moves.extend(p.hand.indices().iter().map(|&idx| Move::Meld(idx)));
+        let (score, max_top_age) = (p.calculate_score(), p.max_top_age());
+        let mut affordable = [false; 10];
+        for age in 1..=10 {
+            affordable[age as usize - 1] = self.achievement_blocker(age, false, score, max_top_age).is_none();
+        }
+        if affordable.contains(&true) {
+            // One pass over the claimed achievements beats hashing each age's ID
+            for id in &self.achievements {
+                let age = id.strip_prefix("Age ").and_then(|n| n.parse::<usize>().ok());
+                if let Some(age) = age.filter(|&age| AGE_ACHIEVEMENTS.get(age.wrapping_sub(1)) == Some(&id.as_str())) {
+                    affordable[age - 1] = false;
                 }
-                // Check eligibility
-                let p = &self.players[player_id];
-                let score = p.calculate_score(load_all_cards());
-                let required_score = (age as u32) * 5;
-                if score < required_score {
-                    return Err(GameError::InvalidAction("Insufficient score".into()));
+            }
+            moves.extend((1..=10).filter(|&age| affordable[age as usize - 1]).map(Move::Achieve));
+        }
+        if self.config.has_expansion(Expansion::Figures) {
+            for (decree, color) in figures::DECREES {
+                if !self.achievements.contains(decree) && figures::can_issue_decree(self, self.current_player, color) {
+                    moves.push(Move::Decree(color));
                 }
-                
-                // Check top card >= Age
-                let max_top_age = p.board.values().filter_map(|s| s.top())
-                    .filter_map(|id| load_all_cards().get(id))
-                    .map(|c| c.age)
-                    .max()
-                    .unwrap_or(0);
-                    
-                if max_top_age < age {
-                    return Err(GameError::InvalidAction("No top card of sufficient age".into()));
+            }
+        }
+        for color in Color::ALL {
+            if let Some(top) = p.board.get(&color).and_then(|pile| pile.top_index()) {
+                moves.push(Move::Dogma(top));
+            }
+        }
+        moves
+    }
+    
+    /// `apply_action` for a move: the same checks and effects, without going through
+    /// card names.
+    pub fn apply_move(&mut self, mv: Move) -> Result<(), GameError> {
+        #[cfg(debug_assertions)]
+        let in_sync = self.zobrist == zobrist::card_locations(self);
+        let result = self.play_move(mv);
+        #[cfg(debug_assertions)]
+        if in_sync {
+            assert_eq!(self.zobrist, zobrist::card_locations(self), "Zobrist hash out of sync after {:?}", mv);
+        }
+        result
+    }
+    
+    /// Who the current player's dogma of `card_id` would reach, and a likely result,
+    /// with inputs answered by the hard heuristic bot. The game itself is not touched.
+    pub fn preview_dogma(&self, card_id: &str) -> Result<crate::dogma::preview::DogmaPreview, GameError> {
+        crate::dogma::preview::preview(self, card_id, &mut crate::agent::HeuristicAgent::new(self.zobrist()))
+    }
+    
+    /// Seat expected to act next: the one answering the pending input, else the current player.
+    pub fn acting_player(&self) -> usize {
+        self.pending_input.as_ref().map_or(self.current_player, |req| req.player_id())
+    }
+    
+    /// Seats of the team `player_id` plays in (just `player_id` outside team play), in seat order.
+    pub fn team_members(&self, player_id: usize) -> Vec<usize> {
+        (0..self.players.len()).filter(|&pid| self.config.teammates(pid, player_id)).collect()
+    }
+    
+    /// Achievements held by `player_id`'s team; partners share them toward victory.
+    pub fn team_achievements(&self, player_id: usize) -> usize {
+        self.team_members(player_id).iter().map(|&pid| self.achievement_count(pid)).sum()
+    }
+    
+    /// Claimed achievements plus those granted by visible Cities fountains and flags.
+    pub fn achievement_count(&self, player_id: usize) -> usize {
+        let p = &self.players[player_id];
+        let mut count = p.achievements.len();
+        for pile in p.board.values() {
+            let icons = pile.icons();
+            count += icons.fountains as usize;
+            if icons.flags > 0 {
+                // Flags are held while no opponent shows more cards of that color
+                let shown = pile.visible_card_count();
+                let beaten = self.players.iter()
+                    .filter(|o| !self.config.teammates(o.id, player_id))
+                    .any(|o| o.board.get(&pile.color).map_or(0, |op| op.visible_card_count()) > shown);
+                if !beaten {
+                    count += icons.flags as usize;
                 }
-                
-                // Claim
-                self.achievements.insert(ach_id.clone());
-                self.players[player_id].achievements.push(ach_id);
-                
-                self.check_victory();
-                return Ok(());
             }
         }
-        Err(GameError::InvalidAction("Invalid achievement target".into()))
+        count
     }
     
     pub fn check_victory(&mut self) {
-        let achievements_needed = match self.players.len() {
-            2 => 6,
-            3 => 5,
-            4 => 4,
-            _ => 6,
-        };
+        let achievements_needed = self.config.achievements_needed;
         
         for p in &self.players {
-            if p.achievements.len() >= achievements_needed {
-                self.winners = vec![p.id];
+            if self.team_achievements(p.id) >= achievements_needed {
+                self.winners = self.team_members(p.id);
+                self.end_condition = Some(EndCondition::Achievements);
+                self.phase = GamePhase::GameOver;
                 return;
             }
         }
     }
     
+    /// Ends the game on score, compared per team (summed over partners).
     pub fn end_game_by_score(&mut self) {
-        let mut max_score = 0;
-        let mut winners = Vec::new();
-        let db = load_all_cards();
-        
+        // (score, achievements) of each team, keyed by its lowest seat
+        let mut teams: Vec<(usize, u32, usize)> = Vec::new();
         for p in &self.players {
-            let score = p.calculate_score(db); // Note: Should include Achievements count as tiebreaker?
-            // Rules: Highest score wins. Tiebreaker: Most achievements.
-            if score > max_score {
-                max_score = score;
-                winners = vec![p.id];
-            } else if score == max_score {
-                winners.push(p.id);
+            let team = self.team_members(p.id);
+            if team[0] != p.id {
+                continue;
             }
+            let score = team.iter().map(|&pid| self.players[pid].calculate_score()).sum();
+            teams.push((p.id, score, self.team_achievements(p.id)));
         }
-        // Apply tiebreaker (Achievements) if multiple winners
-        if winners.len() > 1 {
-            let mut max_ach = 0;
-            let mut final_winners = Vec::new();
-            for &pid in &winners {
-                let count = self.players[pid].achievements.len();
-                if count > max_ach {
-                    max_ach = count;
-                    final_winners = vec![pid];
-                } else if count == max_ach {
-                    final_winners.push(pid);
-                }
-            }
-            self.winners = final_winners;
-        } else {
-            self.winners = winners;
-        }
+        
+        // Rules: Highest score wins. Tiebreaker: Most achievements.
+        let best = teams.iter().map(|&(_, score, ach)| (score, ach)).max().unwrap_or_default();
+        self.winners = teams.iter()
+            .filter(|&&(_, score, ach)| (score, ach) == best)
+            .flat_map(|&(pid, _, _)| self.team_members(pid))
+            .collect();
+        self.winners.sort();
+        self.end_condition = Some(EndCondition::Score);
+        self.phase = GamePhase::GameOver;
+    }
+    
+    /// Ends the game for a card that says "vous gagnez": `player_id` and their partners win.
+    pub fn win_by_effect(&mut self, player_id: usize) {
+        self.winners = self.team_members(player_id);
+        self.end_condition = Some(EndCondition::Special);
+        self.phase = GamePhase::GameOver;
     }
 
     // --- Primitives for Dogma Effects ---
 
-    pub fn draw_age(&mut self, player_id: usize, mut age: u8) -> Result<Option<String>, GameError> {
+    /// Expansion pile `player_id` draws from instead of the base pile, if any.
+    /// Echoes: when the hand is not empty and holds no Echoes card.
+    pub fn expansion_to_draw(&self, player_id: usize) -> Option<Expansion> {
+        if !self.config.has_expansion(Expansion::Echoes) {
+            return None;
+        }
+        let hand = &self.players.get(player_id)?.hand;
+        let holds_echoes = hand.indices().iter().any(|&idx| db::card_at(idx).expansion == Expansion::Echoes);
+        (!hand.is_empty() && !holds_echoes).then_some(Expansion::Echoes)
+    }
+    
+    /// Takes the top card of `age` for `player_id`, from the expansion pile the draw
+    /// rules pick or, if there is none or it is empty, from the base pile.
+    fn pop_draw_pile(&mut self, player_id: usize, age: u8) -> Option<CardIdx> {
+        let mut idx = None;
+        if let Some(expansion) = self.expansion_to_draw(player_id) {
+            let pile = self.expansion_decks.get_mut(&expansion).and_then(|decks| decks.get_mut(&age));
+            idx = pile.and_then(|p| p.pop_index());
+        }
+        let idx = idx.or_else(|| self.deck.get_mut(&age).and_then(|p| p.pop_index()))?;
+        self.hash_index(idx, Zone::Deck);
+        Some(idx)
+    }
+
+    pub fn draw_age(&mut self, player_id: usize, mut age: u8) -> Result<Option<&'static String>, GameError> {
         if age < 1 { return Ok(None); }
         // Cap age at 10 (or 11? Rules say if draw 11 -> end game)
         // Innovation rules: If you must draw a card of value 11 or higher, the game ends.
         
+        let event = KarmaEvent::age(player_id, KarmaTrigger::Draw, age);
+        if karma::intercept(self, &event, KarmaTiming::Instead)? {
+            return Ok(None);
+        }
+        
         while age <= 10 {
-            if let Some(pile) = self.deck.get_mut(&age) {
-                if let Some(card_id) = pile.pop() {
-                    if let Some(p) = self.players.get_mut(player_id) {
-                        p.hand.push(card_id.clone());
-                    }
-                    return Ok(Some(card_id));
-                }
+            if let Some(idx) = self.pop_draw_pile(player_id, age) {
+                self.add_index_to_hand(player_id, idx)?;
+                karma::intercept(self, &event, KarmaTiming::After)?;
+                return Ok(Some(&db::card_at(idx).name));
             }
             age += 1;
         }
@@ -411,56 +927,129 @@ impl GameState {
         Ok(None)
     }
 
-    pub fn tuck_card(&mut self, player_id: usize, card_id: String) -> Result<(), GameError> {
-        let card = load_all_cards().get(&card_id).ok_or_else(|| GameError::CardNotFound(card_id.clone()))?;
-        let color = card.color;
+    /// Callers take the card out of its zone first; if a karma replaces the tuck,
+    /// the karma decides where the card goes.
+    pub fn tuck_card(&mut self, player_id: usize, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        let card_id = card_id.as_ref();
+        let idx = db::card_index(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?;
+        let color = db::card_stats(idx).color;
+        let event = KarmaEvent::card(player_id, KarmaTrigger::Tuck, card_id);
+        if karma::intercept(self, &event, KarmaTiming::Instead)? {
+            return Ok(());
+        }
         
+        self.hash_pile(player_id, color);
         if let Some(p) = self.players.get_mut(player_id) {
-            let pile = p.board.entry(color).or_insert_with(|| crate::player::Pile::new(color));
-            pile.cards.insert(0, card_id); // Tuck = Insert at bottom (index 0)
+            p.board.pile_mut(color).tuck_index(idx);
         }
+        self.hash_pile(player_id, color);
+        karma::intercept(self, &event, KarmaTiming::After)?;
         Ok(())
     }
 
-    pub fn score_card(&mut self, player_id: usize, card_id: String) -> Result<(), GameError> {
+    /// Same contract as `tuck_card` for karmas replacing the score.
+    pub fn score_card(&mut self, player_id: usize, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        let card_id = card_id.as_ref();
+        let idx = db::card_index(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?;
+        let event = KarmaEvent::card(player_id, KarmaTrigger::Score, card_id);
+        if karma::intercept(self, &event, KarmaTiming::Instead)? {
+            return Ok(());
+        }
         if let Some(p) = self.players.get_mut(player_id) {
-            p.score_pile.push(card_id);
+            p.score_pile.push_index(idx);
+            self.hash_index(idx, Zone::Score(player_id));
         }
+        karma::intercept(self, &event, KarmaTiming::After)?;
         Ok(())
     }
 
-    pub fn return_card(&mut self, card_id: String) -> Result<(), GameError> {
-        let card = load_all_cards().get(&card_id).ok_or_else(|| GameError::CardNotFound(card_id.clone()))?;
-        let age = card.age;
+    pub fn return_card(&mut self, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        let card_id = card_id.as_ref();
+        let idx = db::card_index(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?;
+        let card = db::card_at(idx);
         
-        // Return to bottom of deck
-        self.deck.entry(age).or_default().insert(0, card_id);
+        // Return to bottom of deck (of its own set)
+        let deck = match card.expansion {
+            Expansion::Base => &mut self.deck,
+            expansion => self.expansion_decks.entry(expansion).or_default(),
+        };
+        deck.pile_mut(card.age).insert_index(0, idx);
+        self.hash_index(idx, Zone::Deck);
         Ok(())
     }
     
-    // Helper to remove from hand (since many effects do "Return a card from Hand")
-    pub fn remove_from_hand(&mut self, player_id: usize, card_id: &String) -> Result<String, GameError> {
-        let p = self.players.get_mut(player_id).ok_or(GameError::InvalidAction("Invalid Player".into()))?;
-        if let Some(idx) = p.hand.iter().position(|c| c == card_id) {
-            Ok(p.hand.remove(idx))
-        } else {
-            Err(GameError::InvalidAction("Card not in hand".into()))
+    /// Echoes: sets a card aside in `player_id`'s foreshadow zone.
+    pub fn foreshadow_card(&mut self, player_id: usize, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        let card_id = card_id.as_ref();
+        let p = self.players.get_mut(player_id).ok_or_else(|| GameError::InvalidAction("Invalid Player".into()))?;
+        p.foreshadow.push(card_id)?;
+        log_action!(self, "P{} préfigure '{}'", player_id, card_id);
+        self.hash_card(card_id, Zone::Foreshadow(player_id));
+        Ok(())
+    }
+    
+    /// Echoes: after a meld of age `age`, the lowest foreshadowed card of that age
+    /// or less is promoted onto the board. (Its dogma is not self-executed yet.)
+    fn promote_foreshadowed(&mut self, player_id: usize, age: u8) -> Result<(), GameError> {
+        let Some(p) = self.players.get_mut(player_id) else { return Ok(()) };
+        let promoted = p.foreshadow.names().enumerate()
+            .map(|(at, id)| (db::card(id).map_or(0, |c| c.age), id, at))
+            .filter(|(card_age, _, _)| *card_age <= age)
+            .min();
+        if let Some((_, card_id, at)) = promoted {
+            p.foreshadow.remove(at);
+            self.hash_card(card_id, Zone::Foreshadow(player_id));
+            log_action!(self, "P{} promeut '{}'", player_id, card_id);
+            self.place_on_board(player_id, card_id)?;
         }
+        Ok(())
+    }
+    
+    // Helper to remove from hand (since many effects do "Return a card from Hand")
+    pub fn remove_from_hand(&mut self, player_id: usize, card_id: &str) -> Result<&'static String, GameError> {
+        let p = self.players.get_mut(player_id).ok_or_else(|| GameError::InvalidAction("Invalid Player".into()))?;
+        let card = p.hand.remove_card(card_id).ok_or_else(|| GameError::InvalidAction("Card not in hand".into()))?;
+        self.hash_card(card, Zone::Hand(player_id));
+        Ok(card)
+    }
+    
+    /// Puts a card given by another zone into `player_id`'s hand.
+    pub fn add_to_hand(&mut self, player_id: usize, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        let card_id = card_id.as_ref();
+        let idx = db::card_index(card_id).ok_or_else(|| GameError::CardNotFound(card_id.to_string()))?;
+        self.add_index_to_hand(player_id, idx)
+    }
+    
+    fn add_index_to_hand(&mut self, player_id: usize, idx: CardIdx) -> Result<(), GameError> {
+        let p = self.players.get_mut(player_id).ok_or_else(|| GameError::InvalidAction("Invalid Player".into()))?;
+        p.hand.push_index(idx);
+        self.hash_index(idx, Zone::Hand(player_id));
+        Ok(())
+    }
+    
+    pub fn remove_from_score(&mut self, player_id: usize, card_id: &str) -> Result<&'static String, GameError> {
+        let p = self.players.get_mut(player_id).ok_or_else(|| GameError::InvalidAction("Invalid Player".into()))?;
+        let card = p.score_pile.remove_card(card_id).ok_or_else(|| GameError::InvalidAction("Card not in score pile".into()))?;
+        self.hash_card(card, Zone::Score(player_id));
+        Ok(card)
     }
     pub fn splay(&mut self, player_id: usize, color: crate::model::Color, direction: crate::model::SplayDirection) -> Result<(), GameError> {
-        if let Some(p) = self.players.get_mut(player_id) {
-            if let Some(pile) = p.board.get_mut(&color) {
-                if pile.cards.len() > 1 {
-                    pile.splay = direction;
-                }
+        let Some(pile) = self.players.get(player_id).and_then(|p| p.board.get(&color)) else { return Ok(()) };
+        if pile.len() > 1 && pile.splay() != direction {
+            self.zobrist ^= zobrist::splay_key(player_id, color, pile.splay()) ^ zobrist::splay_key(player_id, color, direction);
+            if let Some(pile) = self.players[player_id].board.get_mut(&color) {
+                pile.set_splay(direction);
             }
         }
         Ok(())
     }
 
     pub fn exchange_hand_score(&mut self, player_id: usize) -> Result<(), GameError> {
-        if let Some(p) = self.players.get_mut(player_id) {
+        if let Some(p) = self.players.get_mut(player_id).map(|p| &mut **p) {
             std::mem::swap(&mut p.hand, &mut p.score_pile);
+            for card_id in p.hand.iter().chain(&p.score_pile) {
+                self.zobrist ^= zobrist::card_key(card_id, Zone::Hand(player_id)) ^ zobrist::card_key(card_id, Zone::Score(player_id));
+            }
         }
         Ok(())
     }
@@ -473,6 +1062,7 @@ impl GameState {
         
         self.achievements.insert(ach_id.clone());
         if let Some(p) = self.players.get_mut(player_id) {
+            self.zobrist ^= zobrist::card_key(&ach_id, Zone::Achievements(player_id));
             p.achievements.push(ach_id);
         }
         self.check_victory();
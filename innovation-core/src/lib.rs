@@ -1,14 +1,28 @@
 pub mod model;
+pub mod config;
+pub mod rules;
 pub mod game_state;
 pub mod db;
 pub mod errors;
 pub mod player;
 pub mod actions;
 pub mod dogma;
+pub mod scenario;
+pub mod agent;
+pub mod protocol;
+pub mod shared;
+pub mod card_list;
+pub mod zobrist;
+pub mod solver;
+pub mod analysis;
 
 pub use model::{Card, Color, Icon, DogmaEffect};
 pub use game_state::GameState;
+pub use config::GameConfig;
+pub use rules::Ruleset;
 pub use db::load_all_cards;
+pub use scenario::ScenarioBuilder;
+pub use agent::{Agent, Observation};
 
 #[cfg(test)]
 mod tests;
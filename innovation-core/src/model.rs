@@ -18,6 +18,16 @@ pub enum Color {
     Purple,
 }
 
+impl Color {
+    /// Every color, in a fixed order (used wherever iteration must be deterministic).
+    pub const ALL: [Color; 5] = [Color::Red, Color::Blue, Color::Green, Color::Yellow, Color::Purple];
+
+    /// Position of this color in `Color::ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum Symbol {
@@ -29,11 +39,105 @@ pub enum Symbol {
     Clock,
 }
 
+impl Symbol {
+    pub const ALL: [Symbol; 6] = [Symbol::Crown, Symbol::Leaf, Symbol::Factory, Symbol::Lightbulb, Symbol::Castle, Symbol::Clock];
+
+    /// Position of this symbol in `Symbol::ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Visible resource icons, indexed by `Symbol::index`, and Echoes bonuses
+/// (kept as highest value and count, which is all the bonus rule needs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct IconTotals {
+    pub symbols: [u32; 6],
+    pub best_bonus: u8,
+    pub bonus_count: u8,
+    /// Echoes: visible echo effects.
+    pub echoes: u8,
+    /// Figures: visible inspire effects.
+    pub inspires: u8,
+    /// Cities: visible fountain and flag icons.
+    pub fountains: u8,
+    pub flags: u8,
+}
+
+impl IconTotals {
+    /// Totals of the given icons.
+    pub fn of(icons: &[Icon]) -> Self {
+        let mut totals = IconTotals::default();
+        for icon in icons {
+            match *icon {
+                Icon::Resource(s) => totals.symbols[s.index()] += 1,
+                Icon::Bonus { bonus } => {
+                    totals.best_bonus = totals.best_bonus.max(bonus);
+                    totals.bonus_count += 1;
+                }
+                Icon::Special(SpecialIcon::Echo) => totals.echoes += 1,
+                Icon::Special(SpecialIcon::Inspire) => totals.inspires += 1,
+                Icon::Special(SpecialIcon::Fountain) => totals.fountains += 1,
+                Icon::Special(SpecialIcon::Flag) => totals.flags += 1,
+                _ => {}
+            }
+        }
+        totals
+    }
+
+    pub fn get(&self, symbol: Symbol) -> u32 {
+        self.symbols[symbol.index()]
+    }
+
+    pub fn add(&mut self, other: &IconTotals) {
+        for (total, n) in self.symbols.iter_mut().zip(other.symbols) {
+            *total += n;
+        }
+        self.best_bonus = self.best_bonus.max(other.best_bonus);
+        self.bonus_count += other.bonus_count;
+        self.echoes += other.echoes;
+        self.inspires += other.inspires;
+        self.fountains += other.fountains;
+        self.flags += other.flags;
+    }
+
+    /// Echoes: the highest visible bonus, plus 1 for each other visible bonus.
+    pub fn bonus_points(&self) -> u32 {
+        match self.bonus_count {
+            0 => 0,
+            n => self.best_bonus as u32 + n as u32 - 1,
+        }
+    }
+}
+
+/// Expansion icons that are neither a resource nor the age hexagon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SpecialIcon {
+    /// Echoes: the slot printing the card's echo effect.
+    Echo,
+    /// Figures: the slot printing the card's inspire effect.
+    Inspire,
+    /// Cities: when melded, draw a card of value one higher.
+    Plus,
+    /// Cities: when melded, splay the city's color in that direction.
+    SplayLeft,
+    SplayRight,
+    SplayUp,
+    /// Cities: counts as an achievement while no opponent shows more cards of that color.
+    Flag,
+    /// Cities: counts as an achievement.
+    Fountain,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Icon {
     Age(u8),
     Resource(Symbol),
+    Special(SpecialIcon),
+    /// Echoes: bonus value, written `{"bonus": 3}` in card data.
+    Bonus { bonus: u8 },
 }
 
 impl fmt::Display for Icon {
@@ -41,20 +145,41 @@ impl fmt::Display for Icon {
         match self {
             Icon::Age(n) => write!(f, "{}", n),
             Icon::Resource(s) => write!(f, "{:?}", s),
+            Icon::Special(s) => write!(f, "{:?}", s),
+            Icon::Bonus { bonus } => write!(f, "Bonus {}", bonus),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub enum Expansion {
     #[default]
     Base,
+    /// Echoes of the Past. Partial: 50 of the 105 published cards, one per color and age.
     Echoes,
+    /// Figures in the Sand. Partial: 50 of the 105 published cards, one per color and age.
     Figures,
+    /// Cities of Destiny. Partial: 50 of the 105 published cards, one per color and age.
     Cities,
+    /// Artifacts of History. Partial: 50 of the 105 published cards, one per color and age.
     Artifacts,
 }
 
+impl Expansion {
+    /// Names accepted by `by_name`, for the expansions that can be added to a game.
+    pub const NAMES: [&'static str; 4] = ["echoes", "figures", "cities", "artifacts"];
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "echoes" => Some(Self::Echoes),
+            "figures" => Some(Self::Figures),
+            "cities" => Some(Self::Cities),
+            "artifacts" => Some(Self::Artifacts),
+            _ => None,
+        }
+    }
+}
+
 // --- Logic / Effect System ---
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -64,16 +189,30 @@ pub enum Location {
     Board, // Implies active/top card usually, or the stack
     Deck,
     Achievements,
+    /// Echoes: cards foreshadowed for a later turn.
+    Foreshadow,
+    /// Artifacts: the artifact on display (museum), waiting for the owner's next turn.
+    Display,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum SplayDirection {
+    #[default]
     None,
     Left,
     Right,
     Up,
 }
 
+impl SplayDirection {
+    pub const ALL: [SplayDirection; 4] = [SplayDirection::None, SplayDirection::Left, SplayDirection::Right, SplayDirection::Up];
+
+    /// Position of this direction in `SplayDirection::ALL`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Filter {
     Color(Color),
@@ -181,4 +320,35 @@ pub struct Card {
     pub dogmas: Vec<DogmaEffect>,
     #[serde(default)]
     pub expansion: Expansion,
+    /// Echoes: text of the echo effect, printed in the `SpecialIcon::Echo` slot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub echo: Option<String>,
+    /// Figures: texts of the card's karma effects, run by `dogma::karma`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub karma: Vec<String>,
+    /// Figures: text of the inspire effect, printed in the `SpecialIcon::Inspire` slot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inspire: Option<String>,
+}
+
+impl Card {
+    /// Icon positions left uncovered when this card lies under another one in a pile
+    /// splayed `splay`. Cities cards have six positions: the four base ones, then
+    /// top-center and top-right.
+    pub fn splay_positions(&self, splay: SplayDirection) -> &'static [usize] {
+        if self.icons.len() == 6 {
+            return match splay {
+                SplayDirection::None => &[],
+                SplayDirection::Left => &[3, 5],     // Right column (BR, TR) visible
+                SplayDirection::Right => &[0, 1],    // Left column (TL, BL) visible
+                SplayDirection::Up => &[1, 2, 3],    // Bottom row (BL, BC, BR) visible
+            };
+        }
+        match splay {
+            SplayDirection::None => &[],
+            SplayDirection::Left => &[2, 3],  // Right side (BR, TR) visible
+            SplayDirection::Right => &[0, 1], // Left side (TL, BL) visible
+            SplayDirection::Up => &[1, 2],    // Bottom side (BL, BR) visible
+        }
+    }
 }
@@ -1,108 +1,391 @@
-use crate::model::{Card, Color, Icon, Symbol, SplayDirection};
+use crate::card_list::CardList;
+use crate::db::{self, CardIdx};
+use crate::errors::GameError;
+use crate::shared::Shared;
+use crate::model::{Card, Color, Icon, IconTotals, Symbol, SpecialIcon, SplayDirection};
 use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// One color pile. Cards are held as interned indices, bottom to top, and the icons
+/// the pile shows are recounted on every change so that reading them costs nothing.
+/// Serializes with card names, like the rest of the state.
+///
+/// The fields are private so the totals cannot go stale: code that used the former
+/// `cards: Vec<String>` and `splay` fields reads them through `cards()`, which lists
+/// the cards like that `Vec` did, and `splay()`, and changes them with `push`, `tuck`,
+/// `pop` and `set_splay`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "PileRepr", try_from = "PileRepr")]
 pub struct Pile {
     pub color: Color,
-    pub cards: Vec<String>, // Card IDs
-    pub splay: SplayDirection,
+    cards: Shared<CardList>,
+    splay: SplayDirection,
+    icons: IconTotals,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PileRepr {
+    color: Color,
+    cards: Vec<String>, // Card IDs
+    splay: SplayDirection,
+}
+
+impl From<Pile> for PileRepr {
+    fn from(pile: Pile) -> Self {
+        PileRepr { color: pile.color, cards: pile.cards.to_vec(), splay: pile.splay }
+    }
+}
+
+impl TryFrom<PileRepr> for Pile {
+    type Error = GameError;
+
+    fn try_from(repr: PileRepr) -> Result<Self, GameError> {
+        let mut pile = Pile::new(repr.color);
+        pile.cards = CardList::from_names(&repr.cards)?.into();
+        pile.splay = repr.splay;
+        pile.recount();
+        Ok(pile)
+    }
 }
 
 impl Pile {
     pub fn new(color: Color) -> Self {
         Self {
             color,
-            cards: Vec::new(),
+            cards: Shared::default(),
             splay: SplayDirection::None,
+            icons: IconTotals::default(),
         }
     }
 
-    pub fn top(&self) -> Option<&String> {
+    fn recount(&mut self) {
+        let mut icons = IconTotals::default();
+        if let Some((&top, below)) = self.cards.indices().split_last() {
+            if self.splay != SplayDirection::None {
+                for &idx in below {
+                    icons.add(&db::card_stats(idx).splayed[self.splay.index()]);
+                }
+            }
+            icons.add(&db::card_stats(top).top);
+        }
+        self.icons = icons;
+    }
+
+    pub fn top(&self) -> Option<&'static String> {
         self.cards.last()
     }
+
+    pub fn top_index(&self) -> Option<CardIdx> {
+        self.cards.indices().last().copied()
+    }
     
-    pub fn pop(&mut self) -> Option<String> {
-        self.cards.pop()
+    pub fn pop(&mut self) -> Option<&'static String> {
+        let card_id = self.cards.pop()?;
+        self.recount();
+        Some(card_id)
     }
     
-    pub fn push(&mut self, card_id: String) {
-        self.cards.push(card_id);
+    /// Puts a card on top. Fails if the card is not in the card database.
+    pub fn push(&mut self, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        self.cards.push(card_id)?;
+        self.recount();
+        Ok(())
+    }
+
+    pub fn push_index(&mut self, idx: CardIdx) {
+        self.cards.push_index(idx);
+        self.recount();
+    }
+
+    /// Puts a card at the bottom. Fails if the card is not in the card database.
+    pub fn tuck(&mut self, card_id: impl AsRef<str>) -> Result<(), GameError> {
+        self.cards.insert(0, card_id)?;
+        self.recount();
+        Ok(())
+    }
+
+    pub fn tuck_index(&mut self, idx: CardIdx) {
+        self.cards.insert_index(0, idx);
+        self.recount();
+    }
+
+    /// Card IDs, bottom to top.
+    pub fn cards(&self) -> impl DoubleEndedIterator<Item = &String> + ExactSizeIterator + '_ {
+        self.cards.iter()
+    }
+
+    /// Interned cards, bottom to top.
+    pub fn indices(&self) -> &[CardIdx] {
+        self.cards.indices()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn contains(&self, card_id: &str) -> bool {
+        self.cards.contains(card_id)
+    }
+
+    pub fn splay(&self) -> SplayDirection {
+        self.splay
+    }
+
+    pub fn set_splay(&mut self, splay: SplayDirection) {
+        self.splay = splay;
+        self.recount();
+    }
+
+    /// Resource icons and Echoes bonuses this pile shows.
+    pub fn icons(&self) -> &IconTotals {
+        &self.icons
     }
     
-    pub fn visible_icons(&self, db: &HashMap<String, Card>) -> Vec<Icon> {
-        let mut icons = Vec::new();
-        if self.cards.is_empty() {
-            return icons;
-        }
-        
-        let len = self.cards.len();
-        // Top card always visible (all 4 icons)
-        if let Some(top_card) = self.cards.last().and_then(|id| db.get(id)) {
-            icons.extend(top_card.icons.iter().cloned());
+    /// Cards of this pile that can be seen: all of them once splayed, else just the top one.
+    pub fn visible_card_count(&self) -> usize {
+        match self.splay {
+            SplayDirection::None => self.cards.len().min(1),
+            _ => self.cards.len(),
         }
-        
-        // Cards below top
-        let indices = match self.splay {
-            SplayDirection::None => &[][..],
-            SplayDirection::Left => &[2, 3][..],  // Right side (BR, TR) visible
-            SplayDirection::Right => &[0, 1][..], // Left side (TL, BL) visible
-            SplayDirection::Up => &[1, 2][..],    // Bottom side (BL, BR) visible
+    }
+    
+    /// Every card showing at least one icon, bottom to top, with the icons it shows.
+    pub fn visible_card_icons<'a>(&'a self, db: &'a HashMap<String, Card>) -> Vec<(&'a Card, Vec<Icon>)> {
+        let mut visible = Vec::new();
+        let Some((top_id, below)) = self.indices().split_last() else {
+            return visible;
         };
+        let card_of = |idx: CardIdx| db.get(&db::card_at(idx).name);
         
-        if !indices.is_empty() && len > 1 {
-            for card_id in self.cards.iter().take(len - 1) {
-                if let Some(card) = db.get(card_id) {
-                    for &idx in indices {
-                        if idx < card.icons.len() {
-                            icons.push(card.icons[idx]);
-                        }
-                    }
-                }
+        // Cards below top
+        for card in below.iter().filter_map(|&idx| card_of(idx)) {
+            let icons: Vec<Icon> = card.splay_positions(self.splay).iter()
+                .filter_map(|&idx| card.icons.get(idx).copied())
+                .collect();
+            if !icons.is_empty() {
+                visible.push((card, icons));
             }
         }
         
-        icons
+        // Top card always visible (all its icons)
+        if let Some(top_card) = card_of(*top_id) {
+            visible.push((top_card, top_card.icons.clone()));
+        }
+        visible
+    }
+    
+    pub fn visible_icons(&self, db: &HashMap<String, Card>) -> Vec<Icon> {
+        self.visible_card_icons(db).into_iter().flat_map(|(_, icons)| icons).collect()
+    }
+    
+    /// Echoes: cards of this pile whose echo effect is visible, bottom to top
+    /// (the order they run in when the pile's top card is activated).
+    pub fn visible_echoes(&self, db: &HashMap<String, Card>) -> Vec<String> {
+        if self.icons.echoes == 0 {
+            return Vec::new();
+        }
+        self.visible_card_icons(db).into_iter()
+            .filter(|(card, icons)| card.echo.is_some() && icons.contains(&Icon::Special(SpecialIcon::Echo)))
+            .map(|(card, _)| card.name.clone())
+            .collect()
     }
+    
+    /// Figures: cards of this pile whose inspire effect is visible, bottom to top.
+    pub fn visible_inspires(&self, db: &HashMap<String, Card>) -> Vec<String> {
+        if self.icons.inspires == 0 {
+            return Vec::new();
+        }
+        self.visible_card_icons(db).into_iter()
+            .filter(|(card, icons)| card.inspire.is_some() && icons.contains(&Icon::Special(SpecialIcon::Inspire)))
+            .map(|(card, _)| card.name.clone())
+            .collect()
+    }
+}
+
+/// A player's piles, one slot per color (`Color::index`). Reads like a map from color
+/// to pile holding only the non-empty piles, and serializes as one. Converts from and
+/// into the `HashMap<Color, Pile>` that `Player::board` used to be.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Board {
+    piles: [Option<Pile>; 5],
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Board {
+    pub fn get(&self, color: &Color) -> Option<&Pile> {
+        self.piles[color.index()].as_ref()
+    }
+
+    pub fn get_mut(&mut self, color: &Color) -> Option<&mut Pile> {
+        self.piles[color.index()].as_mut()
+    }
+
+    pub fn contains_key(&self, color: &Color) -> bool {
+        self.piles[color.index()].is_some()
+    }
+
+    /// The pile of `color`, started empty if there is none.
+    pub fn pile_mut(&mut self, color: Color) -> &mut Pile {
+        self.piles[color.index()].get_or_insert_with(|| Pile::new(color))
+    }
+
+    pub fn insert(&mut self, color: Color, pile: Pile) -> Option<Pile> {
+        self.piles[color.index()].replace(pile)
+    }
+
+    pub fn remove(&mut self, color: &Color) -> Option<Pile> {
+        self.piles[color.index()].take()
+    }
+
+    /// Colors with a pile, in `Color::ALL` order.
+    pub fn keys(&self) -> impl Iterator<Item = &Color> + '_ {
+        self.values().map(|pile| &pile.color)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Pile> + '_ {
+        self.piles.iter().flatten()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Pile> + '_ {
+        self.piles.iter_mut().flatten()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Color, &Pile)> + '_ {
+        self.values().map(|pile| (&pile.color, pile))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.piles.iter().all(Option::is_none)
+    }
+
+    /// Resource icons and Echoes bonuses shown by the whole board.
+    pub fn icons(&self) -> IconTotals {
+        let mut totals = IconTotals::default();
+        for pile in self.values() {
+            totals.add(pile.icons());
+        }
+        totals
+    }
+
+    /// Visible `symbol` icons: `icons().get(symbol)` without totalling the other icons.
+    pub fn icon_count(&self, symbol: Symbol) -> u32 {
+        self.values().map(|pile| pile.icons().get(symbol)).sum()
+    }
+}
+
+impl<'a> IntoIterator for &'a Board {
+    type Item = (&'a Color, &'a Pile);
+    type IntoIter = std::iter::Map<std::iter::Flatten<std::slice::Iter<'a, Option<Pile>>>, fn(&Pile) -> (&Color, &Pile)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.piles.iter().flatten().map(|pile| (&pile.color, pile))
+    }
+}
+
+impl FromIterator<(Color, Pile)> for Board {
+    fn from_iter<I: IntoIterator<Item = (Color, Pile)>>(piles: I) -> Self {
+        let mut board = Board::default();
+        for (color, pile) in piles {
+            board.insert(color, pile);
+        }
+        board
+    }
+}
+
+impl From<HashMap<Color, Pile>> for Board {
+    fn from(piles: HashMap<Color, Pile>) -> Self {
+        piles.into_iter().collect()
+    }
+}
+
+impl From<Board> for HashMap<Color, Pile> {
+    fn from(board: Board) -> Self {
+        board.piles.into_iter().flatten().map(|pile| (pile.color, pile)).collect()
+    }
+}
+
+impl Serialize for Board {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Board {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        HashMap::<Color, Pile>::deserialize(deserializer).map(Board::from)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Player {
     pub id: usize,
-    pub hand: Vec<String>,
-    pub score_pile: Vec<String>,
-    pub board: HashMap<Color, Pile>,
+    pub hand: CardList,
+    pub score_pile: CardList,
+    pub board: Board,
     pub achievements: Vec<String>,
+    /// Echoes: foreshadowed cards, waiting to be promoted by a meld.
+    #[serde(default)]
+    pub foreshadow: CardList,
+    /// Artifacts: the artifact on display, if any.
+    #[serde(default)]
+    pub display: Option<String>,
 }
 
 impl Player {
     pub fn new(id: usize) -> Self {
         Self {
             id,
-            hand: Vec::new(),
-            score_pile: Vec::new(),
-            board: HashMap::new(),
+            hand: CardList::new(),
+            score_pile: CardList::new(),
+            board: Board::default(),
             achievements: Vec::new(),
+            foreshadow: CardList::new(),
+            display: None,
         }
     }
     
-    pub fn calculate_score(&self, cards_db: &HashMap<String, Card>) -> u32 {
-        self.score_pile.iter()
-            .filter_map(|id| cards_db.get(id))
-            .map(|c| c.age as u32)
-            .sum()
+    /// Score pile value plus bonus points.
+    pub fn calculate_score(&self) -> u32 {
+        self.score_pile.total_age() + self.bonus_points()
     }
     
-    pub fn icon_counts(&self, db: &HashMap<String, Card>) -> HashMap<Symbol, u32> {
-        let mut counts = HashMap::new();
-        for pile in self.board.values() {
-            for icon in pile.visible_icons(db) {
-                if let Icon::Resource(s) = icon {
-                    *counts.entry(s).or_insert(0) += 1;
-                }
-            }
+    /// Echoes: the highest visible bonus, plus 1 for each other visible bonus.
+    pub fn bonus_points(&self) -> u32 {
+        let mut bonuses = IconTotals::default();
+        for icons in self.board.values().map(Pile::icons) {
+            bonuses.best_bonus = bonuses.best_bonus.max(icons.best_bonus);
+            bonuses.bonus_count += icons.bonus_count;
         }
-        counts
+        bonuses.bonus_points()
+    }
+
+    /// Age of this player's highest top card, 0 with an empty board.
+    pub fn max_top_age(&self) -> u8 {
+        self.board.values()
+            .filter_map(|pile| pile.top_index())
+            .map(db::card_age)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Resource icons and bonuses shown by this player's board.
+    pub fn icon_totals(&self) -> IconTotals {
+        self.board.icons()
+    }
+
+    pub fn icon_counts(&self) -> HashMap<Symbol, u32> {
+        let totals = self.icon_totals();
+        Symbol::ALL.iter()
+            .filter(|&&s| totals.get(s) > 0)
+            .map(|&s| (s, totals.get(s)))
+            .collect()
     }
 }
@@ -0,0 +1,98 @@
+//! Line-based JSON protocol between the engine and external bots.
+//!
+//! The engine hosts the game and talks to each bot over its stdin/stdout, one JSON
+//! object per line. After a `hello`/`ready` handshake, every decision of the bot's
+//! seat is a `request` answered by an `action` with the same `id`. An illegal action
+//! is answered by an `error` and the request is sent again; a seat that times out,
+//! exits or keeps playing illegal moves forfeits. See `docs/engine_protocol.md`.
+
+use crate::actions::Action;
+use crate::agent::{candidate_moves, Observation};
+use crate::config::GameConfig;
+use crate::dogma::flow::InputRequest;
+use crate::game_state::{EndCondition, GameState};
+use serde::{Deserialize, Serialize};
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Most input answers listed in a request's `legal` moves.
+pub const MAX_LISTED_RESPONSES: usize = 256;
+
+/// Messages sent by the engine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EngineMessage {
+    Hello {
+        protocol: u32,
+        seat: usize,
+        config: GameConfig,
+    },
+    /// The bot's seat must act: play an action, or answer `input` when it is set.
+    Request {
+        id: u64,
+        seat: usize,
+        /// The game as the seat sees it; hidden cards read `?<age>`.
+        view: Box<GameState>,
+        input: Option<InputRequest>,
+        /// Every legal move (input answers may be truncated for huge selections).
+        legal: Vec<Action>,
+    },
+    /// The action sent for request `id` was refused; the request follows again.
+    Error {
+        id: u64,
+        message: String,
+    },
+    GameOver {
+        winners: Vec<usize>,
+        end: Option<EndCondition>,
+        /// Seat that forfeited, if the game was stopped that way.
+        forfeit: Option<usize>,
+    },
+    Quit,
+}
+
+/// Messages sent by a bot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotMessage {
+    Ready {
+        name: String,
+    },
+    Action {
+        id: u64,
+        action: Action,
+    },
+}
+
+impl EngineMessage {
+    pub fn hello(game: &GameState, seat: usize) -> Self {
+        EngineMessage::Hello { protocol: PROTOCOL_VERSION, seat, config: game.config.clone() }
+    }
+
+    /// Decision request for `seat`, with its redacted view and legal moves.
+    pub fn request(id: u64, game: &GameState, seat: usize) -> Self {
+        let view = Observation::new(game, seat).redacted();
+        EngineMessage::Request {
+            id,
+            seat,
+            input: view.pending_input.clone(),
+            view: Box::new(view),
+            legal: candidate_moves(game, MAX_LISTED_RESPONSES),
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        serde_json::to_string(self).expect("engine messages serialize")
+    }
+}
+
+impl BotMessage {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        serde_json::from_str(line.trim()).map_err(|e| format!("malformed message: {}", e))
+    }
+}
+
+/// Checks `action` against a copy of `game`, returning the engine's refusal if any.
+pub fn validate(game: &GameState, action: &Action) -> Result<(), String> {
+    game.clone().apply_action(action.clone()).map_err(|e| e.to_string())
+}
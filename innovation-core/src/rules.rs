@@ -0,0 +1,91 @@
+//! Rule numbers that differ between editions of the game, gathered in one
+//! data struct so a new edition is a new constructor rather than a fork of
+//! `game_state.rs`. The ruleset is chosen through `GameConfig::ruleset`.
+
+use crate::errors::GameError;
+use serde::{Deserialize, Serialize};
+
+/// What the activator gets when an opponent shares a cooperative dogma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SharingBonus {
+    None,
+    /// Draw a card of the given age (climbing to the next age if empty).
+    DrawAge(u8),
+    /// A regular draw, of the activator's highest top card.
+    Draw,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ruleset {
+    pub name: String,
+    /// Score needed per age to claim a standard achievement (5 → Age 3 costs 15).
+    pub achievement_cost_per_age: u32,
+    /// Claiming Age N also needs a top card of age N or more.
+    pub achievement_needs_top_card: bool,
+    pub actions_per_turn: u8,
+    /// How many players, starting with the first one, get a single action on
+    /// turn 1, indexed by player count 2, 3 and 4.
+    pub first_turn_single_action_seats: [usize; 3],
+    pub sharing_bonus: SharingBonus,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl Ruleset {
+    /// Names accepted by [`Ruleset::by_name`].
+    pub const NAMES: [&'static str; 2] = ["standard", "third-edition"];
+
+    /// The rules this engine has always played: the sharing bonus is an age 1 draw.
+    pub fn standard() -> Self {
+        Self {
+            name: "standard".to_string(),
+            achievement_cost_per_age: 5,
+            achievement_needs_top_card: true,
+            actions_per_turn: 2,
+            first_turn_single_action_seats: [1, 1, 2],
+            sharing_bonus: SharingBonus::DrawAge(1),
+        }
+    }
+
+    /// Third edition rulebook: the sharing bonus is a regular draw.
+    pub fn third_edition() -> Self {
+        Self {
+            name: "third-edition".to_string(),
+            sharing_bonus: SharingBonus::Draw,
+            ..Self::standard()
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "standard" => Some(Self::standard()),
+            "third-edition" => Some(Self::third_edition()),
+            _ => None,
+        }
+    }
+
+    /// Actions allowed on turn 1 for the player `position` seats after the first player.
+    pub fn first_turn_actions(&self, player_count: usize, position: usize) -> u8 {
+        let limited = self.first_turn_single_action_seats[player_count.clamp(2, 4) - 2];
+        if position < limited { 1 } else { self.actions_per_turn }
+    }
+
+    pub fn validate(&self) -> Result<(), GameError> {
+        if self.actions_per_turn == 0 {
+            return Err(GameError::InvalidConfig(format!("ruleset '{}' allows no action per turn", self.name)));
+        }
+        if self.first_turn_single_action_seats.iter().zip(2..).any(|(seats, players)| *seats > players) {
+            return Err(GameError::InvalidConfig(format!("ruleset '{}' limits more seats than there are players", self.name)));
+        }
+        if let SharingBonus::DrawAge(age) = self.sharing_bonus {
+            if !(1..=10).contains(&age) {
+                return Err(GameError::InvalidConfig(format!("ruleset '{}' has a sharing bonus of age {}", self.name, age)));
+            }
+        }
+        Ok(())
+    }
+}
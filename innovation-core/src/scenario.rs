@@ -0,0 +1,282 @@
+//! Declarative construction of game positions, for card-effect tests and puzzles.
+//!
+//! A scenario lists what each seat holds (hand, piles with splays, score pile,
+//! achievements), which cards sit on top of the draw piles, whose turn it is and
+//! the turn number. Every card not mentioned stays in its (seeded, shuffled) deck.
+//! Expansion cards can be used once their expansion is listed in `expansions`.
+//!
+//! Scenarios can be built in code with [`ScenarioBuilder`] or loaded from JSON:
+//!
+//! ```json
+//! {
+//!   "players": 2,
+//!   "turn": 2,
+//!   "seats": [
+//!     { "hand": ["Archerie"], "board": [{ "cards": ["Rames", "Cités-États"], "splay": "Left" }] },
+//!     { "score": ["Voiles"], "achievements": ["Age 1"] }
+//!   ],
+//!   "decks": { "1": ["Agriculture"] }
+//! }
+//! ```
+
+use crate::config::GameConfig;
+use crate::card_list::Decks;
+use crate::db::{self, CardIdx};
+use crate::errors::GameError;
+use crate::game_state::GameState;
+use crate::model::{Expansion, SplayDirection};
+use crate::player::Pile;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One color pile, listed bottom to top.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PileSetup {
+    pub cards: Vec<String>,
+    #[serde(default)]
+    pub splay: SplayDirection,
+}
+
+/// Everything one player holds at the start of the scenario.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeatSetup {
+    pub hand: Vec<String>,
+    pub board: Vec<PileSetup>,
+    pub score: Vec<String>,
+    pub achievements: Vec<String>,
+    /// Echoes: foreshadowed cards.
+    pub foreshadow: Vec<String>,
+    /// Artifacts: the artifact on display.
+    pub display: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioBuilder {
+    pub players: usize,
+    pub seed: u64,
+    pub max_age: u8,
+    pub current_player: usize,
+    pub turn: u32,
+    /// Four-player team game (see `GameConfig::team_play`).
+    pub team_play: bool,
+    /// Expansions in play, whose cards and rules join the base game.
+    pub expansions: Vec<Expansion>,
+    pub seats: Vec<SeatSetup>,
+    /// Cards to put on top of each draw pile, in the order they will be drawn.
+    pub decks: BTreeMap<u8, Vec<String>>,
+}
+
+impl Default for ScenarioBuilder {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl ScenarioBuilder {
+    pub fn new(players: usize) -> Self {
+        Self {
+            players,
+            seed: 0,
+            max_age: 10,
+            current_player: 0,
+            turn: 1,
+            team_play: false,
+            expansions: Vec::new(),
+            seats: vec![SeatSetup::default(); players],
+            decks: BTreeMap::new(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, GameError> {
+        serde_json::from_str(json).map_err(|e| GameError::InvalidScenario(e.to_string()))
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn max_age(mut self, max_age: u8) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn current_player(mut self, player: usize) -> Self {
+        self.current_player = player;
+        self
+    }
+
+    pub fn turn(mut self, turn: u32) -> Self {
+        self.turn = turn;
+        self
+    }
+
+    pub fn team_play(mut self) -> Self {
+        self.team_play = true;
+        self
+    }
+
+    pub fn expansion(mut self, expansion: Expansion) -> Self {
+        self.expansions.push(expansion);
+        self
+    }
+
+    pub fn hand(mut self, player: usize, cards: &[&str]) -> Self {
+        self.seat(player).hand.extend(cards.iter().map(|c| c.to_string()));
+        self
+    }
+
+    /// Adds a pile to `player`'s board; `cards` go bottom to top.
+    pub fn pile(mut self, player: usize, cards: &[&str], splay: SplayDirection) -> Self {
+        let cards = cards.iter().map(|c| c.to_string()).collect();
+        self.seat(player).board.push(PileSetup { cards, splay });
+        self
+    }
+
+    pub fn score(mut self, player: usize, cards: &[&str]) -> Self {
+        self.seat(player).score.extend(cards.iter().map(|c| c.to_string()));
+        self
+    }
+
+    pub fn achievement(mut self, player: usize, achievement: &str) -> Self {
+        self.seat(player).achievements.push(achievement.to_string());
+        self
+    }
+
+    pub fn foreshadow(mut self, player: usize, cards: &[&str]) -> Self {
+        self.seat(player).foreshadow.extend(cards.iter().map(|c| c.to_string()));
+        self
+    }
+
+    pub fn display(mut self, player: usize, card: &str) -> Self {
+        self.seat(player).display = Some(card.to_string());
+        self
+    }
+
+    /// Stacks `cards` on top of the `age` draw pile; the first one is drawn first.
+    pub fn deck_top(mut self, age: u8, cards: &[&str]) -> Self {
+        self.decks.entry(age).or_default().extend(cards.iter().map(|c| c.to_string()));
+        self
+    }
+
+    fn seat(&mut self, player: usize) -> &mut SeatSetup {
+        if self.seats.len() <= player {
+            self.seats.resize(player + 1, SeatSetup::default());
+        }
+        &mut self.seats[player]
+    }
+
+    /// Produces the `GameState` in Main phase. Fails if a card is unknown, listed twice,
+    /// above `max_age`, or if a seat or pile is inconsistent.
+    pub fn build(&self) -> Result<GameState, GameError> {
+        if self.seats.len() > self.players {
+            return Err(GameError::InvalidScenario(format!("{} seats declared for {} players", self.seats.len(), self.players)));
+        }
+        if self.current_player >= self.players {
+            return Err(GameError::InvalidScenario(format!("current player {} does not exist", self.current_player)));
+        }
+
+        let mut config = GameConfig::new(self.players)?.max_age(self.max_age).team_play(self.team_play).skip_setup(true);
+        for &expansion in &self.expansions {
+            config = config.expansion(expansion);
+        }
+        let mut state = GameState::from_config(self.seed, config)?;
+
+        // Put the dealt hands back, restoring the seeded deck order
+        for pid in (0..state.players.len()).rev() {
+            while let Some(idx) = state.players[pid].hand.pop_index() {
+                let card = db::card_at(idx);
+                if let Some(decks) = decks_of(&mut state, card.expansion) {
+                    decks.pile_mut(card.age).push_index(idx);
+                }
+            }
+        }
+
+        let take = |state: &mut GameState, card: &str| -> Result<CardIdx, GameError> {
+            let info = db::card(card).ok_or_else(|| GameError::CardNotFound(card.to_string()))?;
+            let pile = decks_of(state, info.expansion).and_then(|decks| decks.get_mut(&info.age));
+            let at = pile.as_ref().and_then(|p| p.position(card)).ok_or_else(|| {
+                GameError::InvalidScenario(format!("'{}' is used twice or above the max age", card))
+            })?;
+            Ok(pile.map(|p| p.remove_index(at)).unwrap_or_default())
+        };
+
+        for (pid, seat) in self.seats.iter().enumerate() {
+            for card in &seat.hand {
+                let card = take(&mut state, card)?;
+                state.players[pid].hand.push_index(card);
+            }
+            for card in &seat.score {
+                let card = take(&mut state, card)?;
+                state.players[pid].score_pile.push_index(card);
+            }
+            for card in &seat.foreshadow {
+                let card = take(&mut state, card)?;
+                state.players[pid].foreshadow.push_index(card);
+            }
+            if let Some(card) = &seat.display {
+                state.players[pid].display = Some(db::card_at(take(&mut state, card)?).name.clone());
+            }
+            for setup in &seat.board {
+                let color = match setup.cards.first().and_then(|c| db::card(c)) {
+                    Some(card) => card.color,
+                    None => return Err(GameError::InvalidScenario(format!("empty or unknown pile for P{}", pid))),
+                };
+                if state.players[pid].board.contains_key(&color) {
+                    return Err(GameError::InvalidScenario(format!("P{} has two {:?} piles", pid, color)));
+                }
+                if setup.splay != SplayDirection::None && setup.cards.len() < 2 {
+                    return Err(GameError::InvalidScenario(format!("P{} {:?} pile is too small to splay", pid, color)));
+                }
+                let mut pile = Pile::new(color);
+                pile.set_splay(setup.splay);
+                for card in &setup.cards {
+                    if db::card(card).map(|c| c.color) != Some(color) {
+                        return Err(GameError::InvalidScenario(format!("'{}' does not belong in the {:?} pile", card, color)));
+                    }
+                    pile.push_index(take(&mut state, card)?);
+                }
+                state.players[pid].board.insert(color, pile);
+            }
+            for achievement in &seat.achievements {
+                if !state.achievements.insert(achievement.clone()) {
+                    return Err(GameError::InvalidScenario(format!("achievement '{}' claimed twice", achievement)));
+                }
+                state.players[pid].achievements.push(achievement.clone());
+            }
+        }
+
+        for (age, cards) in &self.decks {
+            // One stack per age: its cards all come from the same draw pile
+            let expansion = cards.first().and_then(|c| db::card(c)).map(|c| c.expansion).unwrap_or_default();
+            let mut stacked = Vec::with_capacity(cards.len());
+            for card in cards {
+                if db::card(card).map(|c| (c.age, c.expansion)) != Some((*age, expansion)) {
+                    return Err(GameError::InvalidScenario(format!("'{}' is not an age {} {:?} card", card, age, expansion)));
+                }
+                stacked.push(take(&mut state, card)?);
+            }
+            // Draws pop from the end of the pile
+            if let Some(pile) = decks_of(&mut state, expansion).and_then(|decks| decks.get_mut(age)) {
+                pile.extend(stacked.into_iter().rev());
+            }
+        }
+
+        state.current_player = self.current_player;
+        state.turn_number = self.turn;
+        state.action_log.clear();
+        state.rehash();
+        state.start_turn();
+        Ok(state)
+    }
+}
+
+/// The draw piles `expansion`'s cards come from: the base decks or the expansion's own.
+fn decks_of(state: &mut GameState, expansion: Expansion) -> Option<&mut Decks> {
+    match expansion {
+        Expansion::Base => Some(&mut state.deck),
+        _ => state.expansion_decks.get_mut(&expansion),
+    }
+}
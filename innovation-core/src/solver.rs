@@ -0,0 +1,387 @@
+//! Exact search of small positions.
+//!
+//! The solver reads the whole `GameState` (every hand and score pile) but not the
+//! order of the draw piles: each draw is a chance node over the cards the pile may
+//! hold on top, weighted by their probability. Decisions are searched with
+//! alpha-beta from one seat's side, its teammates maximizing and everyone else
+//! minimizing the chance that this side wins. A value of 1 is a forced win.
+//!
+//! Lines still going at the search horizon count as not won, so a value is a lower
+//! bound on the real chance unless it is proven: no line it depends on was cut off.
+//! A proven 0 is a lost position; an unproven one only means no win was found.
+//!
+//! Every `apply_action` counts as one action, input answers included. Positions
+//! are stored in a transposition table under their Zobrist hash and the actions
+//! left; the hash leaves out the turn number (it only matters on turn 1) and the
+//! draw pile order. Only proven values are stored as exact; unproven ones are kept
+//! as lower bounds, which they are whatever the horizon.
+
+use crate::card_list::CardList;
+use crate::actions::Action;
+use crate::agent::candidate_moves;
+use crate::db::CardIdx;
+use crate::errors::GameError;
+use crate::game_state::{GamePhase, GameState};
+use crate::model::Expansion;
+use std::collections::HashMap;
+
+/// Values this close to 1 are forced wins (chance nodes add up probabilities).
+const CERTAIN: f64 = 1.0 - 1e-9;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolverConfig {
+    /// Most actions searched ahead.
+    pub depth: usize,
+    /// Positions searched before giving up on deeper searches.
+    pub max_nodes: u64,
+    /// Most answers considered for one input request.
+    pub max_responses: usize,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self { depth: 6, max_nodes: 2_000_000, max_responses: 32 }
+    }
+}
+
+/// Value of one root move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveValue {
+    pub action: Action,
+    pub value: f64,
+    /// `value` is exact rather than a lower bound.
+    pub proven: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// Seat whose side the values are given for: the one acting at the root.
+    pub seat: usize,
+    /// Chance that `seat`'s side wins when everyone plays their best, counting the
+    /// lines cut off at `depth` actions as not won: 1 is a forced win.
+    pub value: f64,
+    /// `value` is exact: the search saw every line it depends on to the end of the
+    /// game. Otherwise it is a lower bound.
+    pub proven: bool,
+    /// Deepest search completed. It stops at the first depth with a forced win.
+    pub depth: usize,
+    /// Root moves by decreasing value.
+    pub moves: Vec<MoveValue>,
+    /// Best moves from the root as the draw piles are actually stacked, until the
+    /// game ends or the search horizon.
+    pub best_line: Vec<Action>,
+    /// Positions searched, over all depths.
+    pub nodes: u64,
+    /// The node budget ran out before `SolverConfig::depth` was reached.
+    pub truncated: bool,
+}
+
+impl Solution {
+    pub fn forced_win(&self) -> bool {
+        self.value >= CERTAIN
+    }
+
+    /// No line wins, whatever `seat`'s side plays.
+    pub fn lost(&self) -> bool {
+        self.proven && self.value <= 1.0 - CERTAIN
+    }
+}
+
+/// Finds the best moves of the acting seat in `state`, deepening one action at a
+/// time up to `config.depth`.
+pub fn solve(state: &GameState, config: &SolverConfig) -> Solution {
+    let mut solver = Solver {
+        config: config.clone(),
+        seat: state.acting_player(),
+        table: HashMap::new(),
+        nodes: 0,
+        aborted: false,
+    };
+    let mut solution = Solution {
+        seat: solver.seat,
+        value: 0.0,
+        proven: false,
+        depth: 0,
+        moves: Vec::new(),
+        best_line: Vec::new(),
+        nodes: 0,
+        truncated: false,
+    };
+    for depth in 1..=config.depth {
+        let moves = solver.root(state, depth);
+        if solver.aborted {
+            solution.truncated = true;
+            break;
+        }
+        solution.value = moves.first().map_or(0.0, |m| m.value);
+        // An unproven move might be worth more than it shows, unless the best one wins
+        solution.proven = moves.iter().all(|m| m.proven) || solution.value >= CERTAIN;
+        solution.depth = depth;
+        solution.best_line = solver.best_line(state, &moves, depth);
+        solution.moves = moves;
+        if solution.forced_win() || solution.proven || state.phase == GamePhase::GameOver {
+            break;
+        }
+    }
+    solution.nodes = solver.nodes;
+    solution
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Bound {
+    Exact,
+    /// The value is at least the stored one.
+    Lower,
+    /// The value is at most the stored one.
+    Upper,
+}
+
+struct Entry {
+    value: f64,
+    bound: Bound,
+    best: Option<Action>,
+}
+
+/// A searched value, and whether it is proven: every line it depends on reached
+/// the end of the game.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Score {
+    value: f64,
+    proven: bool,
+}
+
+impl Score {
+    const HORIZON: Score = Score { value: 0.0, proven: false };
+
+    fn proven(value: f64) -> Self {
+        Score { value, proven: true }
+    }
+}
+
+struct Solver {
+    config: SolverConfig,
+    seat: usize,
+    /// By Zobrist hash and actions left.
+    table: HashMap<(u64, usize), Entry>,
+    nodes: u64,
+    aborted: bool,
+}
+
+impl Solver {
+    /// Exact value of every root move.
+    fn root(&mut self, state: &GameState, depth: usize) -> Vec<MoveValue> {
+        let mut moves: Vec<MoveValue> = candidate_moves(state, self.config.max_responses).into_iter()
+            .filter_map(|action| {
+                let score = self.move_value(state, &action, depth, 0.0, 1.0)?;
+                Some(MoveValue { action, value: score.value, proven: score.proven })
+            })
+            .collect();
+        // Stable, so equal moves keep the engine's order
+        moves.sort_by(|a, b| b.value.total_cmp(&a.value));
+        moves
+    }
+
+    fn best_line(&self, state: &GameState, moves: &[MoveValue], depth: usize) -> Vec<Action> {
+        let mut line = Vec::new();
+        let mut state = state.clone();
+        let mut next = moves.first().map(|m| m.action.clone());
+        for remaining in (1..depth).rev() {
+            let Some(action) = next.take() else { break };
+            if state.apply_action(action.clone()).is_err() {
+                break;
+            }
+            line.push(action);
+            if state.phase == GamePhase::GameOver {
+                return line;
+            }
+            next = self.table.get(&(state.zobrist(), remaining)).and_then(|e| e.best.clone());
+        }
+        line.extend(next);
+        line
+    }
+
+    /// 1 if the game ended with a win for `seat`'s side, else 0.
+    fn result(&self, state: &GameState) -> f64 {
+        let won = state.winners.iter().any(|&w| state.config.teammates(w, self.seat));
+        if won { 1.0 } else { 0.0 }
+    }
+
+    /// Value of `state` with `depth` actions left, exact when it lies strictly
+    /// between `alpha` and `beta`, else a bound on the same side.
+    fn search(&mut self, state: &GameState, depth: usize, mut alpha: f64, mut beta: f64) -> Score {
+        self.nodes += 1;
+        if state.phase == GamePhase::GameOver {
+            return Score::proven(self.result(state));
+        }
+        if depth == 0 || self.aborted {
+            return Score::HORIZON;
+        }
+        if self.nodes > self.config.max_nodes {
+            self.aborted = true;
+            return Score::HORIZON;
+        }
+
+        let key = state.zobrist();
+        if let Some(entry) = self.table.get(&(key, depth)) {
+            // Table bounds hold for the real value, so they are proven
+            match entry.bound {
+                Bound::Exact => return Score::proven(entry.value),
+                Bound::Lower => alpha = alpha.max(entry.value),
+                Bound::Upper => beta = beta.min(entry.value),
+            }
+            if alpha >= beta {
+                return Score::proven(entry.value);
+            }
+        }
+        // Best move here, from this search or the previous, shallower one
+        let first = [depth, depth - 1].iter()
+            .find_map(|&d| self.table.get(&(key, d)).and_then(|e| e.best.clone()));
+
+        let mut moves = candidate_moves(state, self.config.max_responses);
+        if let Some(idx) = first.and_then(|best| moves.iter().position(|m| *m == best)) {
+            moves[..=idx].rotate_right(1);
+        }
+        let maximizing = state.config.teammates(state.acting_player(), self.seat);
+        let (alpha_in, beta_in) = (alpha, beta);
+        let mut best: Option<(f64, Action)> = None;
+        let mut proven = true;
+        for action in moves {
+            let Some(score) = self.move_value(state, &action, depth, alpha, beta) else { continue };
+            let value = score.value;
+            proven &= score.proven;
+            let better = best.as_ref().is_none_or(|(b, _)| if maximizing { value > *b } else { value < *b });
+            if better {
+                best = Some((value, action));
+            }
+            if maximizing {
+                alpha = alpha.max(value);
+            } else {
+                beta = beta.min(value);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        let Some((value, action)) = best else { return Score::proven(0.0) };
+        if !self.aborted {
+            let bound = if value <= alpha_in {
+                Bound::Upper
+            } else if value >= beta_in {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+            // Lines cut off at the horizon only lower an unproven value
+            let bound = match (proven, bound) {
+                (true, bound) => Some(bound),
+                (false, Bound::Upper) => None,
+                (false, _) => Some(Bound::Lower),
+            };
+            if let Some(bound) = bound {
+                self.table.insert((key, depth), Entry { value, bound, best: Some(action) });
+            }
+        }
+        Score { value, proven }
+    }
+
+    /// Value of playing `action`, averaged over the cards it may draw; None if it
+    /// is not legal. Stops averaging once the result cannot reach the window.
+    fn move_value(&mut self, state: &GameState, action: &Action, depth: usize, alpha: f64, beta: f64) -> Option<Score> {
+        let outcomes = outcomes(state, action).ok()?;
+        if let [(_, next)] = outcomes.as_slice() {
+            return Some(self.search(next, depth - 1, alpha, beta));
+        }
+        let (mut sum, mut rest, mut proven) = (0.0, 1.0, true);
+        for (probability, next) in &outcomes {
+            let score = self.search(next, depth - 1, 0.0, 1.0);
+            sum += probability * score.value;
+            proven &= score.proven;
+            rest = (rest - probability).max(0.0);
+            if sum >= beta {
+                return Some(Score { value: sum, proven });
+            }
+            if sum + rest <= alpha {
+                return Some(Score { value: sum + rest, proven });
+            }
+        }
+        Some(Score { value: sum, proven })
+    }
+}
+
+/// A draw pile: `Expansion::Base` for the main deck.
+type PileKey = (Expansion, u8);
+
+/// Cards fixed on top of draw piles, in drawing order.
+type Tops = Vec<(PileKey, Vec<CardIdx>)>;
+
+fn pile_keys(state: &GameState) -> Vec<PileKey> {
+    let mut keys: Vec<PileKey> = state.deck.keys().map(|&age| (Expansion::Base, age))
+        .chain(state.expansion_decks.iter().flat_map(|(&e, decks)| decks.keys().map(move |&age| (e, age))))
+        .collect();
+    keys.sort_by_key(|(expansion, age)| (format!("{:?}", expansion), *age));
+    keys
+}
+
+fn pile(state: &GameState, (expansion, age): PileKey) -> &[CardIdx] {
+    let pile = match expansion {
+        Expansion::Base => state.deck.get(&age),
+        e => state.expansion_decks.get(&e).and_then(|decks| decks.get(&age)),
+    };
+    pile.map_or(&[], |p| p.indices())
+}
+
+fn pile_mut(state: &mut GameState, (expansion, age): PileKey) -> Option<&mut CardList> {
+    let pile = match expansion {
+        Expansion::Base => state.deck.get_mut(&age),
+        e => state.expansion_decks.get_mut(&e).and_then(|decks| decks.get_mut(&age)),
+    };
+    pile.map(|p| &mut **p)
+}
+
+/// Cards taken from the top of `before` to leave `after`; cards returned to the
+/// bottom meanwhile are not counted.
+fn drawn(before: &[CardIdx], after: &[CardIdx]) -> usize {
+    let kept = (0..=before.len().min(after.len())).rev()
+        .find(|&m| after.ends_with(&before[..m]))
+        .unwrap_or(0);
+    before.len() - kept
+}
+
+/// Every way `action` can turn out, with its probability, over the orders of the
+/// draw piles. The action is replayed with more and more pile tops fixed, until
+/// all the cards it draws are fixed ones.
+pub fn outcomes(state: &GameState, action: &Action) -> Result<Vec<(f64, GameState)>, GameError> {
+    let keys = pile_keys(state);
+    let mut out = Vec::new();
+    let mut pending: Vec<(f64, Tops)> = vec![(1.0, Vec::new())];
+    while let Some((probability, fixed)) = pending.pop() {
+        let mut arranged = state.clone();
+        for (key, cards) in &fixed {
+            if let Some(pile) = pile_mut(&mut arranged, *key) {
+                let rest = pile.indices().iter().copied().filter(|c| !cards.contains(c));
+                *pile = rest.chain(cards.iter().rev().copied()).collect();
+            }
+        }
+        let mut next = arranged.clone();
+        next.apply_action(action.clone())?;
+
+        let fixed_count = |key: PileKey| fixed.iter().find(|(k, _)| *k == key).map_or(0, |(_, cards)| cards.len());
+        let unfixed = keys.iter().copied()
+            .find(|&key| drawn(pile(&arranged, key), pile(&next, key)) > fixed_count(key));
+        let Some(key) = unfixed else {
+            out.push((probability, next));
+            continue;
+        };
+        let taken: &[CardIdx] = fixed.iter().find(|(k, _)| *k == key).map_or(&[], |(_, cards)| cards);
+        let candidates: Vec<CardIdx> = pile(state, key).iter().copied().filter(|c| !taken.contains(c)).collect();
+        for card in &candidates {
+            let mut fixed = fixed.clone();
+            match fixed.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, cards)) => cards.push(*card),
+                None => fixed.push((key, vec![*card])),
+            }
+            pending.push((probability / candidates.len() as f64, fixed));
+        }
+    }
+    Ok(out)
+}
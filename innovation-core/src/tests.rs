@@ -1,9 +1,21 @@
 use crate::game_state::GameState;
 use crate::actions::Action;
+use crate::model::SplayDirection;
+use crate::scenario::ScenarioBuilder;
+use crate::config::{GameConfig, HouseRule};
+use crate::errors::GameError;
+use crate::game_state::GamePhase;
+
+/// Fresh game that skips the initial melds and starts in Main phase.
+fn main_phase_game(seed: u64, players: usize) -> GameState {
+    let config = GameConfig::new(players).unwrap().skip_setup(true);
+    GameState::from_config(seed, config).unwrap()
+}
 
 #[test]
 fn test_initial_state() {
-    let mut game = GameState::new(12345, 2);
+    let game = GameState::new(12345, 2);
+    assert_eq!(game.phase, GamePhase::Setup);
     assert_eq!(game.players.len(), 2);
     assert_eq!(game.current_player, 0);
     assert_eq!(game.turn_number, 1);
@@ -15,7 +27,7 @@ fn test_initial_state() {
 
 #[test]
 fn test_draw() {
-    let mut game = GameState::new(12345, 2);
+    let mut game = main_phase_game(12345, 2);
     let p0_hand_size = game.players[0].hand.len();
     
     game.apply_action(Action::Draw).unwrap();
@@ -25,7 +37,7 @@ fn test_draw() {
 
 #[test]
 fn test_meld() {
-    let mut game = GameState::new(12345, 2);
+    let mut game = main_phase_game(12345, 2);
     
     // Force a specific card into hand for testing or just use one from initial hand
     let card_to_meld = game.players[0].hand[0].clone();
@@ -37,23 +49,25 @@ fn test_meld() {
     
     // Check it's on board
     let p = &game.players[0];
-    let found_on_board = p.board.values().any(|pile| pile.cards.contains(&card_to_meld));
+    let found_on_board = p.board.values().any(|pile| pile.contains(&card_to_meld));
     assert!(found_on_board);
 }
 
 #[test]
 fn test_icon_counts() {
-    let mut game = GameState::new(12345, 2);
+    let game = main_phase_game(12345, 2);
     // TODO: Setup a board with known cards and splay, then assert icon counts
     // For now just basic check
-    let counts = game.players[0].icon_counts(crate::db::load_all_cards());
+    let counts = game.players[0].icon_counts();
     // Initial hand empty board -> 0 icons
     assert!(counts.is_empty());
 }
 
 #[test]
 fn test_dogma_execution() {
-    let mut game = GameState::new(12345, 2);
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .hand(0, &["Cités-États"])
+        .build().unwrap();
     
     // P0 Melds a card
     let card_id = game.players[0].hand[0].clone();
@@ -66,7 +80,7 @@ fn test_dogma_execution() {
 
 #[test]
 fn test_achieve() {
-    let mut game = GameState::new(12345, 2);
+    let mut game = main_phase_game(12345, 2);
     
     // Cheat: Give P0 score
     // Add 5 "Age 1" cards to score pile
@@ -76,17 +90,19 @@ fn test_achieve() {
     let age1_card = db.values().find(|c| c.age == 1).unwrap();
     
     for _ in 0..5 {
-        game.players[0].score_pile.push(age1_card.name.clone());
+        game.players[0].score_pile.push(age1_card.name.clone()).unwrap();
     }
     
     // Cheat: Meld an Age 1 card (required for Age 1 achievement)
     let meld_card = db.values().find(|c| c.age == 1 && c.color == crate::model::Color::Red).unwrap();
     game.players[0].board.insert(crate::model::Color::Red, {
         let mut p = crate::player::Pile::new(crate::model::Color::Red);
-        p.cards.push(meld_card.name.clone());
+        p.push(meld_card.name.clone()).unwrap();
         p
     });
     
+    game.rehash();
+    
     // Try achieve Age 1
     game.apply_action(Action::Achieve("Age 1".to_string())).unwrap();
     
@@ -96,10 +112,10 @@ fn test_achieve() {
 
 #[test]
 fn test_registry_execution_ecriture() {
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    
-    // Cheat: Give P0 "Écriture" into hand.
-    game.players[0].hand.push("Écriture".to_string());
+    // P0 has "Écriture" in hand, and two actions this turn.
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .hand(0, &["Écriture"])
+        .build().unwrap();
     
     // Meld it.
     game.apply_action(crate::actions::Action::Meld("Écriture".to_string())).unwrap();
@@ -124,16 +140,12 @@ fn test_registry_execution_ecriture() {
 
 #[test]
 fn test_registry_sequence_outils() {
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    
     // Setup: P0 has "Outils" in hand and only 1 card to recycle.
     // With only 1 card, context_value = 1 which is NOT > 2, so Conditional fails.
-    game.players[0].hand.clear();
-    game.players[0].hand.push("Outils".to_string());
-    game.players[0].hand.push("Maçonnerie".to_string()); // Age 1, so second Recycle won't find it
-    
-    // Clear P1 hand
-    game.players[1].hand.clear();
+    // Maçonnerie is Age 1, so second Recycle won't find it. P1 hand is empty.
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .hand(0, &["Outils", "Maçonnerie"])
+        .build().unwrap();
     
     game.apply_action(crate::actions::Action::Meld("Outils".to_string())).unwrap();
     
@@ -155,7 +167,7 @@ fn test_registry_sequence_outils() {
     // Both conditionals fail, so no draws happen.
     
     // Verify Maçonnerie gone (Recycled to deck/bottom)
-    assert!(!game.players[0].hand.contains(&"Maçonnerie".to_string()));
+    assert!(!game.players[0].hand.contains("Maçonnerie"));
     
     // No DrawAndMeld happened (conditional failed), hand should be empty
     assert!(game.players[0].hand.is_empty());
@@ -166,37 +178,31 @@ fn test_registry_sequence_outils() {
 
 #[test]
 fn test_registry_voiles() {
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    game.players[0].hand.clear();
-    game.players[0].hand.push("Voiles".to_string());
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .hand(0, &["Voiles"])
+        .build().unwrap();
     
     // Meld
     game.apply_action(crate::actions::Action::Meld("Voiles".to_string())).unwrap();
     
-    // Clear P1
-    game.players[1].hand.clear();
-    
     // Execute
     // Voiles: Draw 1 and Meld it.
-    let board_count_before = game.players[0].board.values().map(|p| p.cards.len()).sum::<usize>();
+    let board_count_before = game.players[0].board.values().map(|p| p.len()).sum::<usize>();
     
     game.apply_action(crate::actions::Action::Dogma("Voiles".to_string())).unwrap();
     
-    let board_count_after = game.players[0].board.values().map(|p| p.cards.len()).sum::<usize>();
+    let board_count_after = game.players[0].board.values().map(|p| p.len()).sum::<usize>();
     assert_eq!(board_count_after, board_count_before + 1); // +1 from Melded card (Voiles itself is already there)
 }
 
 #[test]
 fn test_registry_agriculture() {
-    let mut game = crate::game_state::GameState::new(12345, 2);
     // Setup P0 with Agriculture + 2 dummy cards (needed for Return and Score)
-    game.players[0].hand.clear();
-    game.players[0].hand.push("Agriculture".to_string());
-    game.players[0].hand.push("Maçonnerie".to_string());
-    game.players[0].hand.push("Poterie".to_string()); // 2 extra
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .hand(0, &["Agriculture", "Maçonnerie", "Poterie"])
+        .build().unwrap();
     
     game.apply_action(crate::actions::Action::Meld("Agriculture".to_string())).unwrap();
-    game.players[1].hand.clear();
     
     // Execute
     // Steps:
@@ -231,31 +237,17 @@ fn test_registry_agriculture() {
 #[test]
 fn test_registry_code_de_lois() {
     eprintln!("Test Started: test_registry_code_de_lois");
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    
-    // Setup
-    game.players[0].hand.clear();
-    eprintln!("Hand Cleared");
-    
-    // Add Métallurgie (Red) to board
-    game.players[0].hand.push("Métallurgie".to_string());
-    eprintln!("Pushed Métallurgie");
-    game.apply_action(crate::actions::Action::Meld("Métallurgie".to_string())).unwrap();
-    eprintln!("Melded Métallurgie");
-    
-    // Add Code de lois (Purple) to board
-    game.players[0].hand.push("Code de lois".to_string());
-    game.apply_action(crate::actions::Action::Meld("Code de lois".to_string())).unwrap();
-    eprintln!("Melded Code de lois");
-    
-    // Add Archerie (Red) to hand
-    game.players[0].hand.push("Archerie".to_string());
+    // Board: Métallurgie (Red) and Code de lois (Purple). Hand: Archerie (Red).
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .pile(0, &["Métallurgie"], SplayDirection::None)
+        .pile(0, &["Code de lois"], SplayDirection::None)
+        .hand(0, &["Archerie"])
+        .build().unwrap();
     
     // Current state check
     eprintln!("Checking Red Pile Before");
     let red_pile = game.players[0].board.get(&crate::model::Color::Red).unwrap();
     eprintln!("Red Pile Found");
-    use crate::model::SplayDirection;
     assert_eq!(pile_splay(red_pile), SplayDirection::None);
     
     // Execute Dogma
@@ -279,30 +271,23 @@ fn test_registry_code_de_lois() {
     assert_eq!(pile_splay(red_pile_after), SplayDirection::Left);
     
     // Red pile count increased (Outils + Archerie = 2)
-    assert_eq!(red_pile_after.cards.len(), 2);
+    assert_eq!(red_pile_after.len(), 2);
 }
 
 #[test]
 fn test_registry_elevage() {
     eprintln!("Test Started: test_registry_elevage");
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    
-    // Setup
-    game.players[0].hand.clear();
-    
-    // Add Élevage (Yellow) to board
-    game.players[0].hand.push("Élevage".to_string());
-    game.apply_action(crate::actions::Action::Meld("Élevage".to_string())).unwrap();
-    
-    // Add Age 1 card (lowest) and Age 2 card (Calendrier) to hand
-    game.players[0].hand.push("Agriculture".to_string()); // Age 1
-    game.players[0].hand.push("Calendrier".to_string()); // Age 2
+    // Élevage (Yellow) on board. Age 1 card (lowest) and Age 2 card (Calendrier) in hand.
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .pile(0, &["Élevage"], SplayDirection::None)
+        .hand(0, &["Agriculture", "Calendrier"])
+        .build().unwrap();
     
     // Verify context
     let p = &game.players[0];
     assert_eq!(p.hand.len(), 2);
-    assert!(p.hand.contains(&"Agriculture".to_string()));
-    assert!(p.hand.contains(&"Calendrier".to_string()));
+    assert!(p.hand.contains("Agriculture"));
+    assert!(p.hand.contains("Calendrier"));
     
     // Execute Dogma
     // Execute Dogma
@@ -316,31 +301,23 @@ fn test_registry_elevage() {
     // Agriculture should be on board (Yellow pile).
     // Élevage is also Yellow, so Agriculture is on top (or tucked? Meld puts on top).
     let yellow_pile = game.players[0].board.get(&crate::model::Color::Yellow).unwrap();
-    assert_eq!(yellow_pile.cards.last().map(|s| s.as_str()), Some("Agriculture")); // Top card
+    assert_eq!(yellow_pile.top().map(|s| s.as_str()), Some("Agriculture")); // Top card
     
     // Hand should contain Calendrier + 1 drawn card (Age 1).
     let p = &game.players[0];
     assert_eq!(p.hand.len(), 2);
-    assert!(p.hand.contains(&"Calendrier".to_string()));
+    assert!(p.hand.contains("Calendrier"));
     // And one other card
 }
 
 #[test]
 fn test_registry_maconnerie() {
     eprintln!("Test Started: test_registry_maconnerie");
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    
-    // Setup
-    game.players[0].hand.clear();
-    
-    // Add Maçonnerie (Yellow) to board
-    game.players[0].hand.push("Maçonnerie".to_string());
-    game.apply_action(crate::actions::Action::Meld("Maçonnerie".to_string())).unwrap();
-    
-    // Add Castles and Non-Castles to hand
-    game.players[0].hand.push("Cités-États".to_string()); // Castle
-    game.players[0].hand.push("Mysticisme".to_string()); // Castle
-    game.players[0].hand.push("Agriculture".to_string()); // Leaf
+    // Maçonnerie (Yellow) on board. Castles (Cités-États, Mysticisme) and a Leaf (Agriculture) in hand.
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .pile(0, &["Maçonnerie"], SplayDirection::None)
+        .hand(0, &["Cités-États", "Mysticisme", "Agriculture"])
+        .build().unwrap();
     
     // Execute Dogma
     game.apply_action(crate::actions::Action::Dogma("Maçonnerie".to_string())).unwrap();
@@ -365,33 +342,24 @@ fn test_registry_maconnerie() {
     
     // Verify results
     let p = &game.players[0];
-    assert!(p.hand.contains(&"Agriculture".to_string()));
-    assert!(!p.hand.contains(&"Cités-États".to_string()));
-    assert!(!p.hand.contains(&"Mysticisme".to_string()));
+    assert!(p.hand.contains("Agriculture"));
+    assert!(!p.hand.contains("Cités-États"));
+    assert!(!p.hand.contains("Mysticisme"));
     
     // Verify Melds (Purple pile should have 2 cards now? Cités-États and Mysticisme are both Purple)
     let purple_pile = p.board.get(&crate::model::Color::Purple).unwrap();
-    assert_eq!(purple_pile.cards.len(), 2);
+    assert_eq!(purple_pile.len(), 2);
 }
 
 #[test]
 fn test_registry_poterie() {
     // Poterie (Green/Leaves): Recycle up to 3 cards from Hand. Draw and Score a card of value = count.
     eprintln!("Test Started: test_registry_poterie");
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    
-    // Setup
-    game.players[0].hand.clear();
-    
-    // Add Poterie
-    game.players[0].hand.push("Poterie".to_string());
-    game.apply_action(crate::actions::Action::Meld("Poterie".to_string())).unwrap();
-    
-    // Add 3 cards to hand (Real cards)
-    game.players[0].hand.push("La Roue".to_string()); 
-    game.players[0].hand.push("Agriculture".to_string());
-    game.players[0].hand.push("Métallurgie".to_string());
-    game.players[0].hand.push("Tissage".to_string());
+    // Poterie on board, 4 real cards in hand
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .pile(0, &["Poterie"], SplayDirection::None)
+        .hand(0, &["La Roue", "Agriculture", "Métallurgie", "Tissage"])
+        .build().unwrap();
     
     // Execute Dogma
     game.apply_action(crate::actions::Action::Dogma("Poterie".to_string())).unwrap();
@@ -409,10 +377,10 @@ fn test_registry_poterie() {
     eprintln!("Hand after Poterie: {:?}", p.hand);
     eprintln!("Score pile after Poterie: {:?}", p.score_pile);
     
-    assert!(p.hand.contains(&"Tissage".to_string()));
-    assert!(p.hand.contains(&"La Roue".to_string()));
-    assert!(!p.hand.contains(&"Agriculture".to_string()));
-    assert!(!p.hand.contains(&"Métallurgie".to_string()));
+    assert!(p.hand.contains("Tissage"));
+    assert!(p.hand.contains("La Roue"));
+    assert!(!p.hand.contains("Agriculture"));
+    assert!(!p.hand.contains("Métallurgie"));
     
     // Score pile check
     assert_eq!(p.score_pile.len(), 1, "Score pile should have 1 card");
@@ -428,21 +396,23 @@ fn test_registry_outils() {
     // 1. Return 3 cards from hand. If do, draw a 3 and meld it.
     // 2. Return a 3 from hand. If do, draw three 1s.
     eprintln!("Test Started: test_registry_outils");
-    let mut game = crate::game_state::GameState::new(12345, 2);
+    let mut game = main_phase_game(12345, 2);
     
     // Setup P0
     let p0_id = 0;
     game.players[p0_id].hand.clear();
-    game.players[p0_id].hand.push("Outils".to_string());
+    game.players[p0_id].hand.push("Outils").unwrap();
+    game.rehash();
     game.apply_action(crate::actions::Action::Meld("Outils".to_string())).unwrap();
     
     // Hand Setup: 3 Age 1s + 1 Age 3 (Ingénierie)
-    game.players[p0_id].hand.push("Agriculture".to_string());
-    game.players[p0_id].hand.push("Archerie".to_string());
-    game.players[p0_id].hand.push("Métallurgie".to_string());
-    game.players[p0_id].hand.push("Ingénierie".to_string()); // Age 3
+    game.players[p0_id].hand.push("Agriculture").unwrap();
+    game.players[p0_id].hand.push("Archerie").unwrap();
+    game.players[p0_id].hand.push("Métallurgie").unwrap();
+    game.players[p0_id].hand.push("Ingénierie").unwrap(); // Age 3
     
     game.current_player = 0;
+    game.rehash();
     
     // Execute Dogma
     game.apply_action(crate::actions::Action::Dogma("Outils".to_string())).unwrap();
@@ -483,7 +453,7 @@ fn test_registry_outils() {
 
 // Helper because pile_splay field access might need wrapping if I messed up imports in test file
 fn pile_splay(pile: &crate::player::Pile) -> crate::model::SplayDirection {
-    pile.splay
+    pile.splay()
 }
 
 #[test]
@@ -491,36 +461,998 @@ fn test_registry_rames() {
     // Rames (Demand): Transfer a Crown card from your Hand to my Score. Draw 1.
     // Demand requires activator to have MORE icons than victim.
     eprintln!("Test Started: test_registry_rames");
-    let mut game = crate::game_state::GameState::new(12345, 2);
-    
-    // Setup: P0 has Rames on board. P1 has a Crown card in hand.
-    game.players[0].hand.clear();
-    game.players[1].hand.clear();
-    
-    // Meld Rames for P0 (has Castle icons which is the dogma symbol)
-    game.players[0].hand.push("Rames".to_string());
-    game.apply_action(crate::actions::Action::Meld("Rames".to_string())).unwrap();
-    
-    // Meld another Castle card for P0 to ensure they have more Castles than P1
-    game.players[0].hand.push("Cités-États".to_string());
-    game.apply_action(crate::actions::Action::Meld("Cités-États".to_string())).unwrap();
+    // Setup: P0 has Rames on board (has Castle icons which is the dogma symbol),
+    // plus another Castle card to ensure they have more Castles than P1.
+    // P1 gets a card with Crown icon (Élevage has Crown).
+    let mut game = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .pile(0, &["Rames"], SplayDirection::None)
+        .pile(0, &["Cités-États"], SplayDirection::None)
+        .hand(1, &["Élevage"])
+        .build().unwrap();
     
     // Verify P0 has Castles
-    let db = crate::db::load_all_cards();
-    let p0_castles = game.players[0].icon_counts(db).get(&crate::model::Symbol::Castle).cloned().unwrap_or(0);
+    let p0_castles = game.players[0].icon_counts().get(&crate::model::Symbol::Castle).cloned().unwrap_or(0);
     eprintln!("P0 Castles: {}", p0_castles);
     assert!(p0_castles > 0, "P0 should have Castle icons");
     
-    // Give P1 a card with Crown icon (Élevage has Crown)
-    game.players[1].hand.push("Élevage".to_string());
-    
     // P0 activates Rames Dogma
     // Since Rames is a Demand (isSupremacy), P1 (victim with fewer Castles) should be affected.
     // With exactly 1 Crown card, P1 auto-transfers it (no input needed).
     game.apply_action(crate::actions::Action::Dogma("Rames".to_string())).unwrap();
     
     // Verify: Élevage should be in P0's score pile (activator)
-    assert!(game.players[0].score_pile.contains(&"Élevage".to_string()), "Élevage should be in P0 score pile");
+    assert!(game.players[0].score_pile.contains("Élevage"), "Élevage should be in P0 score pile");
     // P1 should have drawn a card after the transfer
     assert!(!game.players[1].hand.is_empty(), "P1 should have drawn a card");
-}
\ No newline at end of file
+}
+#[test]
+fn test_legal_actions() {
+    let mut game = main_phase_game(12345, 2);
+    let actions = game.legal_actions();
+    assert_eq!(actions[0], Action::Draw);
+    for card in &game.players[0].hand {
+        assert!(actions.contains(&Action::Meld(card.clone())));
+    }
+    
+    // Nothing is legal once the game is over
+    game.end_game_by_score();
+    assert!(game.legal_actions().is_empty());
+    assert!(game.apply_action(Action::Draw).is_err());
+}
+
+#[test]
+fn test_scenario_from_json() {
+    let json = r#"{
+        "players": 3,
+        "current_player": 1,
+        "turn": 4,
+        "seats": [
+            { "hand": ["Archerie"] },
+            { "board": [{ "cards": ["Agriculture", "Élevage"], "splay": "Left" }], "score": ["Voiles"] },
+            { "achievements": ["Age 1"] }
+        ],
+        "decks": { "2": ["Calendrier", "Fermentation"] }
+    }"#;
+    let mut game = ScenarioBuilder::from_json(json).unwrap().build().unwrap();
+    
+    assert_eq!(game.players.len(), 3);
+    assert_eq!(game.current_player, 1);
+    assert_eq!(game.turn_number, 4);
+    assert_eq!(game.phase, crate::game_state::GamePhase::Main);
+    assert_eq!(game.players[0].hand, vec!["Archerie".to_string()]);
+    assert!(game.players[1].hand.is_empty());
+    
+    let yellow = game.players[1].board.get(&crate::model::Color::Yellow).unwrap();
+    assert_eq!(yellow.top().map(|s| s.as_str()), Some("Élevage"));
+    assert_eq!(yellow.splay(), SplayDirection::Left);
+    assert_eq!(game.players[1].score_pile, vec!["Voiles".to_string()]);
+    assert!(game.achievements.contains("Age 1"));
+    
+    // Deck order is honoured: Calendrier is drawn first
+    assert_eq!(game.draw_age(2, 2).unwrap().map(String::as_str), Some("Calendrier"));
+    assert_eq!(game.draw_age(2, 2).unwrap().map(String::as_str), Some("Fermentation"));
+    
+    // Every card is still accounted for exactly once
+    let mut total = game.deck.values().map(|d| d.len()).sum::<usize>();
+    for p in &game.players {
+        total += p.hand.len() + p.score_pile.len() + p.board.values().map(|b| b.len()).sum::<usize>();
+    }
+    let base = crate::db::load_all_cards().values().filter(|c| c.expansion == crate::model::Expansion::Base).count();
+    assert_eq!(total, base);
+}
+
+#[test]
+fn test_scenario_rejects_inconsistent_setups() {
+    // Same card twice
+    assert!(ScenarioBuilder::new(2).hand(0, &["Archerie"]).hand(1, &["Archerie"]).build().is_err());
+    // Card above max age
+    assert!(ScenarioBuilder::new(2).max_age(1).hand(0, &["Calendrier"]).build().is_err());
+    // Mixed colors in one pile
+    assert!(ScenarioBuilder::new(2).pile(0, &["Agriculture", "Archerie"], SplayDirection::None).build().is_err());
+    // Splayed single card
+    assert!(ScenarioBuilder::new(2).pile(0, &["Agriculture"], SplayDirection::Up).build().is_err());
+    // Unknown card
+    assert!(ScenarioBuilder::new(2).score(1, &["Nope"]).build().is_err());
+}
+
+#[test]
+fn test_game_config_validation() {
+    for players in [0, 1, 5, 7] {
+        assert!(matches!(GameConfig::new(players), Err(GameError::InvalidConfig(_))));
+    }
+    assert_eq!(GameConfig::new(2).unwrap().achievements_needed, 6);
+    assert_eq!(GameConfig::new(3).unwrap().achievements_needed, 5);
+    assert_eq!(GameConfig::new(4).unwrap().achievements_needed, 4);
+    
+    // Configs edited after construction are checked again when the game is created
+    let invalid = [
+        GameConfig::new(2).unwrap().max_age(0),
+        GameConfig::new(2).unwrap().max_age(11),
+        GameConfig::new(2).unwrap().achievements_needed(0),
+        GameConfig { player_count: 7, ..GameConfig::default() },
+        GameConfig::new(2).unwrap().expansion(crate::model::Expansion::Figures).expansion(crate::model::Expansion::Figures),
+    ];
+    for config in invalid {
+        assert!(matches!(GameState::from_config(1, config), Err(GameError::InvalidConfig(_))));
+    }
+}
+
+#[test]
+fn test_game_config_is_stored_and_applied() {
+    let config = GameConfig::new(2).unwrap()
+        .max_age(3)
+        .achievements_needed(1)
+        .skip_setup(true)
+        .house_rule(HouseRule::NoFirstTurnLimit);
+    let mut game = GameState::from_config(7, config.clone()).unwrap();
+    assert_eq!(game.config, config);
+    assert_eq!(game.phase, GamePhase::Main);
+    assert!(game.deck.keys().all(|age| *age <= 3));
+    
+    // No first-turn limit: P0 keeps playing after one action
+    game.apply_action(Action::Draw).unwrap();
+    assert_eq!(game.current_player, 0);
+    
+    // A single achievement wins
+    game.players[1].achievements.push("Age 1".to_string());
+    game.achievements.insert("Age 1".to_string());
+    game.check_victory();
+    assert_eq!(game.winners, vec![1]);
+    
+    // The config survives a save/load round trip
+    let saved = serde_json::to_string(&game).unwrap();
+    let loaded: GameState = serde_json::from_str(&saved).unwrap();
+    assert_eq!(loaded.config, config);
+}
+
+#[test]
+fn test_team_play_demands_skip_partner() {
+    // P0 and P2 are partners; nobody else has a castle
+    let mut game = ScenarioBuilder::new(4).seed(3).turn(2).team_play()
+        .pile(0, &["Archerie"], SplayDirection::None)
+        .build().unwrap();
+    assert!(game.config.teammates(0, 2) && game.config.teammates(1, 3));
+    assert!(!game.config.teammates(0, 1));
+    
+    game.apply_action(Action::Dogma("Archerie".to_string())).unwrap();
+    
+    // P1 and P3 each drew a card and handed it over; P2 was left alone
+    assert_eq!(game.players[0].hand.len(), 2);
+    assert!(game.players[2].hand.is_empty());
+}
+
+#[test]
+fn test_team_play_victory_and_score() {
+    assert!(matches!(GameConfig::new(3).unwrap().team_play(true).validate(), Err(GameError::InvalidConfig(_))));
+    
+    let mut game = ScenarioBuilder::new(4).seed(3).turn(2).team_play()
+        .achievement(1, "Age 1").achievement(1, "Age 2").achievement(1, "Age 3")
+        .achievement(3, "Age 4").achievement(3, "Age 5")
+        .build().unwrap();
+    assert_eq!(game.config.achievements_needed, 6);
+    assert_eq!(game.team_achievements(3), 5);
+    
+    // The sixth achievement of the team wins the game for both partners
+    game.claim_special_achievement(3, "Monument").unwrap();
+    assert_eq!(game.winners, vec![1, 3]);
+    
+    // On score, partners add up their score piles
+    let mut game = ScenarioBuilder::new(4).seed(3).turn(2).team_play()
+        .score(0, &["Agriculture"]).score(2, &["Archerie"])
+        .score(1, &["Voiles"])
+        .build().unwrap();
+    game.end_game_by_score();
+    assert_eq!(game.winners, vec![0, 2]);
+}
+
+#[test]
+fn test_ruleset_sharing_bonus() {
+    use crate::rules::Ruleset;
+    // P1 shares Écriture; P0's highest top card is an age 2
+    let bonus_age = |ruleset: Ruleset| {
+        let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+            .pile(0, &["Écriture"], SplayDirection::None)
+            .pile(0, &["Construction"], SplayDirection::None)
+            .pile(1, &["Mathématiques"], SplayDirection::None)
+            .build().unwrap();
+        game.config.ruleset = ruleset;
+        game.apply_action(Action::Dogma("Écriture".to_string())).unwrap();
+        // Hand: the two age 2 cards the registry draws, then the bonus
+        let hand = &game.players[0].hand;
+        assert_eq!(hand.len(), 3);
+        crate::db::load_all_cards()[&hand[2]].age
+    };
+    assert_eq!(bonus_age(Ruleset::standard()), 1);
+    assert_eq!(bonus_age(Ruleset::third_edition()), 2);
+    
+    // The NoSharingBonus house rule is the ruleset's SharingBonus::None, whatever the builder order
+    use crate::rules::SharingBonus;
+    let config = GameConfig::new(2).unwrap().house_rule(HouseRule::NoSharingBonus).ruleset(Ruleset::third_edition());
+    assert_eq!(config.ruleset.sharing_bonus, SharingBonus::None);
+    let config = GameConfig::new(2).unwrap().ruleset(Ruleset::third_edition()).house_rule(HouseRule::NoSharingBonus);
+    assert_eq!(config.ruleset.sharing_bonus, SharingBonus::None);
+    let conflicting = GameConfig { ruleset: Ruleset::third_edition(), ..config };
+    assert!(matches!(conflicting.validate(), Err(GameError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_ruleset_achievements_and_first_turn() {
+    use crate::rules::Ruleset;
+    let cheap = Ruleset { achievement_cost_per_age: 1, achievement_needs_top_card: false, ..Ruleset::standard() };
+    let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .score(0, &["Agriculture", "Archerie"])
+        .build().unwrap();
+    game.config.ruleset = cheap;
+    assert!(game.check_achievement(0, 2).is_ok());
+    assert!(game.check_achievement(0, 3).is_err());
+    
+    let standard = Ruleset::standard();
+    assert_eq!(standard.first_turn_actions(2, 0), 1);
+    assert_eq!(standard.first_turn_actions(2, 1), 2);
+    assert_eq!(standard.first_turn_actions(4, 1), 1);
+    assert_eq!(Ruleset::by_name("third-edition"), Some(Ruleset::third_edition()));
+    
+    let broken = Ruleset { actions_per_turn: 0, ..Ruleset::standard() };
+    assert!(GameConfig::new(2).unwrap().ruleset(broken).validate().is_err());
+}
+
+#[test]
+fn test_echoes_icons_and_bonuses() {
+    use crate::model::{Card, Color, Expansion, Icon, SpecialIcon, Symbol};
+    use crate::player::{Pile, Player};
+    use std::collections::HashMap;
+    
+    // Echoes card data uses the new icon kinds
+    let icons: Vec<Icon> = serde_json::from_str(r#"["ECHO", {"bonus": 3}, 2, "LEAF"]"#).unwrap();
+    assert_eq!(icons, vec![
+        Icon::Special(SpecialIcon::Echo), Icon::Bonus { bonus: 3 }, Icon::Age(2), Icon::Resource(Symbol::Leaf),
+    ]);
+    
+    let card = |name: &str, icons: Vec<Icon>, echo: Option<&str>| Card {
+        name: name.to_string(), age: 2, color: Color::Green, icons, dogmas: Vec::new(),
+        expansion: Expansion::Echoes, echo: echo.map(|e| e.to_string()), karma: Vec::new(), inspire: None,
+    };
+    let echo = Icon::Special(SpecialIcon::Echo);
+    let leaf = Icon::Resource(Symbol::Leaf);
+    let db: HashMap<String, Card> = [
+        card("Bottom", vec![echo, Icon::Bonus { bonus: 4 }, leaf, leaf], Some("bottom echo")),
+        card("Middle", vec![leaf, leaf, Icon::Bonus { bonus: 2 }, echo], Some("middle echo")),
+        card("Top", vec![echo, leaf, Icon::Bonus { bonus: 5 }, leaf], Some("top echo")),
+    ].into_iter().map(|c| (c.name.clone(), c)).collect();
+    for card in db.values() {
+        crate::db::register_card(card.clone());
+    }
+    
+    let mut pile = Pile::new(Color::Green);
+    for name in ["Bottom", "Middle", "Top"] {
+        pile.push(name).unwrap();
+    }
+    // Unsplayed: only the top card shows its echo and bonus
+    assert_eq!(pile.visible_echoes(&db), vec!["Top".to_string()]);
+    
+    // Splayed right, the left column (positions 0 and 1) of the cards below shows
+    pile.set_splay(SplayDirection::Right);
+    assert_eq!(pile.visible_echoes(&db), vec!["Bottom".to_string(), "Top".to_string()]);
+    
+    let mut player = Player::new(0);
+    player.board.insert(Color::Green, pile);
+    // Bonuses 4 and 5 visible: highest (5) plus one for the other
+    assert_eq!(player.bonus_points(), 6);
+    assert_eq!(player.calculate_score(), 6);
+    
+    // The board still converts to and from the color -> pile map it used to be
+    let piles: HashMap<Color, Pile> = player.board.clone().into();
+    assert_eq!(piles[&Color::Green].cards().last().map(String::as_str), Some("Top"));
+    assert_eq!(crate::player::Board::from(piles), player.board);
+}
+
+#[test]
+fn test_echoes_setup_and_foreshadow() {
+    use crate::model::Expansion;
+    let config = GameConfig::new(2).unwrap().expansion(Expansion::Echoes);
+    assert!(config.validate().is_ok());
+    // The card data holds only part of the published set, and the config says so
+    let partial = config.partial_expansions();
+    assert_eq!(partial.iter().map(|c| (c.expansion, c.cards, c.published)).collect::<Vec<_>>(), [(Expansion::Echoes, 50, 105)]);
+    assert!(GameConfig::new(2).unwrap().partial_expansions().is_empty());
+    
+    let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .hand(0, &["Voiles", "Mathématiques"])
+        .foreshadow(0, &["Agriculture", "Philosophie"])
+        .build().unwrap();
+    
+    // Melding an age 1 card promotes the foreshadowed age 1 card only
+    game.apply_action(Action::Meld("Voiles".to_string())).unwrap();
+    assert_eq!(game.players[0].foreshadow, vec!["Philosophie".to_string()]);
+    assert!(game.players[0].board.values().any(|pile| pile.top() == Some(&"Agriculture".to_string())));
+    
+    game.apply_action(Action::Meld("Mathématiques".to_string())).unwrap();
+    assert!(game.players[0].foreshadow.is_empty());
+}
+
+#[test]
+fn test_echoes_card_runs_echo_before_dogma() {
+    // Règle: echo draws a 1, then the dogma may recycle up to two cards and draws a 2 if it did
+    let mut game = ScenarioBuilder::new(2).seed(3).expansion(crate::model::Expansion::Echoes)
+        .pile(0, &["Règle"], SplayDirection::None)
+        .build().unwrap();
+    game.apply_action(Action::Dogma("Règle".to_string())).unwrap();
+    
+    let drawn = game.players[0].hand.clone();
+    assert_eq!(drawn.len(), 1);
+    assert_eq!(crate::db::load_all_cards()[&drawn[0]].age, 1);
+    assert!(game.pending_input.is_some());
+    
+    game.apply_action(Action::ResolveInput(drawn[0].clone())).unwrap();
+    let hand = &game.players[0].hand;
+    assert_eq!(hand.len(), 1);
+    assert_eq!(crate::db::load_all_cards()[&hand[0]].age, 2);
+}
+
+#[test]
+fn test_artifact_splays_the_color_it_drew_and_melded() {
+    use crate::model::{Color, Expansion};
+    // Danseuse de Mohenjo-daro: draw and meld a 1, then splay that color left
+    let mut game = ScenarioBuilder::new(2).seed(8).expansion(Expansion::Artifacts)
+        .pile(0, &["Danseuse de Mohenjo-daro"], SplayDirection::None)
+        .pile(0, &["Élevage"], SplayDirection::None)
+        .deck_top(1, &["Agriculture"])
+        .build().unwrap();
+    game.apply_action(Action::Dogma("Danseuse de Mohenjo-daro".to_string())).unwrap();
+    
+    let yellow = game.players[0].board.get(&Color::Yellow).unwrap();
+    assert_eq!(yellow.top().map(|s| s.as_str()), Some("Agriculture"));
+    assert_eq!(yellow.splay(), SplayDirection::Left);
+}
+
+#[test]
+fn test_cities_visible_icons_per_splay() {
+    use crate::model::{Card, Color, Expansion, Icon, SpecialIcon, Symbol};
+    use crate::player::Pile;
+    use std::collections::HashMap;
+    
+    // Partial card data: 50 of the 105 published cards
+    assert_eq!(crate::db::expansion_coverage(Expansion::Cities).map(|c| (c.cards, c.published)), Some((50, 105)));
+    
+    // Positions: TL, BL, BC, BR, TC, TR
+    let city_icons: Vec<Icon> = serde_json::from_str(
+        r#"[3, "CROWN", "LEAF", "CASTLE", "FLAG", "SPLAY_UP"]"#).unwrap();
+    assert_eq!(city_icons[4], Icon::Special(SpecialIcon::Flag));
+    let city = |name: &str| Card {
+        name: name.to_string(), age: 3, color: Color::Red, icons: city_icons.clone(), dogmas: Vec::new(),
+        expansion: Expansion::Cities, echo: None, karma: Vec::new(), inspire: None,
+    };
+    let db: HashMap<String, Card> = [city("Below"), city("Above")].into_iter().map(|c| (c.name.clone(), c)).collect();
+    for card in db.values() {
+        crate::db::register_card(card.clone());
+    }
+    let mut pile = Pile::new(Color::Red);
+    pile.push("Below").unwrap();
+    pile.push("Above").unwrap();
+    
+    let below = |pile: &Pile| -> Vec<Icon> {
+        let icons = pile.visible_icons(&db);
+        icons[..icons.len() - 6].to_vec()
+    };
+    let res = Icon::Resource;
+    assert!(below(&pile).is_empty());
+    assert_eq!(pile.visible_card_count(), 1);
+    pile.set_splay(SplayDirection::Left);
+    assert_eq!(below(&pile), vec![res(Symbol::Castle), Icon::Special(SpecialIcon::SplayUp)]);
+    pile.set_splay(SplayDirection::Right);
+    assert_eq!(below(&pile), vec![Icon::Age(3), res(Symbol::Crown)]);
+    pile.set_splay(SplayDirection::Up);
+    assert_eq!(below(&pile), vec![res(Symbol::Crown), res(Symbol::Leaf), res(Symbol::Castle)]);
+    assert_eq!(pile.visible_card_count(), 2);
+}
+
+#[test]
+fn test_karma_hooks() {
+    use crate::dogma::karma::{self, Karma, KarmaEvent, KarmaTiming, KarmaTrigger};
+    
+    // "If you would meld, instead score it" / "Each time you meld, draw a 1"
+    fn score_instead(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+        let card = state.remove_from_hand(event.player_id, event.card_id.as_ref().unwrap())?;
+        state.score_card(event.player_id, card)
+    }
+    fn draw_after(state: &mut GameState, event: &KarmaEvent) -> Result<(), GameError> {
+        state.draw_age(event.player_id, 1).map(|_| ())
+    }
+    let instead = Karma::new(KarmaTrigger::Meld, KarmaTiming::Instead, score_instead);
+    let after = Karma::new(KarmaTrigger::Meld, KarmaTiming::After, draw_after);
+    
+    let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .hand(0, &["Voiles"])
+        .build().unwrap();
+    let event = KarmaEvent::card(0, KarmaTrigger::Meld, "Voiles");
+    assert_eq!(event.age, Some(1));
+    
+    // Only the first Instead karma applies
+    assert!(karma::run_karmas(&mut game, &event, &[instead, instead, after]).unwrap());
+    assert_eq!(game.players[0].score_pile, vec!["Voiles".to_string()]);
+    assert_eq!(game.players[0].hand.len(), 1);
+    assert!(!game.resolving_karma);
+    assert!(!karma::run_karmas(&mut game, &event, &[after]).unwrap());
+    assert_eq!(game.players[0].hand.len(), 2);
+    
+    // Karmas never fire without the Figures expansion
+    assert!(!karma::intercept(&mut game, &event, KarmaTiming::Instead).unwrap());
+    assert!(karma::active_karmas(&game, 0, KarmaTrigger::Meld, KarmaTiming::Instead).is_empty());
+}
+
+#[test]
+fn test_figures_karma_and_inspire() {
+    use crate::model::Expansion;
+    // Partial card data: 50 of the 105 published cards
+    assert_eq!(crate::db::expansion_coverage(Expansion::Figures).map(|c| (c.cards, c.published)), Some((50, 105)));
+    
+    // Imhotep: "Each time you meld a green card, draw a 2"; inspire: draw a 2
+    let mut game = ScenarioBuilder::new(2).seed(4).turn(2).expansion(Expansion::Figures)
+        .pile(0, &["Imhotep"], SplayDirection::None)
+        .hand(0, &["Voiles", "Écriture"])
+        .deck_top(2, &["Calendrier", "Mathématiques"])
+        .build().unwrap();
+    game.apply_action(Action::Meld("Voiles".to_string())).unwrap();
+    assert_eq!(game.players[0].hand, vec!["Écriture".to_string(), "Calendrier".to_string()]);
+    
+    // Melding onto Imhotep's pile runs its inspire effect first
+    game.apply_action(Action::Meld("Écriture".to_string())).unwrap();
+    assert_eq!(game.players[0].hand, vec!["Calendrier".to_string(), "Mathématiques".to_string()]);
+    assert!(game.action_log.iter().any(|line| line == "P0 est inspiré par 'Imhotep'"));
+    let blue = game.players[0].board.get(&crate::model::Color::Blue).unwrap();
+    assert_eq!(blue.top().map(|s| s.as_str()), Some("Écriture"));
+}
+
+#[test]
+fn test_figures_decree() {
+    use crate::model::Expansion;
+    let decree = Action::Achieve("Décret : Guerre".to_string());
+    let game = ScenarioBuilder::new(2).seed(6).turn(2).expansion(Expansion::Figures)
+        .hand(0, &["Sargon", "Imhotep"])
+        .build().unwrap();
+    assert!(!game.legal_actions().contains(&decree));
+    
+    // Three figures, one of them red, issue the war decree
+    let mut game = ScenarioBuilder::new(2).seed(6).turn(2).expansion(Expansion::Figures)
+        .hand(0, &["Sargon", "Imhotep", "Hammurabi", "Agriculture"])
+        .score(1, &["Calendrier", "Élevage"])
+        .build().unwrap();
+    assert!(game.legal_actions().contains(&decree));
+    game.apply_action(decree.clone()).unwrap();
+    
+    assert_eq!(game.players[0].hand, vec!["Agriculture".to_string()]);
+    assert_eq!(game.players[0].achievements, vec!["Décret : Guerre".to_string()]);
+    assert_eq!(game.players[1].score_pile, vec!["Élevage".to_string()]);
+    // The opponent draws a figure after the achievement
+    let hand = &game.players[1].hand;
+    assert_eq!(hand.len(), 1);
+    assert!(crate::dogma::figures::is_figure(&hand[0]));
+    assert!(game.apply_action(decree).is_err());
+}
+
+#[test]
+fn test_artifacts_dig_and_display() {
+    use crate::model::Expansion;
+    use std::collections::HashMap;
+    
+    // Partial card data: 50 of the 105 published cards
+    assert_eq!(crate::db::expansion_coverage(Expansion::Artifacts).map(|c| (c.cards, c.published)), Some((50, 105)));
+    
+    // Returning the artifact on display
+    let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .display(0, "Rames")
+        .build().unwrap();
+    assert!(game.artifact_pending);
+    assert!(matches!(game.pending_input, Some(crate::dogma::flow::InputRequest::Confirm { player_id: 0, .. })));
+    assert!(game.apply_action(Action::Draw).is_err());
+    game.apply_action(Action::ResolveInput("no".to_string())).unwrap();
+    assert!(game.players[0].display.is_none());
+    assert!(game.deck[&1].contains("Rames"));
+    assert!(game.pending_input.is_none());
+    
+    // Melding it and using its dogma for free
+    let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .display(0, "Écriture")
+        .build().unwrap();
+    game.apply_action(Action::ResolveInput("yes".to_string())).unwrap();
+    assert!(game.players[0].board.values().any(|pile| pile.top() == Some(&"Écriture".to_string())));
+    assert!(!game.players[0].hand.is_empty());
+    assert_eq!(game.actions_taken, 0);
+    assert_eq!(game.current_player, 0);
+    
+    // Covering a card of the same value digs an artifact, climbing ages when needed
+    let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .pile(0, &["Voiles"], SplayDirection::None)
+        .hand(0, &["La Roue"])
+        .build().unwrap();
+    game.config.expansions.push(Expansion::Artifacts);
+    game.deck.get_mut(&2).unwrap().retain(|c| c != "Calendrier");
+    game.expansion_decks.insert(Expansion::Artifacts, HashMap::from([(2, crate::card_list::CardList::try_from(vec!["Calendrier".to_string()]).unwrap().into())]).into());
+    game.apply_action(Action::Meld("La Roue".to_string())).unwrap();
+    assert_eq!(game.players[0].display.as_deref(), Some("Calendrier"));
+    
+    // The artifact waits for its owner's next turn
+    game.apply_action(Action::Draw).unwrap();
+    assert!(!game.artifact_pending);
+    game.apply_action(Action::Draw).unwrap();
+    game.apply_action(Action::Draw).unwrap();
+    assert_eq!(game.current_player, 0);
+    assert!(game.artifact_pending);
+}
+
+#[test]
+fn test_random_agents_and_observation() {
+    use crate::agent::{self, Agent, Observation};
+    
+    // The view of seat 0 does not depend on which age 1 card P1 holds
+    let view = |card: &str| {
+        let game = ScenarioBuilder::new(2).seed(5).turn(2).hand(1, &[card]).build().unwrap();
+        let obs = Observation::new(&game, 0);
+        (obs.view.players[1].hand.clone(), obs.view.deck[&1].clone())
+    };
+    assert_eq!(view("Rames"), view("Archerie"));
+    
+    // Redacted views name no card the seat cannot see, in the log or anywhere else
+    let mut game = GameState::new(9, 2);
+    let mut bots: Vec<Box<dyn Agent>> = (0..2).map(|pid| agent::from_spec("random", pid).unwrap()).collect();
+    for _ in 0..200 {
+        if !game.winners.is_empty() {
+            break;
+        }
+        for seat in 0..2 {
+            let json = serde_json::to_string(&Observation::new(&game, seat).redacted()).unwrap();
+            let opponent = &game.players[1 - seat];
+            let hidden = opponent.hand.iter().chain(&opponent.score_pile).chain(game.deck.values().flat_map(|pile| pile.iter()));
+            for card in hidden {
+                assert!(!json.contains(&format!("\"{}\"", card)) && !json.contains(&format!("'{}'", card)), "seat {} sees '{}'", seat, card);
+            }
+        }
+        let actor = game.acting_player();
+        let action = bots[actor].choose(&Observation::new(&game, actor));
+        game.apply_action(action).unwrap();
+    }
+    
+    // Seeded bots play a full game, reproducibly
+    let play = |seed: u64| {
+        let mut game = GameState::new(seed, 2);
+        let mut bots: Vec<Box<dyn Agent>> = (0..2).map(|pid| agent::from_spec("random", seed + pid).unwrap()).collect();
+        for _ in 0..5000 {
+            if !game.winners.is_empty() {
+                break;
+            }
+            let actor = game.acting_player();
+            let action = bots[actor].choose(&Observation::new(&game, actor));
+            game.apply_action(action).unwrap();
+        }
+        assert!(game.end_condition.is_some());
+        (game.winners.clone(), game.action_log.len())
+    };
+    let (winners, turns) = play(11);
+    assert!(agent::from_spec("ismcts:iterations=10,threads=1", 0).is_ok());
+    assert!(agent::from_spec("heuristic:difficulty=impossible", 0).is_err());
+    assert!(agent::from_spec("random:depth=3", 0).is_err());
+    assert!(!winners.is_empty());
+    assert_eq!(play(11), (winners, turns));
+}
+
+#[test]
+fn test_heuristic_agent() {
+    use crate::agent::{Agent, HeuristicAgent, HeuristicWeights, Observation, RandomAgent};
+    use crate::dogma::flow::InputRequest;
+    
+    let req = InputRequest::SelectCard { player_id: 0, source: vec!["A".into(), "B".into(), "C".into()], min: 0, max: 2 };
+    assert_eq!(req.responses(10), vec!["", "A", "B", "C", "A,B", "A,C", "B,C"]);
+    assert_eq!(req.responses(3).len(), 3);
+    
+    // Claiming an available achievement beats any other move
+    let game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .pile(0, &["Voiles"], SplayDirection::None)
+        .score(0, &["Calendrier", "Philosophie", "Mathématiques"])
+        .hand(0, &["Rames"])
+        .build().unwrap();
+    let mut bot = HeuristicAgent::new(1);
+    assert_eq!(bot.choose(&Observation::new(&game, 0)), Action::Achieve("Age 1".to_string()));
+    assert!(HeuristicWeights::by_difficulty("easy").unwrap().noise > HeuristicWeights::default().noise);
+    
+    // And the greedy bot outplays random moves
+    let mut wins = 0;
+    for seed in 0..4 {
+        let mut game = GameState::new(seed, 2);
+        let mut bots: Vec<Box<dyn Agent>> = vec![Box::new(HeuristicAgent::new(seed)), Box::new(RandomAgent::new(seed))];
+        while game.winners.is_empty() {
+            let actor = game.acting_player();
+            let action = bots[actor].choose(&Observation::new(&game, actor));
+            game.apply_action(action).unwrap();
+        }
+        wins += game.winners.contains(&0) as usize;
+    }
+    assert!(wins >= 3, "heuristic won {} of 4 games", wins);
+}
+
+#[test]
+fn test_ismcts_agent() {
+    use crate::agent::{Agent, Budget, IsmctsAgent, IsmctsConfig, Observation};
+    
+    let game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .pile(0, &["Voiles"], SplayDirection::None)
+        .score(0, &["Calendrier", "Philosophie", "Mathématiques"])
+        .hand(0, &["Rames"])
+        .build().unwrap();
+    let config = IsmctsConfig { budget: Budget::Iterations(300), threads: 3, ..IsmctsConfig::default() };
+    let obs = Observation::new(&game, 0);
+    
+    // Same seed and iteration budget, same search, whatever the thread timing
+    let stats = IsmctsAgent::with_config(9, config.clone()).search(&obs);
+    assert_eq!(stats, IsmctsAgent::with_config(9, config.clone()).search(&obs));
+    assert_eq!(stats.iter().map(|s| s.visits).sum::<u32>(), 300);
+    assert_eq!(IsmctsAgent::with_config(9, config).choose(&obs), Action::Achieve("Age 1".to_string()));
+}
+
+#[test]
+fn test_engine_protocol_messages() {
+    use crate::protocol::{self, BotMessage, EngineMessage};
+    
+    let game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .hand(0, &["Voiles"])
+        .hand(1, &["Rames"])
+        .build().unwrap();
+    let line = EngineMessage::request(3, &game, 0).to_line();
+    let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(value["type"], "request");
+    assert_eq!(value["view"]["players"][0]["hand"], serde_json::json!(["Voiles"]));
+    assert_eq!(value["view"]["players"][1]["hand"], serde_json::json!(["?1"]));
+    assert!(value["legal"].as_array().unwrap().contains(&serde_json::json!({"Meld": "Voiles"})));
+    
+    let answer = BotMessage::parse(r#"{"type":"action","id":3,"action":{"Meld":"Voiles"}}"#).unwrap();
+    let BotMessage::Action { id: 3, action } = answer else { panic!("unexpected {:?}", answer) };
+    assert!(protocol::validate(&game, &action).is_ok());
+    assert!(protocol::validate(&game, &Action::Meld("Rames".to_string())).is_err());
+    assert!(BotMessage::parse("{\"type\":\"dance\"}").is_err());
+    
+    // Input answers are checked against the request: Poterie recycles 0 to 3 cards from hand
+    let mut game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .pile(0, &["Poterie"], SplayDirection::None)
+        .hand(0, &["Voiles", "Archerie", "Métallurgie", "Tissage"])
+        .score(0, &["Rames"])
+        .build().unwrap();
+    game.apply_action(Action::Dogma("Poterie".to_string())).unwrap();
+    let answer = |cards: &str| Action::ResolveInput(cards.to_string());
+    for bogus in ["Voiles,Archerie,Métallurgie,Tissage", "Rames", "Voiles,Voiles", "Agriculture"] {
+        assert!(protocol::validate(&game, &answer(bogus)).is_err(), "{} accepted", bogus);
+    }
+    assert!(protocol::validate(&game, &answer("")).is_ok());
+    game.apply_action(answer("Voiles,Archerie")).unwrap();
+    assert_eq!(game.players[0].score_pile.len(), 2);
+}
+
+#[test]
+fn test_interned_board_and_icon_totals() {
+    use crate::db::{card_at, card_index, card_stats};
+    use crate::model::{Color, IconTotals, Symbol};
+    
+    // Interned indices follow age then name, and map back to the same card
+    let idx = card_index("Voiles").unwrap();
+    assert_eq!(card_at(idx).name, "Voiles");
+    assert_eq!(card_stats(idx).age, 1);
+    assert!(card_index("Calendrier").unwrap() > card_index("Voiles").unwrap());
+    assert_eq!(card_index("Nope"), None);
+    
+    let game = ScenarioBuilder::new(2).seed(5).turn(2)
+        .pile(0, &["Agriculture", "Élevage"], SplayDirection::Left)
+        .pile(0, &["Voiles"], SplayDirection::None)
+        .build().unwrap();
+    let p = &game.players[0];
+    let db = crate::db::load_all_cards();
+    
+    // Cached totals agree with the icons actually showing
+    let shown: Vec<_> = p.board.values().flat_map(|pile| pile.visible_icons(db)).collect();
+    assert_eq!(p.icon_totals(), IconTotals::of(&shown));
+    for symbol in Symbol::ALL {
+        assert_eq!(p.icon_counts().get(&symbol).copied().unwrap_or(0), p.icon_totals().get(symbol));
+    }
+    let yellow = p.board.get(&Color::Yellow).unwrap();
+    assert_eq!(yellow.cards().collect::<Vec<_>>(), ["Agriculture", "Élevage"]);
+    assert_eq!(p.board.keys().copied().collect::<Vec<_>>(), [Color::Green, Color::Yellow]);
+    
+    // Boards still serialize as a map of color to pile, with card names
+    let json = serde_json::to_value(&p.board).unwrap();
+    assert_eq!(json["YELLOW"]["cards"], serde_json::json!(["Agriculture", "Élevage"]));
+    assert_eq!(json["YELLOW"]["splay"], "Left");
+    let back: crate::player::Board = serde_json::from_value(json).unwrap();
+    assert_eq!(back.get(&Color::Yellow).unwrap().icons(), yellow.icons());
+    assert!(serde_json::from_str::<crate::player::Pile>(r#"{"color":"RED","cards":["Nope"],"splay":"None"}"#).is_err());
+    
+    // Card lists keep the total age a score pile is worth through every edit
+    let mut score = crate::card_list::CardList::from_names(["Voiles", "Calendrier"]).unwrap();
+    assert_eq!(score.total_age(), 3);
+    score.push("Calendrier").unwrap();
+    score.insert(0, "Agriculture").unwrap();
+    assert_eq!(score.total_age(), 6);
+    assert!(score.push("Nope").is_err());
+    score.retain(|name| name != "Calendrier");
+    assert_eq!((score.len(), score.total_age()), (2, 2));
+    score.pop();
+    score.remove(0);
+    assert_eq!(score.total_age(), 0);
+}
+
+#[test]
+fn test_state_clone_shares_until_written() {
+    use crate::shared::Shared;
+    
+    let game = main_phase_game(12345, 2);
+    let mut copy = game.clone();
+    assert_eq!(copy, game);
+    assert!(Shared::ptr_eq(&copy.players[1], &game.players[1]));
+    
+    // Only what the copy changes stops being shared
+    let card = copy.players[0].hand[0].clone();
+    copy.apply_action(Action::Meld(card.clone())).unwrap();
+    assert_ne!(copy, game);
+    assert!(game.players[0].hand.contains(&card));
+    assert!(!Shared::ptr_eq(&copy.players[0], &game.players[0]));
+    assert!(Shared::ptr_eq(&copy.players[1], &game.players[1]));
+    assert!(copy.action_log.len() > game.action_log.len());
+    
+    // Same seed and moves, same state, log and rng included
+    let mut replay = main_phase_game(12345, 2);
+    replay.apply_action(Action::Meld(card)).unwrap();
+    assert_eq!(replay, copy);
+    assert!(format!("{:?}", replay).contains("action_log"));
+}
+
+#[test]
+fn test_zobrist_follows_moves() {
+    use crate::model::Color;
+    
+    let mut game = main_phase_game(7, 2);
+    let start = game.zobrist();
+    let hand = game.players[0].hand.clone();
+    
+    // Moving a card out and back restores the hash
+    let card = game.remove_from_hand(0, &hand[0]).unwrap();
+    game.place_on_board(0, card.clone()).unwrap();
+    assert_ne!(game.zobrist(), start);
+    let card = game.remove_from_board(0, card).unwrap();
+    game.add_to_hand(0, card).unwrap();
+    assert_eq!(game.zobrist(), start);
+    
+    // Same cards reached in another order: same position, same hash
+    let mut other = game.clone();
+    for card in &hand {
+        game.remove_from_hand(0, card).unwrap();
+        game.score_card(0, card.clone()).unwrap();
+    }
+    for card in hand.iter().rev() {
+        other.remove_from_hand(0, card).unwrap();
+        other.score_card(0, card.clone()).unwrap();
+    }
+    assert_eq!(game.zobrist(), other.zobrist());
+    
+    // Splays and whose turn it is count too
+    let mut splayed = ScenarioBuilder::new(2).seed(12345).turn(2)
+        .pile(0, &["Métallurgie", "Archerie"], SplayDirection::None)
+        .build().unwrap();
+    let before = splayed.zobrist();
+    splayed.splay(0, Color::Red, SplayDirection::Left).unwrap();
+    assert_ne!(splayed.zobrist(), before);
+    splayed.splay(0, Color::Red, SplayDirection::None).unwrap();
+    assert_eq!(splayed.zobrist(), before);
+    splayed.current_player = 1;
+    assert_ne!(splayed.zobrist(), before);
+    
+    // The incremental hash matches a full recompute
+    let incremental = game.zobrist();
+    game.rehash();
+    assert_eq!(game.zobrist(), incremental);
+    
+    // Fields edited directly leave the hash stale, but actions still play; rehash mends it
+    let mut edited = main_phase_game(7, 2);
+    let card = edited.players[0].hand.pop().unwrap();
+    edited.players[0].score_pile.push(card).unwrap();
+    edited.apply_action(Action::Draw).unwrap();
+    edited.apply_move(crate::actions::Move::Draw).unwrap();
+    let stale = edited.zobrist();
+    edited.rehash();
+    assert_ne!(edited.zobrist(), stale);
+}
+
+#[test]
+fn test_solver_finds_forced_win_and_weighs_draws() {
+    use crate::solver::{self, SolverConfig};
+    
+    // One achievement short, with the score and top card to claim Age 1
+    let mut builder = ScenarioBuilder::new(2).seed(3).turn(2)
+        .pile(0, &["Archerie"], SplayDirection::None)
+        .score(0, &["Voiles", "Rames", "La Roue", "Poterie", "Outils"])
+        .pile(1, &["Agriculture"], SplayDirection::None);
+    for age in 2..=6 {
+        builder = builder.achievement(0, &format!("Age {}", age));
+    }
+    let game = builder.build().unwrap();
+    let solution = solver::solve(&game, &SolverConfig::default());
+    assert_eq!(solution.value, 1.0);
+    assert_eq!(solution.depth, 1);
+    assert_eq!(solution.best_line, vec![Action::Achieve("Age 1".to_string())]);
+    assert!(!solution.truncated);
+    assert!(solution.proven);
+    
+    // Lines cut off by the horizon give a lower bound, not a loss
+    let opening = solver::solve(&main_phase_game(3, 2), &SolverConfig { depth: 1, ..SolverConfig::default() });
+    assert_eq!(opening.value, 0.0);
+    assert!(!opening.proven && !opening.lost());
+    assert!(opening.moves.iter().all(|m| !m.proven));
+    
+    // A draw branches on every card the pile may have on top
+    let outcomes = solver::outcomes(&game, &Action::Draw).unwrap();
+    let age_1 = game.deck[&1].len();
+    assert_eq!(outcomes.len(), age_1);
+    let total: f64 = outcomes.iter().map(|(p, _)| p).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    let drawn: std::collections::HashSet<_> = outcomes.iter().map(|(_, s)| s.players[0].hand[0].clone()).collect();
+    assert_eq!(drawn.len(), age_1);
+}
+
+#[test]
+fn test_td_training_is_reproducible() {
+    use crate::agent::{self, Agent, LearnedAgent, LearnedWeights, Observation, TrainingConfig};
+    
+    let config = TrainingConfig { games: 2, seed: 4, max_age: 3, max_steps: 150, ..TrainingConfig::default() };
+    let mut seen = 0;
+    let weights = agent::train(&config, LearnedWeights::default(), |done, _| seen = done).unwrap();
+    assert_eq!(seen, 2);
+    assert_eq!(weights.games, 2);
+    assert_ne!(weights, LearnedWeights::default());
+    assert_eq!(agent::train(&config, LearnedWeights::default(), |_, _| {}).unwrap(), weights);
+    
+    // Weights files round-trip, and only for the current features
+    let loaded = LearnedWeights::from_json(&weights.to_json()).unwrap();
+    assert!(loaded.weights.iter().zip(&weights.weights).all(|(a, b)| (a - b).abs() < 1e-12));
+    let mut other = weights.clone();
+    other.features.pop();
+    assert!(LearnedWeights::from_json(&serde_json::to_string(&other).unwrap()).is_err());
+    assert!(agent::from_spec("learned", 0).is_err());
+    
+    let game = main_phase_game(8, 2);
+    let action = LearnedAgent::new(1, weights).choose(&Observation::new(&game, 0));
+    assert!(game.legal_actions().contains(&action));
+}
+
+#[test]
+fn test_hints_report_who_a_dogma_reaches() {
+    use crate::agent::{self, Budget, IsmctsConfig};
+    
+    let game = ScenarioBuilder::new(3).seed(5).turn(2)
+        .pile(0, &["Archerie"], SplayDirection::None)
+        .pile(1, &["Poterie"], SplayDirection::None)
+        .pile(2, &["Agriculture"], SplayDirection::None)
+        .build().unwrap();
+    // Demands hit players with fewer icons, sharing needs at least as many
+    assert_eq!(agent::dogma_reach(&game, 0, "Archerie"), (vec![], vec![1, 2]));
+    assert_eq!(agent::dogma_reach(&game, 2, "Agriculture"), (vec![1], vec![]));
+    
+    let config = IsmctsConfig { budget: Budget::Iterations(100), threads: 1, ..IsmctsConfig::default() };
+    // With an empty hand, P0 can only draw or use Archerie
+    let hints = agent::hints(&game, config.clone(), 7, 3);
+    assert_eq!(hints.len(), 2);
+    assert_eq!(agent::hints(&game, config.clone(), 7, 1), hints[..1]);
+    let archerie = hints.iter().find(|h| h.action == Action::Dogma("Archerie".to_string())).unwrap();
+    assert_eq!((archerie.sharers.clone(), archerie.demanded.clone()), (vec![], vec![1, 2]));
+    assert!(hints.windows(2).all(|w| w[0].visits >= w[1].visits));
+}
+
+#[test]
+fn test_analysis_replays_and_flags_swings() {
+    use crate::agent::{self, Agent, Observation};
+    use crate::analysis::{self, AnalysisConfig, Replay};
+    
+    let config = GameConfig::new(2).unwrap().max_age(2);
+    let mut game = GameState::from_config(21, config.clone()).unwrap();
+    let mut replay = Replay::new(21, config);
+    let mut bots: Vec<Box<dyn Agent>> = vec![agent::from_spec("heuristic", 1).unwrap(), agent::from_spec("random", 2).unwrap()];
+    while game.winners.is_empty() {
+        let actor = game.acting_player();
+        let action = bots[actor].choose(&Observation::new(&game, actor));
+        game.apply_action(action.clone()).unwrap();
+        replay.actions.push(action);
+    }
+    let replay = Replay::from_json(&replay.to_json()).unwrap();
+    assert_eq!(replay.positions().unwrap().last().unwrap(), &game);
+    
+    let report = analysis::analyze(&replay, &AnalysisConfig { threshold: 0.0, ..AnalysisConfig::default() }).unwrap();
+    assert_eq!(report.values.len(), replay.actions.len() + 1);
+    assert_eq!(report.winners, game.winners);
+    assert_eq!(report.values.last().unwrap()[game.winners[0]], 1.0);
+    // The biggest swings, in game order
+    assert_eq!(report.moments.len(), 5);
+    assert!(report.moments.windows(2).all(|w| w[0].index < w[1].index));
+    let smallest = report.moments.iter().map(|m| m.swing().abs()).fold(f64::INFINITY, f64::min);
+    let swings = (0..replay.actions.len()).filter(|&idx| {
+        let player = replay.positions().unwrap()[idx].acting_player();
+        (report.values[idx + 1][player] - report.values[idx][player]).abs() > smallest
+    }).count();
+    assert!(swings < 5);
+    // One summary per turn, the last one at the end of the game
+    let last = report.turns.last().unwrap();
+    assert_eq!(last.turn, game.turn_number);
+    assert_eq!(last.players[0].score, game.players[0].calculate_score());
+    assert_eq!(last.players[1].icons.iter().sum::<u32>(), game.players[1].icon_totals().symbols.iter().sum::<u32>());
+    
+    let mut broken = replay.clone();
+    broken.actions.insert(0, Action::Dogma("Archerie".to_string()));
+    assert!(matches!(broken.positions(), Err(GameError::InvalidAction(_))));
+}
+
+#[test]
+fn test_preview_dogma_leaves_the_game_alone() {
+    let game = ScenarioBuilder::new(3).seed(5).turn(2)
+        .pile(0, &["Archerie"], SplayDirection::None)
+        .pile(1, &["Poterie"], SplayDirection::None)
+        .pile(2, &["Maçonnerie"], SplayDirection::None)
+        .build().unwrap();
+    let before = game.clone();
+    let preview = game.preview_dogma("Archerie").unwrap();
+    assert_eq!(game, before);
+    
+    // Archerie demands from P1 (no castle), not from P2 (as many castles)
+    assert_eq!((preview.sharers.clone(), preview.demanded.clone()), (vec![], vec![1]));
+    let line = &preview.dogmas[0];
+    assert!(line.is_supremacy && line.text.starts_with("J'exige"));
+    assert_eq!(line.activator_icons, 3);
+    assert_eq!(line.players.iter().map(|p| (p.player, p.icons, p.reached)).collect::<Vec<_>>(), vec![(1, 0, true), (2, 3, false)]);
+    
+    // P1 draws a 1 and hands it over
+    let changes = &preview.outcome.changes;
+    assert_eq!((changes[0].hand, changes[1].hand, changes[2].hand), (1, 0, 0));
+    assert!(preview.outcome.log.iter().any(|l| l.contains("active le dogme de 'Archerie'")));
+    assert_eq!(game.preview_dogma("Archerie").unwrap(), preview);
+    
+    assert!(matches!(game.preview_dogma("Poterie"), Err(GameError::InvalidAction(_))));
+}
+
+#[test]
+fn test_explain_renders_effects_as_the_engine_runs_them() {
+    use crate::dogma::explain::{explain, explain_dogma, Lang};
+    use crate::dogma::registry;
+    use crate::model::{Effect, Location};
+
+    let archerie = &registry::get_effects("Archerie").unwrap()[0];
+    assert_eq!(explain_dogma(archerie, true, Lang::En),
+        "I demand: draw a 1. Transfer a card of the highest value from your hand to my hand.");
+    assert_eq!(explain_dogma(archerie, true, Lang::Fr),
+        "J'exige : piochez une 1. Transférez une carte de votre Main de la valeur la plus élevée vers ma Main.");
+    
+    // Every registered dogma reads as something in both languages
+    for card in crate::db::load_all_cards().values() {
+        for effect in registry::get_effects(&card.name).into_iter().flatten() {
+            for lang in [Lang::En, Lang::Fr] {
+                assert!(explain(effect, lang).ends_with('.'), "{}: {:?}", card.name, effect);
+            }
+        }
+    }
+    
+    let skipped = Effect::Return { count: 1, source: Location::Hand };
+    assert_eq!(explain(&skipped, Lang::En), "Return a card from your hand (not run by the engine).");
+    assert_eq!(Lang::by_name("de"), None);
+}
+
+#[test]
+fn test_coverage_follows_the_executor_dispatch() {
+    use crate::dogma::coverage::{card_coverage, CoverageProblem, DogmaSource};
+    use crate::model::Symbol;
+    let db = crate::db::load_all_cards();
+    
+    let archerie = card_coverage(&db["Archerie"]);
+    assert_eq!((archerie.dogmas.clone(), archerie.problems.clone()), (vec![DogmaSource::Registry], vec![]));
+    assert_eq!(card_coverage(&db["Monnaie"]).dogmas, vec![DogmaSource::Legacy]);
+    let industrialisation = card_coverage(&db["Industrialisation"]);
+    assert!(!industrialisation.is_complete());
+    assert_eq!(industrialisation.problems, vec![CoverageProblem::SymbolNotOnCard { index: 0, symbol: Symbol::Crown }]);
+    
+    // A second dogma the registry knows nothing about is skipped
+    let mut card = db["Archerie"].clone();
+    card.dogmas.push(card.dogmas[0].clone());
+    let report = card_coverage(&card);
+    assert_eq!(report.dogmas, vec![DogmaSource::Registry, DogmaSource::Missing]);
+    assert_eq!(report.problems, vec![CoverageProblem::LengthMismatch { registry: 1, dogmas: 2 }]);
+}
@@ -0,0 +1,136 @@
+//! Zobrist hashing of game positions.
+//!
+//! Every (card, zone) pair gets a fixed random 64-bit key, and a position hashes to the
+//! XOR of the keys of where its cards are, so moving a card changes the hash by two
+//! XORs. Keys are derived from card names rather than drawn from a table, which keeps
+//! them identical between runs and processes.
+//!
+//! Board cards are keyed by their depth in the pile, so the order of a pile counts;
+//! draw piles are hashed as sets, since their order is hidden from the players.
+
+use crate::db::{self, CardIdx};
+use crate::game_state::GameState;
+use crate::model::{Color, SplayDirection};
+use crate::player::Pile;
+use rustc_hash::FxHasher;
+use std::hash::{Hash, Hasher};
+
+/// Where a card is, as far as the hash is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Hand(usize),
+    Score(usize),
+    Foreshadow(usize),
+    Display(usize),
+    /// Achievements a player holds (standard or special).
+    Achievements(usize),
+    /// Any draw pile.
+    Deck,
+    /// `depth` counts from the bottom of the pile.
+    Board { player: usize, depth: usize },
+}
+
+impl Zone {
+    fn code(self) -> u64 {
+        let (kind, player, depth) = match self {
+            Zone::Hand(p) => (1, p, 0),
+            Zone::Score(p) => (2, p, 0),
+            Zone::Foreshadow(p) => (3, p, 0),
+            Zone::Display(p) => (4, p, 0),
+            Zone::Achievements(p) => (5, p, 0),
+            Zone::Deck => (6, 0, 0),
+            Zone::Board { player, depth } => (7, player, depth),
+        };
+        (kind << 40) | ((player as u64) << 32) | depth as u64
+    }
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Stable number for a card or achievement ID: its interned index when it is a card.
+fn item_id(id: &str) -> u64 {
+    match db::card_index(id) {
+        Some(idx) => idx as u64,
+        None => {
+            // FNV-1a, flagged so it cannot collide with a card index
+            let hash = id.bytes().fold(0xCBF2_9CE4_8422_2325u64, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01B3));
+            hash | (1 << 63)
+        }
+    }
+}
+
+fn key(item: u64, zone: Zone) -> u64 {
+    splitmix64(splitmix64(item) ^ zone.code())
+}
+
+/// Key of `card_id` (or an achievement ID) lying in `zone`.
+pub fn card_key(card_id: &str, zone: Zone) -> u64 {
+    key(item_id(card_id), zone)
+}
+
+/// `card_key` of an interned card.
+pub fn index_key(idx: CardIdx, zone: Zone) -> u64 {
+    key(idx as u64, zone)
+}
+
+fn board_card_key(idx: CardIdx, player: usize, depth: usize) -> u64 {
+    index_key(idx, Zone::Board { player, depth })
+}
+
+/// Key of a pile of `player` splayed in `direction`; unsplayed piles add nothing.
+pub fn splay_key(player: usize, color: Color, direction: SplayDirection) -> u64 {
+    if direction == SplayDirection::None {
+        return 0;
+    }
+    let code = (8u64 << 40) | ((player as u64) << 32) | ((color.index() as u64) << 8) | direction.index() as u64;
+    splitmix64(code)
+}
+
+/// Combined key of a whole board pile: its cards by depth and its splay.
+pub fn pile_key(player: usize, pile: &Pile) -> u64 {
+    pile.indices().iter().enumerate()
+        .fold(splay_key(player, pile.color, pile.splay()), |h, (depth, &idx)| h ^ board_card_key(idx, player, depth))
+}
+
+/// Hash of where every card is, computed from scratch. `GameState` keeps the same value
+/// up to date as cards move.
+pub fn card_locations(state: &GameState) -> u64 {
+    let mut hash = 0;
+    for (pid, p) in state.players.iter().enumerate() {
+        for (cards, zone) in [
+            (&p.hand, Zone::Hand(pid)),
+            (&p.score_pile, Zone::Score(pid)),
+            (&p.foreshadow, Zone::Foreshadow(pid)),
+        ] {
+            hash = cards.indices().iter().fold(hash, |h, &idx| h ^ index_key(idx, zone));
+        }
+        hash = p.achievements.iter().fold(hash, |h, id| h ^ card_key(id, Zone::Achievements(pid)));
+        if let Some(artifact) = &p.display {
+            hash ^= card_key(artifact, Zone::Display(pid));
+        }
+        hash = p.board.values().fold(hash, |h, pile| h ^ pile_key(pid, pile));
+    }
+    let decks = state.deck.values().chain(state.expansion_decks.values().flat_map(|decks| decks.values()));
+    for pile in decks {
+        hash = pile.indices().iter().fold(hash, |h, &idx| h ^ index_key(idx, Zone::Deck));
+    }
+    hash
+}
+
+/// Hash of whose turn it is and what the game waits for. Cheap enough to compute on
+/// demand, unlike card locations.
+pub(crate) fn turn_key(state: &GameState) -> u64 {
+    let mut hasher = FxHasher::default();
+    state.phase.hash(&mut hasher);
+    state.current_player.hash(&mut hasher);
+    state.actions_taken.hash(&mut hasher);
+    state.artifact_pending.hash(&mut hasher);
+    state.pending_input.hash(&mut hasher);
+    state.dogma_state.hash(&mut hasher);
+    splitmix64(hasher.finish())
+}
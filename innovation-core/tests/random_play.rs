@@ -0,0 +1,364 @@
+//! Random-play fuzzing harness.
+//!
+//! Plays seeded games by picking random legal actions and random valid answers to
+//! every `InputRequest`, and checks engine invariants after each step. Every random
+//! decision is drawn from a recorded choice stream, so a failing game can be replayed
+//! and shrunk (by deleting and lowering choices) down to a minimal action sequence.
+//!
+//! `INNOVATION_FUZZ_GAMES` sets the number of seeds per (player count, max age) pair.
+
+use innovation_core::actions::Action;
+use innovation_core::config::GameConfig;
+use innovation_core::db::load_all_cards;
+use innovation_core::dogma::flow::InputRequest;
+use innovation_core::game_state::{GamePhase, GameState};
+use innovation_core::model::{Expansion, IconTotals};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+
+const MAX_STEPS: usize = 5000;
+const DEFAULT_GAMES: u64 = 4;
+const SHRINK_BUDGET: usize = 2000;
+
+#[derive(Debug, Clone, Copy)]
+struct Scenario {
+    seed: u64,
+    players: usize,
+    max_age: u8,
+    team_play: bool,
+    expansions: &'static [Expansion],
+}
+
+#[derive(Debug, Clone)]
+struct Failure {
+    step: usize,
+    reason: String,
+    trace: Vec<Action>,
+}
+
+impl Failure {
+    /// Coarse failure category, so shrinking does not wander off to an unrelated bug.
+    fn kind(&self) -> &str {
+        self.reason.split(':').next().unwrap_or("")
+    }
+}
+
+/// Source of every random decision taken during a game.
+/// Replays use a fixed prefix; past its end, fresh values come from the rng (or 0 when shrinking).
+struct Choices {
+    prefix: Vec<u64>,
+    drawn: Vec<u64>,
+    rng: Option<StdRng>,
+}
+
+impl Choices {
+    fn fresh(seed: u64) -> Self {
+        Self { prefix: Vec::new(), drawn: Vec::new(), rng: Some(StdRng::seed_from_u64(seed)) }
+    }
+
+    fn replay(prefix: Vec<u64>) -> Self {
+        Self { prefix, drawn: Vec::new(), rng: None }
+    }
+
+    fn choose(&mut self, n: usize) -> usize {
+        let raw = match self.prefix.get(self.drawn.len()) {
+            Some(v) => *v,
+            None => self.rng.as_mut().map(|r| r.next_u64()).unwrap_or(0),
+        };
+        self.drawn.push(raw);
+        (raw % n.max(1) as u64) as usize
+    }
+}
+
+fn random_response(req: &InputRequest, choices: &mut Choices) -> String {
+    match req {
+        InputRequest::SelectCard { source, min, max, .. } => {
+            let mut pool = source.clone();
+            pool.sort();
+            let lo = (*min).min(pool.len());
+            let hi = (*max).min(pool.len());
+            let count = lo + choices.choose(hi - lo + 1);
+            let mut picked = Vec::with_capacity(count);
+            for _ in 0..count {
+                picked.push(pool.remove(choices.choose(pool.len())));
+            }
+            picked.join(",")
+        }
+        InputRequest::SelectColor { colors, .. } => {
+            format!("{:?}", colors[choices.choose(colors.len())])
+        }
+        InputRequest::Confirm { .. } => {
+            if choices.choose(2) == 0 { "yes".to_string() } else { "no".to_string() }
+        }
+    }
+}
+
+fn all_cards(game: &GameState) -> Vec<&String> {
+    let mut cards: Vec<&String> = game.deck.values().flatten().collect();
+    cards.extend(game.expansion_decks.values().flat_map(|decks| decks.values().flatten()));
+    for p in &game.players {
+        cards.extend(p.hand.iter());
+        cards.extend(p.foreshadow.iter());
+        cards.extend(p.display.iter());
+        cards.extend(p.score_pile.iter());
+        cards.extend(p.board.values().flat_map(|pile| pile.cards()));
+    }
+    cards
+}
+
+fn check_invariants(game: &GameState, total_cards: usize) -> Result<(), String> {
+    if game.current_player >= game.players.len() {
+        return Err(format!("current player out of range: {}", game.current_player));
+    }
+    if game.actions_taken >= 2 {
+        return Err(format!("actions taken not reset: {}", game.actions_taken));
+    }
+
+    let cards = all_cards(game);
+    let mut seen = HashSet::new();
+    for card in &cards {
+        if !seen.insert(*card) {
+            return Err(format!("card duplicated: {}", card));
+        }
+    }
+    if cards.len() != total_cards {
+        return Err(format!("card count changed: {} instead of {}", cards.len(), total_cards));
+    }
+
+    let mut claimed = HashSet::new();
+    for (idx, p) in game.players.iter().enumerate() {
+        if p.id != idx {
+            return Err(format!("player id mismatch: seat {} has id {}", idx, p.id));
+        }
+        for (color, pile) in &p.board {
+            if pile.color != *color {
+                return Err(format!("pile stored under wrong color: {:?} under {:?}", pile.color, color));
+            }
+            if pile.is_empty() {
+                return Err(format!("empty pile left on board: P{} {:?}", idx, color));
+            }
+            if *pile.icons() != IconTotals::of(&pile.visible_icons(load_all_cards())) {
+                return Err(format!("stale icon totals: P{} {:?}", idx, color));
+            }
+        }
+        for ach in &p.achievements {
+            if !claimed.insert(ach) || !game.achievements.contains(ach) {
+                return Err(format!("achievement bookkeeping broken: {}", ach));
+            }
+        }
+    }
+    if claimed.len() != game.achievements.len() {
+        return Err("achievement bookkeeping broken: unowned achievement".to_string());
+    }
+
+    if game.winners.is_empty() == (game.phase == GamePhase::GameOver) {
+        return Err(format!("phase mismatch: {:?} with winners {:?}", game.phase, game.winners));
+    }
+    if game.winners.iter().any(|w| *w >= game.players.len()) {
+        return Err(format!("winner out of range: {:?}", game.winners));
+    }
+
+    if game.winners.is_empty() {
+        if let Some(req) = &game.pending_input {
+            if game.dogma_state.is_none() && !game.artifact_pending {
+                return Err("input pending outside of a dogma".to_string());
+            }
+            let answerable = match req {
+                InputRequest::SelectCard { source, min, max, .. } => !source.is_empty() && min <= max,
+                InputRequest::SelectColor { colors, .. } => !colors.is_empty(),
+                InputRequest::Confirm { .. } => true,
+            };
+            if !answerable {
+                return Err(format!("unanswerable input request: {:?}", req));
+            }
+        } else if game.dogma_state.is_some() {
+            return Err("dogma left in progress without pending input".to_string());
+        }
+    }
+    Ok(())
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Plays one game, returning the first invariant violation, panic or rejected legal action.
+fn play(scenario: Scenario, choices: &mut Choices) -> Option<Failure> {
+    let mut trace = Vec::new();
+    let mut game = match panic::catch_unwind(|| {
+        let config = GameConfig::new(scenario.players)?.max_age(scenario.max_age).team_play(scenario.team_play);
+        let config = scenario.expansions.iter().fold(config, |config, e| config.expansion(*e));
+        GameState::from_config(scenario.seed, config)
+    }) {
+        Ok(Ok(game)) => game,
+        Ok(Err(e)) => return Some(Failure { step: 0, reason: format!("rejected: {:?}", e), trace }),
+        Err(payload) => {
+            return Some(Failure { step: 0, reason: format!("panic: {}", panic_message(payload)), trace });
+        }
+    };
+    let total_cards = all_cards(&game).len();
+
+    for step in 0..MAX_STEPS {
+        if !game.winners.is_empty() {
+            return None;
+        }
+
+        let action = match &game.pending_input {
+            Some(req) => Action::ResolveInput(random_response(req, choices)),
+            None => {
+                let mut legal = game.legal_actions();
+                if legal.is_empty() {
+                    return Some(Failure { step, reason: "stuck: no legal action".to_string(), trace });
+                }
+                legal.swap_remove(choices.choose(legal.len()))
+            }
+        };
+        trace.push(action.clone());
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| game.apply_action(action)));
+        let reason = match result {
+            Ok(Ok(())) => check_invariants(&game, total_cards).err().map(|e| format!("invariant: {}", e)),
+            Ok(Err(e)) => Some(format!("rejected: {:?}", e)),
+            Err(payload) => Some(format!("panic: {}", panic_message(payload))),
+        };
+        if let Some(reason) = reason {
+            return Some(Failure { step, reason, trace });
+        }
+    }
+    Some(Failure { step: MAX_STEPS, reason: "unfinished: step limit reached".to_string(), trace })
+}
+
+/// Greedily deletes and lowers recorded choices while the game keeps failing the same way.
+fn shrink(scenario: Scenario, mut choices: Vec<u64>, mut failure: Failure) -> (Vec<u64>, Failure) {
+    let budget = Cell::new(SHRINK_BUDGET);
+    let attempt = |candidate: &[u64], kind: &str| -> Option<(Vec<u64>, Failure)> {
+        budget.set(budget.get().checked_sub(1)?);
+        let mut replay = Choices::replay(candidate.to_vec());
+        let found = play(scenario, &mut replay)?;
+        (found.kind() == kind).then_some((replay.drawn, found))
+    };
+
+    let mut improved = true;
+    while improved && budget.get() > 0 {
+        improved = false;
+        let kind = failure.kind().to_string();
+
+        for chunk in [8, 4, 2, 1] {
+            let mut start = 0;
+            while start + chunk <= choices.len() {
+                let mut candidate = choices.clone();
+                candidate.drain(start..start + chunk);
+                match attempt(&candidate, &kind) {
+                    Some((c, f)) if f.trace.len() <= failure.trace.len() => {
+                        choices = c;
+                        failure = f;
+                        improved = true;
+                    }
+                    _ => start += 1,
+                }
+            }
+        }
+
+        for idx in 0..choices.len() {
+            for lower in [0, choices[idx] / 2] {
+                if lower >= choices[idx] {
+                    continue;
+                }
+                let mut candidate = choices.clone();
+                candidate[idx] = lower;
+                if let Some((c, f)) = attempt(&candidate, &kind) {
+                    if f.trace.len() <= failure.trace.len() {
+                        choices = c;
+                        failure = f;
+                        improved = true;
+                        break;
+                    }
+                }
+            }
+            if idx + 1 >= choices.len() {
+                break;
+            }
+        }
+    }
+    (choices, failure)
+}
+
+fn fuzz_player_count(players: usize, team_play: bool) {
+    fuzz(players, team_play, &[]);
+}
+
+fn fuzz(players: usize, team_play: bool, expansions: &'static [Expansion]) {
+    let games: u64 = std::env::var("INNOVATION_FUZZ_GAMES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_GAMES);
+
+    for max_age in 1..=10 {
+        for game_idx in 0..games {
+            let seed = game_idx * 1000 + max_age as u64 * 10 + players as u64;
+            let scenario = Scenario { seed, players, max_age, team_play, expansions };
+            let mut choices = Choices::fresh(seed);
+            let Some(failure) = play(scenario, &mut choices) else { continue };
+
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(|_| {}));
+            let (_, minimal) = shrink(scenario, choices.drawn, failure);
+            panic::set_hook(hook);
+
+            let steps: Vec<String> = minimal.trace.iter().map(|a| format!("  {:?}", a)).collect();
+            panic!(
+                "{:?} failed at step {}: {}\nminimal action sequence ({} actions):\n{}",
+                scenario, minimal.step, minimal.reason, minimal.trace.len(), steps.join("\n")
+            );
+        }
+    }
+}
+
+#[test]
+fn random_play_two_players() {
+    fuzz_player_count(2, false);
+}
+
+#[test]
+fn random_play_three_players() {
+    fuzz_player_count(3, false);
+}
+
+#[test]
+fn random_play_four_players() {
+    fuzz_player_count(4, false);
+}
+
+#[test]
+fn random_play_four_player_teams() {
+    fuzz_player_count(4, true);
+}
+
+#[test]
+fn random_play_with_echoes() {
+    fuzz(3, false, &[Expansion::Echoes]);
+}
+
+#[test]
+fn random_play_with_cities() {
+    fuzz(3, false, &[Expansion::Cities]);
+}
+
+#[test]
+fn random_play_with_artifacts() {
+    fuzz(3, false, &[Expansion::Artifacts]);
+}
+
+#[test]
+fn random_play_with_figures() {
+    fuzz(3, false, &[Expansion::Figures]);
+}
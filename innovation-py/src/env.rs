@@ -0,0 +1,404 @@
+//! Reinforcement learning environment over `GameState`, independent of Python.
+//!
+//! Every move is one index of a fixed action space ([`ActionLayout`]). Multi-card
+//! `SelectCard` answers are built one pick at a time, closed by `Done` (or sent as
+//! soon as the maximum count is reached), so every valid answer is reachable with a
+//! fixed number of indices. Observations are fixed-size `f32` vectors, seat-relative.
+
+use innovation_core::actions::Action;
+use innovation_core::agent::{self, Agent, Observation};
+use innovation_core::config::GameConfig;
+use innovation_core::dogma::flow::InputRequest;
+use innovation_core::errors::GameError;
+use innovation_core::game_state::{GamePhase, GameState};
+use innovation_core::model::{Color, SplayDirection, Symbol};
+use innovation_core::load_all_cards;
+use std::collections::HashMap;
+
+pub const MAX_PLAYERS: usize = 4;
+const AGES: usize = 10;
+
+/// A decoded action index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvAction {
+    Draw,
+    Meld(usize),
+    Dogma(usize),
+    Achieve(u8),
+    /// Adds a card to the `SelectCard` answer being built.
+    Pick(usize),
+    /// Sends the `SelectCard` answer built so far.
+    Done,
+    Color(usize),
+    Confirm(bool),
+}
+
+/// Fixed action space over the card list (cards indexed by age then name).
+#[derive(Debug, Clone)]
+pub struct ActionLayout {
+    pub cards: Vec<String>,
+    index: HashMap<String, usize>,
+}
+
+impl Default for ActionLayout {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActionLayout {
+    pub fn new() -> Self {
+        let mut cards: Vec<(u8, String)> = load_all_cards().values().map(|c| (c.age, c.name.clone())).collect();
+        cards.sort();
+        let cards: Vec<String> = cards.into_iter().map(|(_, name)| name).collect();
+        let index = cards.iter().enumerate().map(|(i, c)| (c.clone(), i)).collect();
+        Self { cards, index }
+    }
+
+    pub fn card_index(&self, card: &str) -> Option<usize> {
+        self.index.get(card).copied()
+    }
+
+    fn n(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn size(&self) -> usize {
+        1 + 3 * self.n() + AGES + 1 + Color::ALL.len() + 2
+    }
+
+    pub fn encode(&self, action: EnvAction) -> usize {
+        let n = self.n();
+        match action {
+            EnvAction::Draw => 0,
+            EnvAction::Meld(i) => 1 + i,
+            EnvAction::Dogma(i) => 1 + n + i,
+            EnvAction::Achieve(age) => 1 + 2 * n + (age as usize - 1),
+            EnvAction::Pick(i) => 1 + 2 * n + AGES + i,
+            EnvAction::Done => 1 + 3 * n + AGES,
+            EnvAction::Color(c) => 2 + 3 * n + AGES + c,
+            EnvAction::Confirm(yes) => 2 + 3 * n + AGES + Color::ALL.len() + usize::from(!yes),
+        }
+    }
+
+    pub fn decode(&self, index: usize) -> Option<EnvAction> {
+        let n = self.n();
+        let colors = Color::ALL.len();
+        Some(match index {
+            0 => EnvAction::Draw,
+            i if i < 1 + n => EnvAction::Meld(i - 1),
+            i if i < 1 + 2 * n => EnvAction::Dogma(i - 1 - n),
+            i if i < 1 + 2 * n + AGES => EnvAction::Achieve((i - 2 * n) as u8),
+            i if i < 1 + 3 * n + AGES => EnvAction::Pick(i - 1 - 2 * n - AGES),
+            i if i == 1 + 3 * n + AGES => EnvAction::Done,
+            i if i < 2 + 3 * n + AGES + colors => EnvAction::Color(i - 2 - 3 * n - AGES),
+            i if i < self.size() => EnvAction::Confirm(i == 2 + 3 * n + AGES + colors),
+            _ => return None,
+        })
+    }
+
+    /// Readable name of an action index, for logs and debugging.
+    pub fn describe(&self, index: usize) -> String {
+        match self.decode(index) {
+            Some(EnvAction::Draw) => "draw".to_string(),
+            Some(EnvAction::Meld(i)) => format!("meld {}", self.cards[i]),
+            Some(EnvAction::Dogma(i)) => format!("dogma {}", self.cards[i]),
+            Some(EnvAction::Achieve(age)) => format!("achieve Age {}", age),
+            Some(EnvAction::Pick(i)) => format!("pick {}", self.cards[i]),
+            Some(EnvAction::Done) => "done".to_string(),
+            Some(EnvAction::Color(c)) => format!("color {:?}", Color::ALL[c]),
+            Some(EnvAction::Confirm(yes)) => if yes { "yes" } else { "no" }.to_string(),
+            None => "invalid".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EnvConfig {
+    pub players: usize,
+    pub max_age: u8,
+    /// Seat controlled through `step`; the others are played by `opponent`.
+    /// None lets `step` play every seat (self-play).
+    pub seat: Option<usize>,
+    /// Agent spec of the other seats (see `agent::from_spec`).
+    pub opponent: String,
+    /// Moves after which the episode is truncated.
+    pub max_steps: usize,
+}
+
+impl Default for EnvConfig {
+    fn default() -> Self {
+        Self { players: 2, max_age: 10, seat: Some(0), opponent: "random".to_string(), max_steps: 5000 }
+    }
+}
+
+/// Result of one `step`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepResult {
+    /// Reward of the controlled seat, or of the seat that moved in self-play:
+    /// +1 for a win, -1 for a loss, 0 until the game ends.
+    pub reward: f32,
+    pub terminated: bool,
+    pub truncated: bool,
+}
+
+pub struct Env {
+    pub config: EnvConfig,
+    pub layout: ActionLayout,
+    pub game: GameState,
+    opponents: Vec<Option<Box<dyn Agent>>>,
+    /// `SelectCard` answer being built.
+    picks: Vec<String>,
+    steps: usize,
+}
+
+impl Env {
+    pub fn new(config: EnvConfig, seed: u64) -> Result<Self, GameError> {
+        if config.seat.is_some_and(|s| s >= config.players) {
+            return Err(GameError::InvalidConfig(format!("seat {} does not exist", config.seat.unwrap_or(0))));
+        }
+        agent::from_spec(&config.opponent, 0)?;
+        let game = Self::new_game(&config, seed)?;
+        let mut env = Self { config, layout: ActionLayout::new(), game, opponents: Vec::new(), picks: Vec::new(), steps: 0 };
+        env.reset(seed)?;
+        Ok(env)
+    }
+
+    fn new_game(config: &EnvConfig, seed: u64) -> Result<GameState, GameError> {
+        GameState::from_config(seed, GameConfig::new(config.players)?.max_age(config.max_age))
+    }
+
+    pub fn reset(&mut self, seed: u64) -> Result<(), GameError> {
+        self.game = Self::new_game(&self.config, seed)?;
+        self.opponents = (0..self.config.players).map(|pid| match self.config.seat {
+            Some(seat) if seat != pid => agent::from_spec(&self.config.opponent, seed.wrapping_add(pid as u64 + 1)).ok(),
+            _ => None,
+        }).collect();
+        self.picks.clear();
+        self.steps = 0;
+        self.play_opponents()
+    }
+
+    pub fn observation_size(&self) -> usize {
+        encode_size(self.layout.cards.len())
+    }
+
+    /// Seat whose move `step` expects.
+    pub fn current_seat(&self) -> usize {
+        self.game.acting_player()
+    }
+
+    pub fn done(&self) -> bool {
+        self.game.phase == GamePhase::GameOver || self.steps >= self.config.max_steps
+    }
+
+    /// Legal action indices of the acting seat.
+    pub fn legal_actions(&self) -> Vec<usize> {
+        if self.done() {
+            return Vec::new();
+        }
+        let layout = &self.layout;
+        let card = |id: &String| layout.card_index(id);
+        match &self.game.pending_input {
+            Some(InputRequest::SelectCard { source, min, max, .. }) => {
+                let (min, max) = ((*min).min(source.len()), (*max).min(source.len()));
+                let mut legal = Vec::new();
+                if self.picks.len() < max {
+                    legal.extend(source.iter()
+                        .filter(|c| !self.picks.contains(c))
+                        .filter_map(card)
+                        .map(|i| layout.encode(EnvAction::Pick(i))));
+                }
+                if self.picks.len() >= min {
+                    legal.push(layout.encode(EnvAction::Done));
+                }
+                legal.sort_unstable();
+                legal.dedup();
+                legal
+            }
+            Some(InputRequest::SelectColor { colors, .. }) => colors.iter()
+                .filter_map(|c| Color::ALL.iter().position(|x| x == c))
+                .map(|c| layout.encode(EnvAction::Color(c)))
+                .collect(),
+            Some(InputRequest::Confirm { .. }) => {
+                vec![layout.encode(EnvAction::Confirm(true)), layout.encode(EnvAction::Confirm(false))]
+            }
+            None => {
+                let mut legal: Vec<usize> = self.game.legal_actions().iter().filter_map(|action| match action {
+                    Action::Draw => Some(layout.encode(EnvAction::Draw)),
+                    Action::Meld(c) => card(c).map(|i| layout.encode(EnvAction::Meld(i))),
+                    Action::Dogma(c) => card(c).map(|i| layout.encode(EnvAction::Dogma(i))),
+                    Action::Achieve(target) => target.trim_start_matches("Age ").parse::<u8>().ok()
+                        .filter(|age| (1..=AGES as u8).contains(age))
+                        .map(|age| layout.encode(EnvAction::Achieve(age))),
+                    Action::ResolveInput(_) => None,
+                }).collect();
+                legal.sort_unstable();
+                legal
+            }
+        }
+    }
+
+    pub fn action_mask(&self) -> Vec<bool> {
+        let mut mask = vec![false; self.layout.size()];
+        for i in self.legal_actions() {
+            mask[i] = true;
+        }
+        mask
+    }
+
+    /// Plays action `index` for the acting seat, then the opponents until the
+    /// controlled seat must act again.
+    pub fn step(&mut self, index: usize) -> Result<StepResult, GameError> {
+        if !self.legal_actions().contains(&index) {
+            return Err(GameError::InvalidAction(format!("action {} ({}) is not legal", index, self.layout.describe(index))));
+        }
+        let actor = self.current_seat();
+        let card = |i: usize| self.layout.cards[i].clone();
+        let action = match self.layout.decode(index).expect("legal indices decode") {
+            EnvAction::Draw => Some(Action::Draw),
+            EnvAction::Meld(i) => Some(Action::Meld(card(i))),
+            EnvAction::Dogma(i) => Some(Action::Dogma(card(i))),
+            EnvAction::Achieve(age) => Some(Action::Achieve(format!("Age {}", age))),
+            EnvAction::Pick(i) => {
+                self.picks.push(card(i));
+                let full = matches!(&self.game.pending_input,
+                    Some(InputRequest::SelectCard { source, max, .. }) if self.picks.len() >= (*max).min(source.len()));
+                full.then(|| self.take_picks())
+            }
+            EnvAction::Done => Some(self.take_picks()),
+            EnvAction::Color(c) => Some(Action::ResolveInput(format!("{:?}", Color::ALL[c]))),
+            EnvAction::Confirm(yes) => Some(Action::ResolveInput(if yes { "yes" } else { "no" }.to_string())),
+        };
+        if let Some(action) = action {
+            self.game.apply_action(action)?;
+        }
+        self.steps += 1;
+        self.play_opponents()?;
+
+        let seat = self.config.seat.unwrap_or(actor);
+        let terminated = self.game.phase == GamePhase::GameOver;
+        let reward = match terminated {
+            true if self.game.winners.contains(&seat) => 1.0,
+            true => -1.0,
+            false => 0.0,
+        };
+        Ok(StepResult { reward, terminated, truncated: !terminated && self.steps >= self.config.max_steps })
+    }
+
+    fn take_picks(&mut self) -> Action {
+        Action::ResolveInput(std::mem::take(&mut self.picks).join(","))
+    }
+
+    fn play_opponents(&mut self) -> Result<(), GameError> {
+        while !self.done() {
+            let actor = self.game.acting_player();
+            let Some(bot) = self.opponents.get_mut(actor).and_then(|b| b.as_mut()) else { break };
+            let action = bot.choose(&Observation::new(&self.game, actor));
+            self.game.apply_action(action)?;
+        }
+        Ok(())
+    }
+
+    /// Fixed-size observation of `seat`: what that seat can see, seats listed from its own.
+    pub fn observe(&self, seat: usize) -> Vec<f32> {
+        let db = load_all_cards();
+        let n = self.layout.cards.len();
+        let game = &self.game;
+        let mut out = Vec::with_capacity(self.observation_size());
+        let multi_hot = |out: &mut Vec<f32>, cards: &mut dyn Iterator<Item = &String>| {
+            let start = out.len();
+            out.resize(start + n, 0.0);
+            for c in cards {
+                if let Some(i) = self.layout.card_index(c) {
+                    out[start + i] = 1.0;
+                }
+            }
+        };
+
+        // Seats, starting with the observer (absent seats stay at zero)
+        for offset in 0..MAX_PLAYERS {
+            let start = out.len();
+            if offset >= game.players.len() {
+                out.resize(start + seat_block_size(n), 0.0);
+                continue;
+            }
+            let p = &game.players[(seat + offset) % game.players.len()];
+            for color in Color::ALL {
+                let pile = p.board.get(&color);
+                let top_age = pile.and_then(|pile| pile.top()).and_then(|id| db.get(id)).map_or(0, |c| c.age);
+                out.push(top_age as f32 / AGES as f32);
+                out.push(pile.map_or(0, |pile| pile.len()) as f32 / 10.0);
+                let splay = pile.map_or(SplayDirection::None, |pile| pile.splay());
+                for dir in [SplayDirection::Left, SplayDirection::Right, SplayDirection::Up] {
+                    out.push(if splay == dir { 1.0 } else { 0.0 });
+                }
+            }
+            let icons = p.icon_totals();
+            out.extend(Symbol::ALL.iter().map(|&s| icons.get(s) as f32 / 20.0));
+            out.push(p.calculate_score() as f32 / 50.0);
+            out.push(game.achievement_count(p.id) as f32 / game.config.achievements_needed as f32);
+            out.push(p.hand.len() as f32 / 10.0);
+            out.push(p.score_pile.len() as f32 / 10.0);
+            out.push(if game.current_player == p.id { 1.0 } else { 0.0 });
+            multi_hot(&mut out, &mut p.board.values().filter_map(|pile| pile.top()));
+            // Hand ages are public, the cards only to their owner
+            let mut ages = [0.0f32; AGES];
+            for id in &p.hand {
+                if let Some(c) = db.get(id) {
+                    ages[(c.age as usize).clamp(1, AGES) - 1] += 0.2;
+                }
+            }
+            out.extend(ages);
+            debug_assert_eq!(out.len() - start, seat_block_size(n));
+        }
+
+        let me = &game.players[seat];
+        multi_hot(&mut out, &mut me.hand.iter());
+        multi_hot(&mut out, &mut me.score_pile.iter());
+
+        // Shared state
+        for age in 1..=AGES as u8 {
+            out.push(game.deck.get(&age).map_or(0, |d| d.len()) as f32 / 10.0);
+            out.push(if game.achievements.contains(&format!("Age {}", age)) { 0.0 } else { 1.0 });
+        }
+        out.push(game.turn_number as f32 / 100.0);
+        out.push(game.actions_taken as f32);
+        out.push(if game.phase == GamePhase::Setup { 1.0 } else { 0.0 });
+
+        // Pending input of this seat
+        let input = game.pending_input.as_ref().filter(|req| req.player_id() == seat);
+        let kind = match input {
+            None => 0,
+            Some(InputRequest::SelectCard { .. }) => 1,
+            Some(InputRequest::SelectColor { .. }) => 2,
+            Some(InputRequest::Confirm { .. }) => 3,
+        };
+        out.extend((0..4).map(|k| if k == kind { 1.0 } else { 0.0 }));
+        match input {
+            Some(InputRequest::SelectCard { source, min, max, .. }) => {
+                multi_hot(&mut out, &mut source.iter());
+                multi_hot(&mut out, &mut self.picks.iter());
+                out.push(*min as f32 / 10.0);
+                out.push(*max as f32 / 10.0);
+            }
+            _ => out.resize(out.len() + 2 * n + 2, 0.0),
+        }
+        let colors = match input {
+            Some(InputRequest::SelectColor { colors, .. }) => colors.clone(),
+            _ => Vec::new(),
+        };
+        out.extend(Color::ALL.iter().map(|c| if colors.contains(c) { 1.0 } else { 0.0 }));
+
+        debug_assert_eq!(out.len(), self.observation_size());
+        out
+    }
+}
+
+fn seat_block_size(cards: usize) -> usize {
+    Color::ALL.len() * 5 + Symbol::ALL.len() + 5 + cards + AGES
+}
+
+fn encode_size(cards: usize) -> usize {
+    MAX_PLAYERS * seat_block_size(cards) + 2 * cards + 2 * AGES + 3 + 4 + 2 * cards + 2 + Color::ALL.len()
+}
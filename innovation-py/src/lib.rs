@@ -0,0 +1,13 @@
+//! Gym-style environment for reinforcement learning on the Innovation engine.
+//!
+//! The environment logic lives in [`env`] and is plain Rust; the `python` feature
+//! adds the `innovation_gym` Python module (built as a wheel with maturin).
+
+pub mod env;
+#[cfg(feature = "python")]
+mod python;
+
+pub use env::{ActionLayout, Env, EnvAction, EnvConfig, StepResult};
+
+#[cfg(test)]
+mod tests;
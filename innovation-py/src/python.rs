@@ -0,0 +1,111 @@
+//! Python bindings: `innovation_gym.InnovationEnv`, following the Gymnasium API
+//! (`reset` returns `(obs, info)`, `step` returns `(obs, reward, terminated, truncated, info)`).
+
+use crate::env::{Env, EnvConfig};
+use innovation_core::errors::GameError;
+use numpy::{IntoPyArray, PyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+fn py_err(e: GameError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+#[pyclass(unsendable, name = "InnovationEnv")]
+struct PyEnv {
+    env: Env,
+    /// Seed of the next `reset()` called without one.
+    next_seed: u64,
+}
+
+impl PyEnv {
+    /// Seat whose observation `reset` and `step` return.
+    fn seat(&self) -> usize {
+        self.env.config.seat.unwrap_or_else(|| self.env.current_seat())
+    }
+
+    fn info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let info = PyDict::new(py);
+        info.set_item("action_mask", self.env.action_mask().into_pyarray(py))?;
+        info.set_item("seat", self.env.current_seat())?;
+        info.set_item("winners", self.env.game.winners.clone())?;
+        info.set_item("turn", self.env.game.turn_number)?;
+        Ok(info)
+    }
+}
+
+#[pymethods]
+impl PyEnv {
+    /// `seat=None` plays every seat through `step` (self-play); otherwise the other
+    /// seats are played by the built-in `opponent` agent.
+    #[new]
+    #[pyo3(signature = (players=2, seat=Some(0), opponent="random".to_string(), max_age=10, max_steps=5000, seed=0))]
+    fn new(players: usize, seat: Option<usize>, opponent: String, max_age: u8, max_steps: usize, seed: u64) -> PyResult<Self> {
+        let config = EnvConfig { players, max_age, seat, opponent, max_steps };
+        let env = Env::new(config, seed).map_err(py_err)?;
+        Ok(Self { env, next_seed: seed.wrapping_add(1) })
+    }
+
+    #[pyo3(signature = (seed=None))]
+    fn reset<'py>(&mut self, py: Python<'py>, seed: Option<u64>) -> PyResult<(Bound<'py, PyArray1<f32>>, Bound<'py, PyDict>)> {
+        let seed = seed.unwrap_or(self.next_seed);
+        self.next_seed = seed.wrapping_add(1);
+        self.env.reset(seed).map_err(py_err)?;
+        Ok((self.env.observe(self.seat()).into_pyarray(py), self.info(py)?))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn step<'py>(&mut self, py: Python<'py>, action: usize) -> PyResult<(Bound<'py, PyArray1<f32>>, f32, bool, bool, Bound<'py, PyDict>)> {
+        let result = self.env.step(action).map_err(py_err)?;
+        let obs = self.env.observe(self.seat()).into_pyarray(py);
+        Ok((obs, result.reward, result.terminated, result.truncated, self.info(py)?))
+    }
+
+    fn action_mask<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<bool>> {
+        self.env.action_mask().into_pyarray(py)
+    }
+
+    fn legal_actions(&self) -> Vec<usize> {
+        self.env.legal_actions()
+    }
+
+    fn observe<'py>(&self, py: Python<'py>, seat: usize) -> PyResult<Bound<'py, PyArray1<f32>>> {
+        if seat >= self.env.game.players.len() {
+            return Err(PyValueError::new_err(format!("seat {} does not exist", seat)));
+        }
+        Ok(self.env.observe(seat).into_pyarray(py))
+    }
+
+    fn describe_action(&self, index: usize) -> String {
+        self.env.layout.describe(index)
+    }
+
+    #[getter]
+    fn observation_size(&self) -> usize {
+        self.env.observation_size()
+    }
+
+    #[getter]
+    fn action_size(&self) -> usize {
+        self.env.layout.size()
+    }
+
+    #[getter]
+    fn current_seat(&self) -> usize {
+        self.env.current_seat()
+    }
+
+    /// Latest game log lines.
+    #[pyo3(signature = (lines=10))]
+    fn render(&self, lines: usize) -> String {
+        let log = &self.env.game.action_log;
+        log.to_vec_from(log.len().saturating_sub(lines)).join("\n")
+    }
+}
+
+#[pymodule]
+fn innovation_gym(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyEnv>()?;
+    Ok(())
+}
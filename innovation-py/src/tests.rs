@@ -0,0 +1,40 @@
+use crate::env::{ActionLayout, Env, EnvAction, EnvConfig};
+
+#[test]
+fn test_action_layout_round_trip() {
+    let layout = ActionLayout::new();
+    for index in 0..layout.size() {
+        let action = layout.decode(index).unwrap();
+        assert_eq!(layout.encode(action), index);
+    }
+    assert!(layout.decode(layout.size()).is_none());
+    assert_eq!(layout.decode(layout.size() - 1), Some(EnvAction::Confirm(false)));
+}
+
+#[test]
+fn test_episodes_with_masked_random_actions() {
+    for (players, seat) in [(2, Some(1)), (3, None)] {
+        let config = EnvConfig { players, seat, ..EnvConfig::default() };
+        let mut env = Env::new(config, 7).unwrap();
+        let size = env.observation_size();
+        let mut rng = 12345u64;
+        let mut last = None;
+        for _ in 0..20000 {
+            let legal = env.legal_actions();
+            if legal.is_empty() {
+                break;
+            }
+            assert!(seat.is_none_or(|s| env.current_seat() == s));
+            assert_eq!(env.observe(env.current_seat()).len(), size);
+            let mask = env.action_mask();
+            assert_eq!(mask.iter().filter(|m| **m).count(), legal.len());
+            rng = rng.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let result = env.step(legal[(rng >> 33) as usize % legal.len()]).unwrap();
+            last = Some(result);
+        }
+        let last = last.unwrap();
+        assert!(last.terminated || last.truncated);
+        assert!(last.reward != 0.0 || last.truncated);
+        assert!(env.step(0).is_err());
+    }
+}
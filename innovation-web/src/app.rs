@@ -1,28 +1,40 @@
 use leptos::prelude::*;
-use crate::components::{Header, PlayerBoard, Hand, ActionBar, Log, GameMenu};
+use crate::components::{Header, PlayerBoard, Hand, ActionBar, Log, GameMenu, AnalysisPanel};
 use crate::state::GameSignal;
-use innovation_core::GameState;
+use innovation_core::GameConfig;
 
 #[component]
 pub fn App() -> impl IntoView {
     // Create game state signal with random seed
     let seed = web_sys::js_sys::Date::now() as u64;
-    let game = GameSignal::new(seed, 2, 1);
+    // Two players, age 1 only: short games for the browser
+    let config = GameConfig::new(2).expect("2 players is valid").max_age(1);
+    let game = GameSignal::new(seed, config);
     
     // Provide game context to all children
-    provide_context(game);
+    provide_context(game.clone());
+    
+    // Opponents on top, your team at the bottom; partners side by side
+    let (own_team, opponents): (Vec<usize>, Vec<usize>) = game.game.with_untracked(|g| {
+        (0..g.players.len()).partition(|&pid| g.config.teammates(pid, 0))
+    });
     
     view! {
         <div class="app">
             <Header/>
             <GameMenu/>
             <div class="game-area">
-                <PlayerBoard player_idx=1 is_opponent=true/>
-                <PlayerBoard player_idx=0 is_opponent=false/>
+                <div class="team-row">
+                    {opponents.into_iter().map(|idx| view! { <PlayerBoard player_idx=idx is_opponent=true/> }).collect::<Vec<_>>()}
+                </div>
+                <div class="team-row">
+                    {own_team.into_iter().map(|idx| view! { <PlayerBoard player_idx=idx is_opponent=false/> }).collect::<Vec<_>>()}
+                </div>
             </div>
             <Hand/>
             <Log/>
             <ActionBar/>
+            <AnalysisPanel/>
         </div>
     }
 }
@@ -1,5 +1,7 @@
 use leptos::prelude::*;
 use crate::state::GameSignal;
+use innovation_core::actions::Action;
+use innovation_core::agent::Hint;
 
 #[component]
 pub fn ActionBar() -> impl IntoView {
@@ -13,7 +15,66 @@ pub fn ActionBar() -> impl IntoView {
                 <MeldButton/>
                 <DogmaButton/>
             </Show>
+            <HintButton/>
         </div>
+        <HintList/>
+    }
+}
+
+#[component]
+fn HintButton() -> impl IntoView {
+    let game_signal = expect_context::<GameSignal>();
+    let game_over = move || game_signal.game.with(|g| !g.winners.is_empty());
+
+    view! {
+        <button class="secondary" on:click=move |_| game_signal.hint() disabled=game_over>
+            "Conseil"
+        </button>
+    }
+}
+
+fn seats(pids: &[usize]) -> String {
+    pids.iter().map(|p| format!("P{}", p)).collect::<Vec<_>>().join(", ")
+}
+
+/// French description of an action.
+pub fn action_label(action: &Action) -> String {
+    match action {
+        Action::Draw => "Piocher".to_string(),
+        Action::Meld(card) => format!("Poser {}", card),
+        Action::Achieve(decree) if decree.starts_with("Décret") => format!("Promulguer le {}", decree.to_lowercase()),
+        Action::Achieve(age) => format!("Dominer l'âge {}", age),
+        Action::Dogma(card) => format!("Dogme {}", card),
+        Action::ResolveInput(answer) if answer.is_empty() => "Ne rien choisir".to_string(),
+        Action::ResolveInput(answer) => format!("Choisir {}", answer),
+    }
+}
+
+fn describe(hint: &Hint) -> String {
+    let mut text = format!("{} — {:.0}% de victoire", action_label(&hint.action), hint.value * 100.0);
+    if let Action::Dogma(_) = hint.action {
+        if hint.sharers.is_empty() {
+            text += ", non partagé";
+        } else {
+            text += &format!(", partagé par {}", seats(&hint.sharers));
+        }
+        if !hint.demanded.is_empty() {
+            text += &format!(", exigence contre {}", seats(&hint.demanded));
+        }
+    }
+    text
+}
+
+#[component]
+fn HintList() -> impl IntoView {
+    let game_signal = expect_context::<GameSignal>();
+
+    view! {
+        <Show when=move || game_signal.hints.with(|h| !h.is_empty())>
+            <ol class="hints">
+                {move || game_signal.hints.get().iter().map(|hint| view! { <li>{describe(hint)}</li> }).collect_view()}
+            </ol>
+        </Show>
     }
 }
 
@@ -0,0 +1,66 @@
+use leptos::prelude::*;
+use crate::state::GameSignal;
+use crate::components::action_label;
+use innovation_core::analysis::{self, AnalysisConfig, Analysis, MomentKind};
+
+/// Key moves and per-turn totals, shown once the game is over.
+#[component]
+pub fn AnalysisPanel() -> impl IntoView {
+    let game_signal = expect_context::<GameSignal>();
+    let report = Memo::new(move |_| {
+        let over = game_signal.game.with(|g| !g.winners.is_empty());
+        over.then(|| game_signal.replay.with(|r| analysis::analyze(r, &AnalysisConfig::default()).ok()))
+            .flatten()
+    });
+
+    view! {
+        {move || report.get().map(|report| view! { <AnalysisReport report=report/> })}
+    }
+}
+
+#[component]
+fn AnalysisReport(report: Analysis) -> impl IntoView {
+    let json = report.to_json();
+    let href = format!("data:application/json,{}", String::from(web_sys::js_sys::encode_uri_component(&json)));
+    let moments = report.moments.iter().map(|m| {
+        let kind = match m.kind {
+            MomentKind::Blunder => "erreur",
+            MomentKind::TurningPoint => "tournant",
+        };
+        let text = format!("Tour {}, P{} : {} — {}, victoire {:.0}% → {:.0}%",
+            m.turn, m.player, action_label(&m.action), kind, m.before * 100.0, m.after * 100.0);
+        view! { <li class=kind>{text}</li> }
+    }).collect_view();
+    let players = report.turns.first().map_or(0, |t| t.players.len());
+    let header = (0..players).map(|pid| view! { <th>{format!("P{} score", pid)}</th><th>{format!("P{} dom.", pid)}</th><th>{format!("P{} icônes", pid)}</th> }).collect_view();
+    let symbols = report.symbols.clone();
+    let rows = report.turns.iter().map(|turn| {
+        let cells = turn.players.iter().map(|p| {
+            let icons = symbols.iter().zip(&p.icons)
+                .filter(|(_, &c)| c > 0)
+                .map(|(s, c)| format!("{:?}:{}", s, c))
+                .collect::<Vec<_>>()
+                .join(" ");
+            view! { <td>{p.score}</td><td>{p.achievements}</td><td class="icons">{icons}</td> }
+        }).collect_view();
+        view! { <tr><td>{turn.turn}</td>{cells}</tr> }
+    }).collect_view();
+
+    view! {
+        <div class="analysis">
+            <h2>"📊 Analyse de la partie"</h2>
+            <Show when={
+                let empty = report.moments.is_empty();
+                move || empty
+            }>
+                <p>"Aucun coup n'a beaucoup changé le cours de la partie."</p>
+            </Show>
+            <ul class="moments">{moments}</ul>
+            <table class="turns">
+                <tr><th>"Tour"</th>{header}</tr>
+                {rows}
+            </table>
+            <a href=href download="analyse.json">"Exporter (JSON)"</a>
+        </div>
+    }
+}
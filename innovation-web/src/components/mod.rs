@@ -5,11 +5,13 @@ mod action_bar;
 mod log;
 mod pile;
 mod game_menu;
+mod analysis;
 
 pub use header::Header;
 pub use player_board::PlayerBoard;
 pub use hand::Hand;
-pub use action_bar::ActionBar;
+pub use action_bar::{action_label, ActionBar};
 pub use log::Log;
 pub use pile::Pile;
 pub use game_menu::GameMenu;
+pub use analysis::AnalysisPanel;
@@ -33,7 +33,7 @@ pub fn Pile(color: CardColor, player_idx: usize, pile_idx: usize) -> impl IntoVi
                 if let Some(card_name) = p.top() {
                     let card = db.get(card_name);
                     let age = card.map(|c| c.age).unwrap_or(0);
-                    return Some((card_name.clone(), age, p.splay, p.cards.len()));
+                    return Some((card_name.clone(), age, p.splay(), p.len()));
                 }
             }
             None
@@ -45,7 +45,7 @@ pub fn Pile(color: CardColor, player_idx: usize, pile_idx: usize) -> impl IntoVi
     };
     
     let on_click = move |_| {
-        if let Some((card_name, _, _, _)) = pile_data() {
+        if pile_data().is_some() {
             game_signal.selected_pile_idx.set(Some(pile_idx));
         }
     };
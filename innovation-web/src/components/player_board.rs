@@ -1,18 +1,17 @@
 use leptos::prelude::*;
 use crate::state::GameSignal;
 use crate::components::Pile;
-use innovation_core::{load_all_cards, Color as CardColor};
+use innovation_core::Color as CardColor;
 
 #[component]
 pub fn PlayerBoard(player_idx: usize, is_opponent: bool) -> impl IntoView {
     let game_signal = expect_context::<GameSignal>();
-    let db = load_all_cards();
     
     let player_info = move || {
         game_signal.game.with(|g| {
             let p = &g.players[player_idx];
-            let score = p.calculate_score(&db);
-            let icons = p.icon_counts(&db);
+            let score = p.calculate_score();
+            let icons = p.icon_counts();
             let icon_str: String = icons.iter()
                 .filter(|(_, &c)| c > 0)
                 .map(|(s, c)| format!("{:?}:{}", s, c))
@@ -30,7 +29,11 @@ pub fn PlayerBoard(player_idx: usize, is_opponent: bool) -> impl IntoView {
             if is_current && !is_opponent { "player-board current" } else { "player-board" }
         }>
             <div class="player-info">
-                <span>{if is_opponent { "Adversaire" } else { "Vous" }}</span>
+                <span>{match (is_opponent, player_idx) {
+                    (true, _) => format!("Adversaire (P{})", player_idx),
+                    (false, 0) => "Vous".to_string(),
+                    (false, _) => format!("Partenaire (P{})", player_idx),
+                }}</span>
                 <span>{move || {
                     let (score, hand_len, icons, _) = player_info();
                     format!("Score: {} | Main: {} | {}", score, hand_len, icons)
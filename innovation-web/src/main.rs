@@ -2,7 +2,6 @@ mod app;
 mod components;
 mod state;
 
-use leptos::prelude::*;
 use app::App;
 
 fn main() {
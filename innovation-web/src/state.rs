@@ -1,5 +1,7 @@
 use leptos::prelude::*;
-use innovation_core::{GameState, actions::Action, load_all_cards, game_state::GamePhase};
+use innovation_core::{GameConfig, GameState, actions::Action, game_state::GamePhase};
+use innovation_core::agent::{self, Budget, Hint, IsmctsConfig};
+use innovation_core::analysis::Replay;
 
 #[derive(Clone)]
 pub struct GameSignal {
@@ -9,12 +11,17 @@ pub struct GameSignal {
     pub action_log: RwSignal<Vec<String>>,
     pub show_full_log: RwSignal<bool>,
     pub seed: RwSignal<u64>,
+    /// Suggested moves for the current position, empty until asked for.
+    pub hints: RwSignal<Vec<Hint>>,
+    /// Actions played so far, for the analysis at the end of the game.
+    pub replay: RwSignal<Replay>,
 }
 
 impl GameSignal {
-    pub fn new(seed: u64, players: usize, max_age: u8) -> Self {
-        let game = GameState::new_with_max_age(seed, players, max_age);
-        let initial_log = game.action_log.clone();
+    pub fn new(seed: u64, config: GameConfig) -> Self {
+        let replay = Replay::new(seed, config.clone());
+        let game = GameState::from_config(seed, config).expect("invalid game config");
+        let initial_log = game.action_log.to_vec();
         
         Self {
             game: RwSignal::new(game),
@@ -23,6 +30,8 @@ impl GameSignal {
             action_log: RwSignal::new(initial_log),
             show_full_log: RwSignal::new(false),
             seed: RwSignal::new(seed),
+            hints: RwSignal::new(Vec::new()),
+            replay: RwSignal::new(replay),
         }
     }
     
@@ -31,14 +40,18 @@ impl GameSignal {
         let new_seed = web_sys::js_sys::Date::now() as u64;
         self.seed.set(new_seed);
         
-        let game = GameState::new_with_max_age(new_seed, 2, 1);
-        let initial_log = game.action_log.clone();
+        // Same rules as the current game
+        let config = self.game.with_untracked(|g| g.config.clone());
+        self.replay.set(Replay::new(new_seed, config.clone()));
+        let game = GameState::from_config(new_seed, config).expect("invalid game config");
+        let initial_log = game.action_log.to_vec();
         
         self.game.set(game);
         self.action_log.set(initial_log);
         self.selected_hand_idx.set(None);
         self.selected_pile_idx.set(None);
         self.show_full_log.set(false);
+        self.hints.set(Vec::new());
     }
     
     pub fn abandon_game(&self) {
@@ -52,10 +65,11 @@ impl GameSignal {
     
     pub fn apply_action(&self, action: Action) {
         self.game.update(|g| {
-            match g.apply_action(action) {
+            match g.apply_action(action.clone()) {
                 Ok(_) => {
+                    self.replay.update(|r| r.actions.push(action));
                     // Sync logs
-                    let logs: Vec<String> = g.action_log.drain(..).collect();
+                    let logs = std::mem::take(&mut g.action_log).to_vec();
                     self.action_log.update(|l| l.extend(logs));
                 }
                 Err(e) => {
@@ -63,6 +77,16 @@ impl GameSignal {
                 }
             }
         });
+        self.hints.set(Vec::new());
+    }
+
+    /// Searches the three best moves of the acting player. The browser has a single
+    /// thread and no clock, so the search runs a fixed number of iterations in place.
+    pub fn hint(&self) {
+        let config = IsmctsConfig { budget: Budget::Iterations(300), threads: 1, ..IsmctsConfig::default() };
+        let seed = self.seed.get_untracked();
+        let hints = self.game.with_untracked(|g| agent::hints(g, config, seed, 3));
+        self.hints.set(hints);
     }
     
     pub fn draw(&self) {